@@ -0,0 +1,228 @@
+//! A thin client for talking to a single API host.
+//!
+//! An [`ApiClient`] wraps a [`Client`] together with a base URL, so that
+//! requests can be made with paths relative to it, and with default
+//! authentication and headers applied to every request.
+//!
+//! ```rust
+//! # async fn run() -> Result<(), reqwest::Error> {
+//! let api = reqwest::api::ApiClient::builder("https://api.example.com/v1/")
+//!     .bearer_auth("my-token")
+//!     .build()?;
+//!
+//! // GET https://api.example.com/v1/users/42
+//! let res = api.get("users/42").send().await?;
+//! # Ok(())
+//! # }
+//! ```
+use std::fmt;
+
+use http::header::{HeaderMap, HeaderValue, AUTHORIZATION};
+use url::Position;
+
+use super::{Client, ClientBuilder, RequestBuilder};
+use crate::{IntoUrl, Method, Url};
+
+/// A `Client` bound to a base URL.
+///
+/// Cloning an `ApiClient` is cheap, and shares the underlying connection
+/// pool.
+#[derive(Clone)]
+pub struct ApiClient {
+    // built with the API's `base_url`, always ending in a `/`
+    client: Client,
+}
+
+/// A builder to configure an [`ApiClient`].
+#[must_use]
+pub struct ApiClientBuilder {
+    client: ClientBuilder,
+    base_url: crate::Result<Url>,
+    headers: HeaderMap,
+}
+
+impl ApiClient {
+    /// Creates an `ApiClientBuilder` for the API at `base_url`.
+    pub fn builder<U: IntoUrl>(base_url: U) -> ApiClientBuilder {
+        ApiClientBuilder {
+            client: Client::builder(),
+            base_url: base_url.into_url(),
+            headers: HeaderMap::new(),
+        }
+    }
+
+    /// Get the base URL that request paths are relative to.
+    pub fn base_url(&self) -> &Url {
+        self.client
+            .base_url()
+            .expect("ApiClient is built with a base URL")
+    }
+
+    /// Get the underlying `Client`.
+    pub fn client(&self) -> &Client {
+        &self.client
+    }
+
+    /// Convenience method to make a `GET` request to a path.
+    pub fn get(&self, path: &str) -> RequestBuilder {
+        self.request(Method::GET, path)
+    }
+
+    /// Convenience method to make a `POST` request to a path.
+    pub fn post(&self, path: &str) -> RequestBuilder {
+        self.request(Method::POST, path)
+    }
+
+    /// Convenience method to make a `PUT` request to a path.
+    pub fn put(&self, path: &str) -> RequestBuilder {
+        self.request(Method::PUT, path)
+    }
+
+    /// Convenience method to make a `PATCH` request to a path.
+    pub fn patch(&self, path: &str) -> RequestBuilder {
+        self.request(Method::PATCH, path)
+    }
+
+    /// Convenience method to make a `DELETE` request to a path.
+    pub fn delete(&self, path: &str) -> RequestBuilder {
+        self.request(Method::DELETE, path)
+    }
+
+    /// Start building a request with the `Method` and a path.
+    ///
+    /// The path is always resolved below the base URL, even if it starts
+    /// with a `/`, and may include a query string.
+    ///
+    /// # Errors
+    ///
+    /// This method fails if the path is an absolute URL, starts with `//`
+    /// naming another host, or has `..` segments leading out of the base
+    /// URL, so that the client's authentication is never sent elsewhere.
+    pub fn request(&self, method: Method, path: &str) -> RequestBuilder {
+        let url = resolve(self.base_url(), path);
+        RequestBuilder::new(
+            self.client.clone(),
+            url.map(|url| super::Request::new(method, url)),
+        )
+    }
+}
+
+/// Resolves `path` below `base`, which ends in a `/`.
+fn resolve(base: &Url, path: &str) -> crate::Result<Url> {
+    // `Url::join` would let these replace the scheme or host of the base.
+    // Backslashes are read as slashes in http(s) URLs.
+    let mut leading = path.chars().take_while(|&c| c == '/' || c == '\\');
+    let scheme_relative = leading.next().is_some() && leading.next().is_some();
+    if scheme_relative || Url::parse(path).is_ok() {
+        return Err(crate::error::builder("API path is not relative"));
+    }
+
+    let url = base
+        .join(path.trim_start_matches('/'))
+        .map_err(crate::error::builder)?;
+    // `..` segments are normalized away by `join`, so whatever is left must
+    // still be below the base path.
+    if url[..Position::BeforePath] != base[..Position::BeforePath]
+        || !url.path().starts_with(base.path())
+    {
+        return Err(crate::error::builder("API path leads out of the base URL"));
+    }
+    Ok(url)
+}
+
+impl fmt::Debug for ApiClient {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ApiClient")
+            .field("base_url", &self.base_url().as_str())
+            .field("client", &self.client)
+            .finish()
+    }
+}
+
+impl ApiClientBuilder {
+    /// Use HTTP basic authentication for every request.
+    pub fn basic_auth<U, P>(self, username: U, password: Option<P>) -> ApiClientBuilder
+    where
+        U: fmt::Display,
+        P: fmt::Display,
+    {
+        let mut value = crate::util::basic_auth(username, password);
+        value.set_sensitive(true);
+        self.header(AUTHORIZATION, value)
+    }
+
+    /// Use HTTP bearer authentication for every request.
+    pub fn bearer_auth<T>(self, token: T) -> ApiClientBuilder
+    where
+        T: fmt::Display,
+    {
+        match HeaderValue::try_from(format!("Bearer {token}")) {
+            Ok(mut value) => {
+                value.set_sensitive(true);
+                self.header(AUTHORIZATION, value)
+            }
+            Err(e) => self.with_error(crate::error::builder(e)),
+        }
+    }
+
+    /// Sets the default headers for every request, in addition to any
+    /// configured on the `ClientBuilder`.
+    pub fn default_headers(mut self, headers: HeaderMap) -> ApiClientBuilder {
+        for (key, value) in headers.iter() {
+            self.headers.insert(key, value.clone());
+        }
+        self
+    }
+
+    /// Use a customized `ClientBuilder` for the underlying `Client`.
+    pub fn client_builder(mut self, client: ClientBuilder) -> ApiClientBuilder {
+        self.client = client;
+        self
+    }
+
+    /// Returns an `ApiClient` that uses this builder's configuration.
+    ///
+    /// # Errors
+    ///
+    /// This method fails if the base URL is invalid, or the `Client` cannot
+    /// be built.
+    pub fn build(self) -> crate::Result<ApiClient> {
+        let mut base_url = self.base_url?;
+        if base_url.cannot_be_a_base() {
+            return Err(crate::error::builder("API base URL cannot be a base"));
+        }
+        // Without a trailing slash, joining would replace the last segment.
+        if !base_url.path().ends_with('/') {
+            let path = format!("{}/", base_url.path());
+            base_url.set_path(&path);
+        }
+
+        let client = self
+            .client
+            .base_url(base_url)
+            .default_headers(self.headers)
+            .build()?;
+        Ok(ApiClient { client })
+    }
+
+    fn header(mut self, key: http::HeaderName, value: HeaderValue) -> ApiClientBuilder {
+        self.headers.insert(key, value);
+        self
+    }
+
+    fn with_error(mut self, err: crate::Error) -> ApiClientBuilder {
+        if self.base_url.is_ok() {
+            self.base_url = Err(err);
+        }
+        self
+    }
+}
+
+impl fmt::Debug for ApiClientBuilder {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ApiClientBuilder")
+            .field("base_url", &self.base_url.as_ref().map(Url::as_str))
+            .field("client", &self.client)
+            .finish()
+    }
+}
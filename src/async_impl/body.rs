@@ -10,6 +10,7 @@ use http_body_util::combinators::BoxBody;
 use pin_project_lite::pin_project;
 #[cfg(feature = "stream")]
 use tokio::fs::File;
+use tokio::sync::OwnedSemaphorePermit;
 use tokio::time::Sleep;
 #[cfg(feature = "stream")]
 use tokio_util::io::ReaderStream;
@@ -46,9 +47,44 @@ pin_project! {
     }
 }
 
+pin_project! {
+    /// A response body holding a permit of
+    /// [`max_concurrent`](crate::ClientBuilder::max_concurrent) until it ends.
+    pub(crate) struct PermitBody<B> {
+        #[pin]
+        inner: B,
+        permit: Option<OwnedSemaphorePermit>,
+    }
+}
+
+#[cfg(feature = "stream")]
+pin_project! {
+    /// A body of a length known in advance, counted down as it's sent.
+    struct ExactLength<B> {
+        #[pin]
+        inner: B,
+        remaining: u64,
+    }
+}
+
+/// The sending half of a [`Body::channel`].
+#[cfg(feature = "stream")]
+#[cfg_attr(docsrs, doc(cfg(feature = "stream")))]
+pub struct BodySender {
+    tx: tokio::sync::mpsc::Sender<Result<Bytes, Abort>>,
+}
+
+#[cfg(feature = "stream")]
+#[derive(Debug)]
+struct Abort;
+
 /// Converts any `impl Body` into a `impl Stream` of just its DATA frames.
 #[cfg(any(feature = "stream", feature = "multipart",))]
-pub(crate) struct DataStream<B>(pub(crate) B);
+pub(crate) struct DataStream<B> {
+    body: B,
+    // The URL of the response the body belongs to, given to its errors.
+    url: Option<Box<url::Url>>,
+}
 
 impl Body {
     /// Returns a reference to the internal data of the `Body`.
@@ -61,6 +97,27 @@ impl Body {
         }
     }
 
+    /// Create a `Body` from the JSON serialization of `json`.
+    ///
+    /// Unlike [`RequestBuilder::json`](crate::RequestBuilder::json), this
+    /// doesn't set a `Content-Type` header.
+    ///
+    /// # Errors
+    ///
+    /// Serialization can fail if `T`'s implementation of `Serialize` decides to
+    /// fail, or if `T` contains a map with non-string keys.
+    ///
+    /// # Optional
+    ///
+    /// This requires the optional `json` feature enabled.
+    #[cfg(feature = "json")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "json")))]
+    pub fn from_json<T: serde::Serialize + ?Sized>(json: &T) -> crate::Result<Body> {
+        serde_json::to_vec(json)
+            .map(Body::from)
+            .map_err(crate::error::builder)
+    }
+
     /// Wrap a futures `Stream` in a box inside `Body`.
     ///
     /// # Example
@@ -116,6 +173,45 @@ impl Body {
         }
     }
 
+    /// Create a `Body` streaming the contents of an [`AsyncRead`].
+    ///
+    /// With a `len`, the request is sent with that `Content-Length`, and the
+    /// reader must provide exactly that many bytes. Otherwise the body is
+    /// streamed without a length, using chunked transfer encoding on HTTP/1.1.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use reqwest::Body;
+    /// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+    /// let file = tokio::fs::File::open("upload.bin").await?;
+    /// let len = file.metadata().await?.len();
+    /// let body = Body::from_reader(file, Some(len));
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`AsyncRead`]: tokio::io::AsyncRead
+    ///
+    /// # Optional
+    ///
+    /// This requires the `stream` feature to be enabled.
+    #[cfg(feature = "stream")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "stream")))]
+    pub fn from_reader<R>(reader: R, len: Option<u64>) -> Body
+    where
+        R: tokio::io::AsyncRead + Send + 'static,
+    {
+        let body = Body::stream(ReaderStream::new(reader));
+        match len {
+            Some(len) => Body::wrap(ExactLength {
+                inner: body,
+                remaining: len,
+            }),
+            None => body,
+        }
+    }
+
     pub(crate) fn empty() -> Body {
         Body::reusable(Bytes::new())
     }
@@ -154,6 +250,46 @@ impl Body {
         }
     }
 
+    /// Create a body streaming the chunks pushed through a [`BodySender`].
+    ///
+    /// This is useful to produce upload data incrementally, such as from
+    /// another task. Only one chunk is buffered, so [`BodySender::send`] waits
+    /// while the previous chunk hasn't been taken by the connection yet.
+    /// Dropping the sender ends the body.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # async fn run() -> Result<(), reqwest::Error> {
+    /// let (mut tx, body) = reqwest::Body::channel();
+    /// tokio::spawn(async move {
+    ///     for chunk in ["hello", " ", "world"] {
+    ///         if tx.send(chunk).await.is_err() {
+    ///             break;
+    ///         }
+    ///     }
+    /// });
+    ///
+    /// let res = reqwest::Client::new()
+    ///     .post("http://httpbin.org/post")
+    ///     .body(body)
+    ///     .send()
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Optional
+    ///
+    /// This requires the `stream` feature to be enabled.
+    #[cfg(feature = "stream")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "stream")))]
+    pub fn channel() -> (BodySender, Body) {
+        let (tx, mut rx) = tokio::sync::mpsc::channel(1);
+        let body = Body::stream(futures_util::stream::poll_fn(move |cx| rx.poll_recv(cx)));
+        (BodySender { tx }, body)
+    }
+
     pub(crate) fn try_clone(&self) -> Option<Body> {
         match self.inner {
             Inner::Reusable(ref chunk) => Some(Body::reusable(chunk.clone())),
@@ -163,11 +299,15 @@ impl Body {
 
     #[cfg(feature = "multipart")]
     pub(crate) fn into_stream(self) -> DataStream<Body> {
-        DataStream(self)
+        DataStream::new(self)
     }
 
-    #[cfg(feature = "multipart")]
-    pub(crate) fn content_length(&self) -> Option<u64> {
+    /// Returns the length of the body, if it is known.
+    ///
+    /// This is the length of in-memory bodies, and `None` for streams, unless
+    /// the stream knows its exact length.
+    #[allow(clippy::len_without_is_empty)]
+    pub fn len(&self) -> Option<u64> {
         match self.inner {
             Inner::Reusable(ref bytes) => Some(bytes.len() as u64),
             Inner::Streaming(ref body) => body.size_hint().exact(),
@@ -175,6 +315,45 @@ impl Body {
     }
 }
 
+// ===== impl BodySender =====
+
+#[cfg(feature = "stream")]
+impl BodySender {
+    /// Send a chunk of the body.
+    ///
+    /// This waits until the previous chunk has been taken. It fails if the
+    /// body was dropped, such as when the request failed or was cancelled.
+    pub async fn send(&mut self, chunk: impl Into<Bytes>) -> crate::Result<()> {
+        self.tx
+            .send(Ok(chunk.into()))
+            .await
+            .map_err(|_| crate::error::body("body channel closed"))
+    }
+
+    /// Abort the body, failing the request instead of ending the body
+    /// normally.
+    pub async fn abort(self) {
+        let _ = self.tx.send(Err(Abort)).await;
+    }
+}
+
+#[cfg(feature = "stream")]
+impl fmt::Debug for BodySender {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("BodySender").finish()
+    }
+}
+
+#[cfg(feature = "stream")]
+impl fmt::Display for Abort {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("body sender aborted")
+    }
+}
+
+#[cfg(feature = "stream")]
+impl std::error::Error for Abort {}
+
 impl Default for Body {
     #[inline]
     fn default() -> Body {
@@ -284,6 +463,33 @@ impl HttpBody for Body {
     }
 }
 
+// ===== impl ExactLength =====
+
+#[cfg(feature = "stream")]
+impl<B> HttpBody for ExactLength<B>
+where
+    B: HttpBody<Data = Bytes>,
+{
+    type Data = Bytes;
+    type Error = B::Error;
+
+    fn poll_frame(
+        self: Pin<&mut Self>,
+        cx: &mut Context,
+    ) -> Poll<Option<Result<hyper::body::Frame<Self::Data>, Self::Error>>> {
+        let this = self.project();
+        let frame = ready!(this.inner.poll_frame(cx));
+        if let Some(data) = frame.as_ref().and_then(|f| f.as_ref().ok()?.data_ref()) {
+            *this.remaining = this.remaining.saturating_sub(data.len() as u64);
+        }
+        Poll::Ready(frame)
+    }
+
+    fn size_hint(&self) -> http_body::SizeHint {
+        http_body::SizeHint::with_exact(self.remaining)
+    }
+}
+
 // ===== impl TotalTimeoutBody =====
 
 pub(crate) fn total_timeout<B>(body: B, timeout: Pin<Box<Sleep>>) -> TotalTimeoutBody<B> {
@@ -301,6 +507,66 @@ pub(crate) fn with_read_timeout<B>(body: B, timeout: Duration) -> ReadTimeoutBod
     }
 }
 
+/// A response body warning when it's dropped without having been read, for
+/// [`warn_on_unread_body`](crate::ClientBuilder::warn_on_unread_body).
+pub(crate) struct UnreadWarningBody {
+    inner: ResponseBody,
+    url: url::Url,
+    read: bool,
+}
+
+pub(crate) fn warn_if_unread(body: ResponseBody, url: url::Url) -> UnreadWarningBody {
+    UnreadWarningBody {
+        inner: body,
+        url,
+        read: false,
+    }
+}
+
+impl hyper::body::Body for UnreadWarningBody {
+    type Data = Bytes;
+    type Error = Box<dyn std::error::Error + Send + Sync>;
+
+    fn poll_frame(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context,
+    ) -> Poll<Option<Result<hyper::body::Frame<Self::Data>, Self::Error>>> {
+        self.read = true;
+        Pin::new(&mut self.inner).poll_frame(cx)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> http_body::SizeHint {
+        self.inner.size_hint()
+    }
+
+    #[inline]
+    fn is_end_stream(&self) -> bool {
+        self.inner.is_end_stream()
+    }
+}
+
+impl Drop for UnreadWarningBody {
+    fn drop(&mut self) {
+        if self.read || self.inner.is_end_stream() {
+            return;
+        }
+        // The query is left out, since it may hold secrets.
+        let (host, path) = (self.url.host_str().unwrap_or_default(), self.url.path());
+        #[cfg(feature = "tracing")]
+        tracing::warn!(host, path, "response body dropped unread");
+        #[cfg(not(feature = "tracing"))]
+        log::warn!("response body from {host}{path} dropped unread");
+    }
+}
+
+pub(crate) fn with_permit<B>(body: B, permit: OwnedSemaphorePermit) -> PermitBody<B> {
+    PermitBody {
+        inner: body,
+        permit: Some(permit),
+    }
+}
+
 impl<B> hyper::body::Body for TotalTimeoutBody<B>
 where
     B: hyper::body::Body,
@@ -379,6 +645,37 @@ where
     }
 }
 
+impl<B> hyper::body::Body for PermitBody<B>
+where
+    B: hyper::body::Body,
+{
+    type Data = B::Data;
+    type Error = B::Error;
+
+    fn poll_frame(
+        self: Pin<&mut Self>,
+        cx: &mut Context,
+    ) -> Poll<Option<Result<hyper::body::Frame<Self::Data>, Self::Error>>> {
+        let this = self.project();
+        let item = ready!(this.inner.poll_frame(cx));
+        // the body won't be read any further after its end or an error
+        if !matches!(item, Some(Ok(_))) {
+            this.permit.take();
+        }
+        Poll::Ready(item)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> http_body::SizeHint {
+        self.inner.size_hint()
+    }
+
+    #[inline]
+    fn is_end_stream(&self) -> bool {
+        self.inner.is_end_stream()
+    }
+}
+
 pub(crate) type ResponseBody =
     http_body_util::combinators::BoxBody<Bytes, Box<dyn std::error::Error + Send + Sync>>;
 
@@ -423,29 +720,58 @@ where
 
 // ===== impl DataStream =====
 
+#[cfg(any(feature = "stream", feature = "multipart",))]
+impl<B> DataStream<B> {
+    pub(crate) fn new(body: B) -> DataStream<B> {
+        DataStream { body, url: None }
+    }
+
+    #[cfg(feature = "stream")]
+    pub(crate) fn with_url(mut self, url: Box<url::Url>) -> DataStream<B> {
+        self.url = Some(url);
+        self
+    }
+}
+
 #[cfg(any(feature = "stream", feature = "multipart",))]
 impl<B> futures_core::Stream for DataStream<B>
 where
-    B: HttpBody<Data = Bytes> + Unpin,
+    B: HttpBody<Data = Bytes, Error = crate::Error> + Unpin,
 {
-    type Item = Result<Bytes, B::Error>;
+    type Item = crate::Result<Bytes>;
 
     fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
         loop {
-            return match ready!(Pin::new(&mut self.0).poll_frame(cx)) {
+            return match ready!(Pin::new(&mut self.body).poll_frame(cx)) {
                 Some(Ok(frame)) => {
-                    // skip non-data frames
-                    if let Ok(buf) = frame.into_data() {
-                        Poll::Ready(Some(Ok(buf)))
-                    } else {
-                        continue;
+                    // skip non-data frames, and empty ones, so that every
+                    // item is at least a byte long
+                    match frame.into_data() {
+                        Ok(buf) if !buf.is_empty() => Poll::Ready(Some(Ok(buf))),
+                        _ => continue,
                     }
                 }
-                Some(Err(err)) => Poll::Ready(Some(Err(err))),
+                Some(Err(err)) => match self.url {
+                    Some(ref url) => Poll::Ready(Some(Err(err.if_no_url(|| (**url).clone())))),
+                    None => Poll::Ready(Some(Err(err))),
+                },
                 None => Poll::Ready(None),
             };
         }
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.body.is_end_stream() {
+            return (0, Some(0));
+        }
+        // Each item is at least a byte long, so a known remaining length is
+        // an upper bound on the number of items.
+        match self.body.size_hint().exact() {
+            Some(0) => (0, Some(0)),
+            Some(remaining) => (1, usize::try_from(remaining).ok()),
+            None => (0, None),
+        }
+    }
 }
 
 // ===== impl IntoBytesBody =====
@@ -517,4 +843,37 @@ mod tests {
         assert!(stream_body.is_end_stream());
         assert_eq!(stream_body.size_hint().exact(), Some(0));
     }
+
+    #[cfg(feature = "stream")]
+    #[test]
+    fn body_len() {
+        assert_eq!(Body::from("abc").len(), Some(3));
+
+        let chunks: Vec<Result<_, std::io::Error>> = vec![Ok("abc")];
+        let stream_body = Body::wrap_stream(futures_util::stream::iter(chunks));
+        assert_eq!(stream_body.len(), None);
+    }
+
+    #[cfg(feature = "stream")]
+    #[test]
+    fn body_from_reader_len() {
+        let body = Body::from_reader(&b"hello"[..], Some(5));
+        assert_eq!(body.len(), Some(5));
+        assert_eq!(body.as_bytes(), None);
+
+        let body = Body::from_reader(&b"hello"[..], None);
+        assert_eq!(body.len(), None);
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn body_from_json() {
+        let body = Body::from_json(&serde_json::json!({ "hello": [1, 2] })).unwrap();
+        assert_eq!(body.as_bytes(), Some(&br#"{"hello":[1,2]}"#[..]));
+        assert_eq!(body.len(), Some(15));
+
+        let mut map = std::collections::HashMap::new();
+        map.insert(vec![1u8], "not a string key");
+        assert!(Body::from_json(&map).unwrap_err().is_builder());
+    }
 }
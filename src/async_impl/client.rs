@@ -10,6 +10,8 @@ use std::{collections::HashMap, convert::TryInto, net::SocketAddr};
 use std::{fmt, str};
 
 use super::decoder::Accepts;
+use super::early_hints::{EarlyHintsCallback, Link};
+use super::rate_limit::RateLimiter;
 use super::request::{Request, RequestBuilder};
 use super::response::Response;
 use super::Body;
@@ -17,12 +19,19 @@ use super::Body;
 use crate::async_impl::h3_client::connect::{H3ClientConfig, H3Connector};
 #[cfg(feature = "http3")]
 use crate::async_impl::h3_client::H3Client;
-use crate::config::{RequestConfig, TotalTimeout};
+#[cfg(feature = "cache")]
+use crate::cache::{self, HttpCache};
+use crate::config::{
+    Batched, Deadline, NoDefaultHeaders, RemovedHeaders, RequestConfig, TotalTimeout,
+};
+#[cfg(feature = "json")]
+use crate::config::JsonAccept;
 #[cfg(unix)]
 use crate::connect::uds::UnixSocketProvider;
 use crate::connect::{
     sealed::{Conn, Unnameable},
-    BoxedConnectorLayer, BoxedConnectorService, Connector, ConnectorBuilder,
+    BoxedConnectorLayer, BoxedConnectorService, ConnectionReused, ConnectionUses, Connector,
+    ConnectorBuilder,
 };
 #[cfg(feature = "cookies")]
 use crate::cookie;
@@ -32,6 +41,7 @@ use crate::cookie::service::CookieService;
 use crate::dns::hickory::HickoryDnsResolver;
 use crate::dns::{gai::GaiResolver, DnsResolverWithOverrides, DynResolver, Resolve};
 use crate::error::{self, BoxError};
+use crate::interceptor::Interceptor;
 use crate::into_url::try_uri;
 use crate::proxy::Matcher as ProxyMatcher;
 use crate::redirect::{self, TowerRedirectPolicy};
@@ -39,6 +49,7 @@ use crate::redirect::{self, TowerRedirectPolicy};
 use crate::tls::CertificateRevocationList;
 #[cfg(feature = "__tls")]
 use crate::tls::{self, TlsBackend};
+use crate::transport::Transport;
 #[cfg(feature = "__tls")]
 use crate::Certificate;
 #[cfg(any(feature = "native-tls", feature = "__rustls"))]
@@ -46,7 +57,8 @@ use crate::Identity;
 use crate::{IntoUrl, Method, Proxy, Url};
 
 use http::header::{
-    Entry, HeaderMap, HeaderValue, ACCEPT, ACCEPT_ENCODING, PROXY_AUTHORIZATION, RANGE, USER_AGENT,
+    Entry, HeaderMap, HeaderName, HeaderValue, ACCEPT, ACCEPT_ENCODING, AUTHORIZATION,
+    PROXY_AUTHORIZATION, RANGE, USER_AGENT,
 };
 use http::uri::Scheme;
 use http::Uri;
@@ -58,7 +70,9 @@ use pin_project_lite::pin_project;
 use quinn::TransportConfig;
 #[cfg(feature = "http3")]
 use quinn::VarInt;
-use tokio::time::Sleep;
+use serde::Serialize;
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+use tokio::time::{Instant, Sleep};
 use tower::util::BoxCloneSyncServiceLayer;
 use tower::{Layer, Service};
 use tower_http::follow_redirect::FollowRedirect;
@@ -85,8 +99,10 @@ pub struct Client {
 #[must_use]
 pub struct ClientBuilder {
     config: Config,
+    error: Option<crate::Error>,
 }
 
+#[derive(Clone)]
 enum HttpVersionPref {
     Http1,
     #[cfg(feature = "http2")]
@@ -99,11 +115,12 @@ enum HttpVersionPref {
 #[derive(Clone)]
 struct HyperService {
     hyper: HyperClient,
+    transport: Option<Arc<dyn Transport>>,
 }
 
 impl Service<hyper::Request<crate::async_impl::body::Body>> for HyperService {
     type Error = crate::Error;
-    type Response = http::Response<hyper::body::Incoming>;
+    type Response = http::Response<super::body::ResponseBody>;
     type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send + Sync>>;
 
     fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
@@ -111,16 +128,35 @@ impl Service<hyper::Request<crate::async_impl::body::Body>> for HyperService {
     }
 
     fn call(&mut self, req: hyper::Request<crate::async_impl::body::Body>) -> Self::Future {
+        if let Some(transport) = self.transport.clone() {
+            return Box::pin(sync_wrapper::SyncFuture::new(async move {
+                let res = transport.send(Request::try_from(req)?).await?;
+                Ok(http::Response::from(res).map(super::body::boxed))
+            }));
+        }
+
         let clone = self.hyper.clone();
         let mut inner = std::mem::replace(&mut self.hyper, clone);
-        Box::pin(async move { inner.call(req).await.map_err(crate::error::request) })
+        Box::pin(async move {
+            let mut res = inner.call(req).await.map_err(crate::error::request)?;
+            if let Some(uses) = res.extensions_mut().remove::<ConnectionUses>() {
+                res.extensions_mut().insert(ConnectionReused(uses.record()));
+            }
+            Ok(res.map(super::body::boxed))
+        })
     }
 }
 
+const DEFAULT_USER_AGENT: &str = concat!("reqwest/", env!("CARGO_PKG_VERSION"));
+
+#[derive(Clone)]
 struct Config {
     // NOTE: When adding a new field, update `fmt::Debug for ClientBuilder`
     accepts: Accepts,
     headers: HeaderMap,
+    default_user_agent: bool,
+    warn_on_unread_body: bool,
+    query: Vec<(String, String)>,
     #[cfg(feature = "__tls")]
     hostname_verification: bool,
     #[cfg(feature = "__tls")]
@@ -204,8 +240,20 @@ struct Config {
     #[cfg(feature = "cookies")]
     cookie_store: Option<Arc<dyn cookie::CookieStore>>,
     hickory_dns: bool,
-    error: Option<crate::Error>,
     https_only: bool,
+    http_allowed_hosts: Arc<Vec<String>>,
+    sensitive_headers: Vec<HeaderName>,
+    json_accept: bool,
+    early_hints: Option<EarlyHintsCallback>,
+    interceptors: Vec<Arc<dyn Interceptor>>,
+    // the limiter and semaphore are created by `build`, so that clients
+    // derived with `Client::builder_from` get their own
+    rate_limit: Option<(u32, Duration)>,
+    max_concurrent: Option<usize>,
+    #[cfg(feature = "cache")]
+    cache: Option<Arc<dyn HttpCache>>,
+    transport: Option<Arc<dyn Transport>>,
+    base_url: Option<Url>,
     #[cfg(feature = "http3")]
     tls_enable_early_data: bool,
     #[cfg(feature = "http3")]
@@ -244,10 +292,13 @@ impl ClientBuilder {
         headers.insert(ACCEPT, HeaderValue::from_static("*/*"));
 
         ClientBuilder {
+            error: None,
             config: Config {
-                error: None,
                 accepts: Accepts::default(),
                 headers,
+                default_user_agent: false,
+                warn_on_unread_body: false,
+                query: Vec::new(),
                 #[cfg(feature = "__tls")]
                 hostname_verification: true,
                 #[cfg(feature = "__tls")]
@@ -332,6 +383,17 @@ impl ClientBuilder {
                 #[cfg(feature = "cookies")]
                 cookie_store: None,
                 https_only: false,
+                http_allowed_hosts: Arc::new(Vec::new()),
+                sensitive_headers: Vec::new(),
+                json_accept: true,
+                early_hints: None,
+                interceptors: Vec::new(),
+                rate_limit: None,
+                max_concurrent: None,
+                #[cfg(feature = "cache")]
+                cache: None,
+                transport: None,
+                base_url: None,
                 dns_overrides: HashMap::new(),
                 #[cfg(feature = "http3")]
                 tls_enable_early_data: false,
@@ -365,12 +427,20 @@ impl ClientBuilder {
     /// This method fails if a TLS backend cannot be initialized, or the resolver
     /// cannot load the system configuration.
     pub fn build(self) -> crate::Result<Client> {
-        let config = self.config;
-
-        if let Some(err) = config.error {
+        if let Some(err) = self.error {
             return Err(err);
         }
 
+        // kept for `Client::builder_from`
+        let original = self.config.clone();
+        let mut config = self.config;
+
+        if config.default_user_agent && !config.headers.contains_key(USER_AGENT) {
+            config
+                .headers
+                .insert(USER_AGENT, HeaderValue::from_static(DEFAULT_USER_AGENT));
+        }
+
         let mut proxies = config.proxies;
         if config.auto_sys_proxy {
             proxies.push(ProxyMatcher::system());
@@ -531,7 +601,9 @@ impl ClientBuilder {
                             // TLS v1.3. This would be entirely reasonable,
                             // native-tls just doesn't support it.
                             // https://github.com/sfackler/rust-native-tls/issues/140
-                            crate::error::builder("invalid minimum TLS version for backend")
+                            crate::error::builder(
+                                "TLS 1.3 can't be the minimum version with the native-tls backend",
+                            )
                         })?;
                         tls.min_protocol_version(Some(protocol));
                     }
@@ -542,7 +614,9 @@ impl ClientBuilder {
                             // We could arguably do max_protocol_version(None), given
                             // that 1.4 does not exist yet, but that'd get messy in the
                             // future.
-                            crate::error::builder("invalid maximum TLS version for backend")
+                            crate::error::builder(
+                                "TLS 1.3 can't be the maximum version with the native-tls backend",
+                            )
                         })?;
                         tls.max_protocol_version(Some(protocol));
                     }
@@ -711,20 +785,24 @@ impl ClientBuilder {
                     }
 
                     if versions.is_empty() {
-                        return Err(crate::error::builder("empty supported tls versions"));
+                        return Err(crate::error::builder(
+                            "rustls supports no TLS version between the minimum and maximum",
+                        ));
                     }
 
                     // Allow user to have installed a runtime default.
-                    // If not, we use ring.
-                    let provider = rustls::crypto::CryptoProvider::get_default()
-                        .map(|arc| arc.clone())
-                        .unwrap_or_else(|| {
-                            #[cfg(not(feature = "__rustls-ring"))]
-                            panic!("No provider set");
-
-                            #[cfg(feature = "__rustls-ring")]
-                            Arc::new(rustls::crypto::ring::default_provider())
-                        });
+                    // If not, we use ring, or fail if it isn't enabled.
+                    let provider = match rustls::crypto::CryptoProvider::get_default() {
+                        Some(provider) => provider.clone(),
+                        #[cfg(feature = "__rustls-ring")]
+                        None => Arc::new(rustls::crypto::ring::default_provider()),
+                        #[cfg(not(feature = "__rustls-ring"))]
+                        None => {
+                            return Err(crate::error::builder(
+                                "no rustls crypto provider installed",
+                            ));
+                        }
+                    };
 
                     // Build TLS config
                     let signature_algorithms = provider.signature_verification_algorithms;
@@ -962,12 +1040,15 @@ impl ClientBuilder {
         let hyper_client = builder.build(connector_builder.build(config.connector_layers));
         let hyper_service = HyperService {
             hyper: hyper_client,
+            transport: config.transport,
         };
 
         let redirect_policy = {
             let mut p = TowerRedirectPolicy::new(config.redirect_policy);
             p.with_referer(config.referer)
-                .with_https_only(config.https_only);
+                .with_https_only(config.https_only)
+                .with_http_allowed_hosts(config.http_allowed_hosts.clone())
+                .with_early_hints(config.early_hints.clone());
             p
         };
 
@@ -997,7 +1078,12 @@ impl ClientBuilder {
                     }
                     None => None,
                 },
-                headers: config.headers,
+                headers: {
+                    let mut headers = config.headers;
+                    crate::util::mark_sensitive(&mut headers, &config.sensitive_headers);
+                    headers
+                },
+                query: config.query,
                 referer: config.referer,
                 read_timeout: config.read_timeout,
                 total_timeout: RequestConfig::new(config.timeout),
@@ -1006,7 +1092,21 @@ impl ClientBuilder {
                 proxies_maybe_http_auth,
                 proxies_maybe_http_custom_headers,
                 https_only: config.https_only,
+                http_allowed_hosts: config.http_allowed_hosts,
+                sensitive_headers: config.sensitive_headers,
+                json_accept: config.json_accept,
+                warn_on_unread_body: config.warn_on_unread_body,
+                early_hints: config.early_hints,
+                interceptors: config.interceptors,
+                rate_limit: config
+                    .rate_limit
+                    .map(|(requests, per)| Arc::new(RateLimiter::new(requests, per))),
+                max_concurrent: config.max_concurrent.map(|max| Arc::new(Semaphore::new(max))),
+                #[cfg(feature = "cache")]
+                cache: config.cache,
+                base_url: config.base_url,
                 redirect_policy_desc,
+                config: original,
             }),
         })
     }
@@ -1043,11 +1143,25 @@ impl ClientBuilder {
                 self.config.headers.insert(USER_AGENT, value);
             }
             Err(e) => {
-                self.config.error = Some(crate::error::builder(e.into()));
+                self.error = Some(crate::error::builder(e.into()));
             }
         };
         self
     }
+
+    /// Send a `User-Agent` of `reqwest/<version>` when no other one is set.
+    ///
+    /// The default is sent unless [`user_agent`](ClientBuilder::user_agent)
+    /// or [`default_headers`](ClientBuilder::default_headers) set one, no
+    /// matter in which order they're called. A request setting its own
+    /// `User-Agent` still replaces it.
+    ///
+    /// Default is `false`, sending no `User-Agent` at all.
+    pub fn default_user_agent_if_unset(mut self, enabled: bool) -> ClientBuilder {
+        self.config.default_user_agent = enabled;
+        self
+    }
+
     /// Sets the default headers for every request.
     ///
     /// # Example
@@ -1078,6 +1192,132 @@ impl ClientBuilder {
         self
     }
 
+    /// Adds a default header for every request, keeping the default values
+    /// it already has.
+    ///
+    /// Where [`default_headers`](ClientBuilder::default_headers) replaces a
+    /// default header, this allows one that can repeat, like `Accept`, to
+    /// have several values. As with other default headers, a request that
+    /// sets the header itself doesn't get any of them.
+    ///
+    /// Appending to `Accept` keeps the client's own `*/*` default, so replace
+    /// that first if it isn't wanted.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use reqwest::header::{self, ACCEPT};
+    /// # fn doc() -> Result<(), reqwest::Error> {
+    /// let mut headers = header::HeaderMap::new();
+    /// headers.insert(ACCEPT, header::HeaderValue::from_static("application/json"));
+    ///
+    /// let client = reqwest::Client::builder()
+    ///     .default_headers(headers)
+    ///     .default_header_append(ACCEPT, "text/plain")
+    ///     .build()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn default_header_append<K, V>(mut self, key: K, value: V) -> ClientBuilder
+    where
+        HeaderName: TryFrom<K>,
+        <HeaderName as TryFrom<K>>::Error: Into<http::Error>,
+        HeaderValue: TryFrom<V>,
+        <HeaderValue as TryFrom<V>>::Error: Into<http::Error>,
+    {
+        match <HeaderName as TryFrom<K>>::try_from(key) {
+            Ok(key) => match <HeaderValue as TryFrom<V>>::try_from(value) {
+                Ok(value) => {
+                    self.config.headers.append(key, value);
+                }
+                Err(e) => self.error = Some(crate::error::builder(e.into())),
+            },
+            Err(e) => self.error = Some(crate::error::builder(e.into())),
+        }
+        self
+    }
+
+    /// Sets a default `Authorization: Bearer` header for every request.
+    ///
+    /// Like other default headers, it isn't added to a request that sets its
+    /// own `Authorization` header, such as with
+    /// [`RequestBuilder::bearer_auth`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # fn doc() -> Result<(), reqwest::Error> {
+    /// let client = reqwest::Client::builder()
+    ///     .bearer_auth("my-token")
+    ///     .build()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Building the `Client` fails if the token isn't a valid header value.
+    pub fn bearer_auth<T>(mut self, token: T) -> ClientBuilder
+    where
+        T: fmt::Display,
+    {
+        match HeaderValue::try_from(format!("Bearer {token}")) {
+            Ok(mut value) => {
+                value.set_sensitive(true);
+                self.config.headers.insert(AUTHORIZATION, value);
+            }
+            Err(e) => {
+                self.error = Some(crate::error::builder(e));
+            }
+        }
+        self
+    }
+
+    /// Sets default query parameters to add to the URL of every request.
+    ///
+    /// A default parameter is only added if the request's URL doesn't already
+    /// have a parameter with the same name. Calling this again adds to the
+    /// parameters set before.
+    ///
+    /// The parameters are added when a [`RequestBuilder`] builds or sends its
+    /// request, so [`Request::url`] shows them. A `Request` created otherwise
+    /// gets them when it's executed.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # async fn doc() -> Result<(), reqwest::Error> {
+    /// let client = reqwest::Client::builder()
+    ///     .default_query(&[("api_key", "secret")])
+    ///     .build()?;
+    /// // GET https://api.example.com/search?q=rust&api_key=secret
+    /// let res = client
+    ///     .get("https://api.example.com/search")
+    ///     .query(&[("q", "rust")])
+    ///     .send()
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Building the `Client` fails if `query` can't be serialized as query
+    /// parameters.
+    pub fn default_query<T: Serialize + ?Sized>(mut self, query: &T) -> ClientBuilder {
+        match serde_urlencoded::to_string(query) {
+            Ok(encoded) => {
+                self.config
+                    .query
+                    .extend(url::form_urlencoded::parse(encoded.as_bytes()).into_owned());
+            }
+            Err(e) => {
+                self.error = Some(crate::error::builder(e));
+            }
+        }
+        self
+    }
+
     /// Enable a persistent cookie store for the client.
     ///
     /// Cookies received in responses will be preserved and included in
@@ -1328,6 +1568,28 @@ impl ClientBuilder {
         self
     }
 
+    /// Add the proxies configured by environment variables to the list of
+    /// proxies the `Client` will use.
+    ///
+    /// This reads `HTTP_PROXY`, `HTTPS_PROXY`, `ALL_PROXY` and `NO_PROXY`
+    /// (or their lowercase versions) when this is called. `NO_PROXY` is
+    /// a comma-separated list of hosts, IP networks, and domain suffixes like
+    /// `.example.com` or `*.example.com`, which bypass the proxies.
+    ///
+    /// The "system" proxy, used when no proxy is added, already includes
+    /// these variables; this is for using them along with other proxies, or
+    /// without the platform's proxy settings.
+    ///
+    /// # Note
+    ///
+    /// Like adding any proxy, this disables the automatic usage of the
+    /// "system" proxy.
+    pub fn proxy_from_env(mut self) -> ClientBuilder {
+        self.config.proxies.push(ProxyMatcher::env());
+        self.config.auto_sys_proxy = false;
+        self
+    }
+
     /// Clear all `Proxies`, so `Client` will use no proxy anymore.
     ///
     /// # Note
@@ -1346,7 +1608,11 @@ impl ClientBuilder {
     /// Enables a total request timeout.
     ///
     /// The timeout is applied from when the request starts connecting until the
-    /// response body has finished. Also considered a total deadline.
+    /// response body has finished. Also considered a total deadline: it isn't
+    /// reset when a redirect is followed.
+    ///
+    /// A timeout too large to be represented, such as `Duration::MAX`,
+    /// saturates and never elapses.
     ///
     /// Default is no timeout.
     pub fn timeout(mut self, timeout: Duration) -> ClientBuilder {
@@ -1576,6 +1842,9 @@ impl ClientBuilder {
 
     /// Set whether sockets have `TCP_NODELAY` enabled.
     ///
+    /// Disabling it lets small writes be coalesced into fewer packets, at the
+    /// cost of latency, which matters most for streamed bodies.
+    ///
     /// Default is `true`.
     pub fn tcp_nodelay(mut self, enabled: bool) -> ClientBuilder {
         self.config.nodelay = enabled;
@@ -2100,6 +2369,250 @@ impl ClientBuilder {
         self
     }
 
+    /// Allow requests to these hosts to use plain `http`, even when
+    /// [`https_only`](ClientBuilder::https_only) is enabled.
+    ///
+    /// This is meant for development against a local server, such as
+    /// `localhost` or `127.0.0.1`. Hosts are compared case-insensitively,
+    /// without their port. It applies to redirects as well.
+    ///
+    /// Defaults to no hosts.
+    pub fn http_allowed_hosts(mut self, hosts: Vec<String>) -> ClientBuilder {
+        self.config.http_allowed_hosts = Arc::new(hosts);
+        self
+    }
+
+    /// Treat the values of these headers as sensitive, like those of
+    /// `Authorization`, `Proxy-Authorization`, `Cookie` and `Set-Cookie`.
+    ///
+    /// Their values are marked with [`HeaderValue::set_sensitive`] in the
+    /// default headers, in every request the client sends, and in every
+    /// response it receives, which leaves them out of `Debug` output.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # fn doc() -> Result<(), reqwest::Error> {
+    /// let client = reqwest::Client::builder()
+    ///     .sensitive_headers([reqwest::header::HeaderName::from_static("x-api-key")])
+    ///     .build()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn sensitive_headers<I>(mut self, names: I) -> ClientBuilder
+    where
+        I: IntoIterator<Item = HeaderName>,
+    {
+        self.config.sensitive_headers.extend(names);
+        self
+    }
+
+    /// Set a base URL that the URLs of requests are resolved against.
+    ///
+    /// URLs passed to [`Client::request`] and its shorthands may then be
+    /// relative, such as `"/v1/users"`. They are joined to the base like
+    /// links in a web page, with [`Url::join`](crate::Url::join), so an
+    /// absolute URL replaces the base entirely, and a relative path replaces
+    /// the last segment of the base path unless it ends with a `/`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn doc() -> Result<(), reqwest::Error> {
+    /// let client = reqwest::Client::builder()
+    ///     .base_url("https://api.example.com")
+    ///     .build()?;
+    ///
+    /// // GET https://api.example.com/v1/users
+    /// let req = client.get("/v1/users").build()?;
+    /// # assert_eq!(req.url().as_str(), "https://api.example.com/v1/users");
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Building the `Client` fails if the base URL is invalid.
+    pub fn base_url<U: IntoUrl>(mut self, base_url: U) -> ClientBuilder {
+        match base_url.into_url() {
+            Ok(url) if url.cannot_be_a_base() => {
+                self.error = Some(crate::error::builder("base URL cannot be a base"));
+            }
+            Ok(url) => {
+                self.config.base_url = Some(url);
+            }
+            Err(e) => {
+                self.error = Some(e);
+            }
+        }
+        self
+    }
+
+    #[cfg(feature = "blocking")]
+    pub(crate) fn get_base_url(&self) -> Option<&Url> {
+        self.config.base_url.as_ref()
+    }
+
+    #[cfg(feature = "blocking")]
+    pub(crate) fn get_default_query(&self) -> &[(String, String)] {
+        &self.config.query
+    }
+
+    /// Set whether requests built with [`RequestBuilder::json`] are sent
+    /// with an `Accept: application/json` header, when they don't have an
+    /// `Accept` header of their own. It takes the place of the `Accept`
+    /// among the default headers.
+    ///
+    /// Default is `true`.
+    ///
+    /// # Optional
+    ///
+    /// This requires the optional `json` feature enabled.
+    #[cfg(feature = "json")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "json")))]
+    pub fn json_accept(mut self, enabled: bool) -> ClientBuilder {
+        self.config.json_accept = enabled;
+        self
+    }
+
+    /// Log a warning when a response body is dropped without being read.
+    ///
+    /// A `Response` dropped before any of [`bytes`](crate::Response::bytes),
+    /// [`text`](crate::Response::text), [`chunk`](crate::Response::chunk) and
+    /// the like started reading its body holds on to the connection for
+    /// nothing, and often hints at a bug. The warning is emitted with
+    /// `tracing` if the `tracing` feature is enabled, and `log` otherwise.
+    /// Bodies that are known to be empty, and responses to `HEAD` requests,
+    /// never warn.
+    ///
+    /// Default is `false`.
+    pub fn warn_on_unread_body(mut self, enabled: bool) -> ClientBuilder {
+        self.config.warn_on_unread_body = enabled;
+        self
+    }
+
+    /// Call a function with the preload links of each `103 Early Hints`
+    /// response received before a final response.
+    ///
+    /// Servers use Early Hints to tell the client which subresources the
+    /// final response will need, so they can be fetched while the server is
+    /// still preparing it. Only links with `rel=preload` are passed on, with
+    /// their URLs resolved against the request URL. The callback isn't
+    /// called if there are none.
+    ///
+    /// Informational responses are currently only received over HTTP/1.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn doc() -> Result<(), reqwest::Error> {
+    /// let client = reqwest::Client::builder()
+    ///     .on_early_hints(|links| {
+    ///         for link in links {
+    ///             println!("preload {} as {:?}", link.url(), link.as_destination());
+    ///         }
+    ///     })
+    ///     .build()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn on_early_hints<F>(mut self, callback: F) -> ClientBuilder
+    where
+        F: Fn(&[Link]) + Send + Sync + 'static,
+    {
+        self.config.early_hints = Some(Arc::new(callback));
+        self
+    }
+
+    /// Add an [`Interceptor`] called around every request this client
+    /// executes.
+    ///
+    /// Interceptors see requests in the order they were added, and responses
+    /// in the reverse order. See the [`interceptor`](crate::interceptor)
+    /// module for an example.
+    pub fn with_interceptor(mut self, interceptor: Arc<dyn Interceptor>) -> ClientBuilder {
+        self.config.interceptors.push(interceptor);
+        self
+    }
+
+    /// Limit the client to sending `requests` requests `per` period.
+    ///
+    /// Requests are throttled with a token bucket: up to `requests` of them
+    /// are sent right away, and then one more every `per / requests`.
+    /// Sending waits until the bucket has a token, which counts towards the
+    /// [`timeout`](ClientBuilder::timeout). A request that couldn't get one
+    /// before its timeout fails right away, without waiting.
+    ///
+    /// A request takes its token when it's first polled, after getting its
+    /// [`max_concurrent`](ClientBuilder::max_concurrent) slot, if any.
+    /// Redirects and retries of a request don't take another token.
+    ///
+    /// Default is no limit.
+    ///
+    /// # Errors
+    ///
+    /// Building the `Client` fails if `requests` is zero.
+    pub fn rate_limit(mut self, requests: u32, per: Duration) -> ClientBuilder {
+        if requests == 0 {
+            self.error = Some(crate::error::builder("rate limit of zero requests"));
+        } else {
+            self.config.rate_limit = Some((requests, per));
+        }
+        self
+    }
+
+    /// Limit the number of requests this client has in flight at once.
+    ///
+    /// A request over the limit waits for another one to finish, which counts
+    /// towards the [`timeout`](ClientBuilder::timeout). A request is in
+    /// flight until its `Response` is dropped or its body fully read, so
+    /// holding on to a streamed body keeps its slot taken.
+    ///
+    /// The requests of a [`Client::execute_all`] batch are the exception:
+    /// they free their slot as soon as their response headers arrive, so the
+    /// limit doesn't bound how many of their bodies are read at once.
+    ///
+    /// Default is no limit.
+    ///
+    /// # Errors
+    ///
+    /// Building the `Client` fails if `max` is zero.
+    pub fn max_concurrent(mut self, max: usize) -> ClientBuilder {
+        if max == 0 {
+            self.error = Some(crate::error::builder("max concurrent requests of zero"));
+        } else {
+            self.config.max_concurrent = Some(max.min(Semaphore::MAX_PERMITS));
+        }
+        self
+    }
+
+    /// Cache the responses to `GET` requests in the given [`HttpCache`].
+    ///
+    /// A request for a URL with a fresh response in the cache is answered
+    /// from it, without a network call, and a stale one is revalidated. See
+    /// the [`cache`](crate::cache) module for which responses are stored.
+    ///
+    /// # Optional
+    ///
+    /// This requires the optional `cache` feature to be enabled.
+    #[cfg(feature = "cache")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "cache")))]
+    pub fn cache(mut self, cache: Arc<dyn HttpCache>) -> ClientBuilder {
+        self.config.cache = Some(cache);
+        self
+    }
+
+    /// Send requests through the given [`Transport`] instead of the network.
+    ///
+    /// Redirects, retries, cookies, timeouts, and decompression still apply,
+    /// with each request sent through the transport, while connection
+    /// settings such as proxies and TLS are unused. See the
+    /// [`transport`](crate::transport) module.
+    pub fn transport(mut self, transport: Arc<dyn Transport>) -> ClientBuilder {
+        self.config.transport = Some(transport);
+        self
+    }
+
     #[doc(hidden)]
     #[cfg(feature = "hickory-dns")]
     #[cfg_attr(docsrs, doc(cfg(feature = "hickory-dns")))]
@@ -2379,6 +2892,37 @@ impl Client {
         ClientBuilder::new()
     }
 
+    /// Creates a `ClientBuilder` with the same configuration as this
+    /// `Client`.
+    ///
+    /// This is useful to create a client that differs from an existing one in
+    /// only a few settings. The new client gets its own connection pool, and
+    /// its own budgets for [`rate_limit`](ClientBuilder::rate_limit) and
+    /// [`max_concurrent`](ClientBuilder::max_concurrent), but shares shared
+    /// state that was configured, such as a cookie store or a DNS resolver.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # fn doc() -> Result<(), reqwest::Error> {
+    /// let client = reqwest::Client::builder()
+    ///     .timeout(std::time::Duration::from_secs(10))
+    ///     .build()?;
+    ///
+    /// let uploads = client
+    ///     .builder_from()
+    ///     .timeout(std::time::Duration::from_secs(300))
+    ///     .build()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn builder_from(&self) -> ClientBuilder {
+        ClientBuilder {
+            config: self.inner.config.clone(),
+            error: None,
+        }
+    }
+
     /// Convenience method to make a `GET` request to a URL.
     ///
     /// # Errors
@@ -2388,6 +2932,41 @@ impl Client {
         self.request(Method::GET, url)
     }
 
+    /// Convenience method to make a `GET` request to a URL expanded from a
+    /// template.
+    ///
+    /// Each `{name}` placeholder in `template` is replaced by the matching
+    /// value in `params`, percent-encoded so that characters such as `/`,
+    /// `?` or spaces can't change the structure of the URL.
+    ///
+    /// If the client has a [`base_url`](ClientBuilder::base_url), the expanded `Url` is
+    /// resolved against it, as with [`request`](Client::request).
+    ///
+    /// # Errors
+    ///
+    /// This method fails if a placeholder has no matching parameter, or the
+    /// expanded `Url` cannot be parsed.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::collections::HashMap;
+    /// # async fn run() -> Result<(), reqwest::Error> {
+    /// let params = HashMap::from([("id", "42")]);
+    /// let res = reqwest::Client::new()
+    ///     .get_template("https://api.example.com/users/{id}", &params)
+    ///     .send()
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn get_template(&self, template: &str, params: &HashMap<&str, &str>) -> RequestBuilder {
+        let req = crate::util::expand_url_template(template, params)
+            .and_then(|url| crate::into_url::resolve(self.inner.base_url.as_ref(), url))
+            .map(|url| Request::new(Method::GET, url));
+        RequestBuilder::new(self.clone(), req)
+    }
+
     /// Convenience method to make a `POST` request to a URL.
     ///
     /// # Errors
@@ -2438,14 +3017,60 @@ impl Client {
     /// Returns a `RequestBuilder`, which will allow setting headers and
     /// the request body before sending.
     ///
+    /// If the client has a [`base_url`](ClientBuilder::base_url), the `Url`
+    /// is resolved against it.
+    ///
     /// # Errors
     ///
     /// This method fails whenever the supplied `Url` cannot be parsed.
     pub fn request<U: IntoUrl>(&self, method: Method, url: U) -> RequestBuilder {
-        let req = url.into_url().map(move |url| Request::new(method, url));
+        let req = crate::into_url::resolve(self.inner.base_url.as_ref(), url)
+            .map(move |url| Request::new(method, url));
         RequestBuilder::new(self.clone(), req)
     }
 
+    /// Start building a `Request` with the `Method` and an already parsed
+    /// `Url`.
+    ///
+    /// Unlike [`request()`](Client::request), this skips converting and
+    /// checking the URL, and the client's base URL isn't applied. An
+    /// unsupported URL, such as one without a host, is still rejected when
+    /// the request is sent.
+    pub fn request_url(&self, method: Method, url: Url) -> RequestBuilder {
+        RequestBuilder::new(self.clone(), Ok(Request::new(method, url)))
+    }
+
+    /// Returns a new `Client` with `headers` as its default headers, sharing
+    /// this client's connection pool and all other configuration.
+    ///
+    /// The given headers replace the existing default headers entirely. This
+    /// is much cheaper than building a new `Client`, and useful when
+    /// different callers, such as tenants, need their own default headers
+    /// but should still reuse connections.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use reqwest::header;
+    /// # fn run() -> Result<(), reqwest::Error> {
+    /// let client = reqwest::Client::new();
+    ///
+    /// let mut headers = header::HeaderMap::new();
+    /// headers.insert("x-tenant", header::HeaderValue::from_static("acme"));
+    /// let tenant = client.with_headers(headers);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_headers(&self, headers: HeaderMap) -> Client {
+        let mut inner = ClientRef::clone(&self.inner);
+        // kept in the config too, for `builder_from`
+        inner.config.headers = headers.clone();
+        inner.headers = headers;
+        Client {
+            inner: Arc::new(inner),
+        }
+    }
+
     /// Executes a `Request`.
     ///
     /// A `Request` can be built manually with `Request::new()` or obtained
@@ -2465,33 +3090,177 @@ impl Client {
         self.execute_request(request)
     }
 
-    pub(super) fn execute_request(&self, req: Request) -> Pending {
-        let (method, url, mut headers, body, version, extensions) = req.pieces();
+    /// Executes several `Request`s concurrently.
+    ///
+    /// The results are in the same order as the requests, and a request
+    /// failing doesn't affect the others.
+    ///
+    /// With [`ClientBuilder::max_concurrent`], no more than that many of the
+    /// requests are in flight at once. Unlike requests sent one by one,
+    /// those of a batch free their slot as soon as their response headers
+    /// arrive, since the batch can't wait on its own unread responses. The
+    /// limit therefore doesn't bound how many of the batch's bodies are
+    /// downloaded at once.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # async fn run() -> Result<(), reqwest::Error> {
+    /// let client = reqwest::Client::new();
+    /// let requests = ["https://hyper.rs", "https://tokio.rs"]
+    ///     .into_iter()
+    ///     .map(|url| client.get(url).build())
+    ///     .collect::<Result<Vec<_>, _>>()?;
+    ///
+    /// for res in client.execute_all(requests).await {
+    ///     println!("{}", res?.status());
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn execute_all(&self, requests: Vec<Request>) -> Vec<crate::Result<Response>> {
+        // Each request is its own task, so that only those that were woken
+        // are polled, and dropping the batch aborts all of them.
+        let mut tasks = tokio::task::JoinSet::new();
+        let mut results = Vec::with_capacity(requests.len());
+        for (i, mut req) in requests.into_iter().enumerate() {
+            *RequestConfig::<Batched>::get_mut(req.extensions_mut()) = Some(());
+            let url = req.url().clone();
+            let pending = self.execute_request(req);
+            tasks.spawn(async move { (i, pending.await) });
+            results.push(Err(error::request("request was cancelled").with_url(url)));
+        }
+        while let Some(joined) = tasks.join_next().await {
+            match joined {
+                Ok((i, res)) => results[i] = res,
+                Err(err) if err.is_panic() => std::panic::resume_unwind(err.into_panic()),
+                // the runtime is shutting down
+                Err(_) => (),
+            }
+        }
+        results
+    }
+
+    pub(super) fn execute_request(&self, mut req: Request) -> Pending {
+        for interceptor in &self.inner.interceptors {
+            interceptor.on_request(&mut req);
+        }
+
+        #[cfg(any(feature = "gzip", feature = "brotli"))]
+        match super::encoder::compress_body(&mut req) {
+            Ok(None) => (),
+            Ok(Some(encoding)) => {
+                let client = self.clone();
+                return Pending {
+                    inner: PendingInner::Boxed(Box::pin(sync_wrapper::SyncFuture::new(
+                        async move {
+                            let req = super::encoder::compress_blocking(req, encoding).await?;
+                            client.execute_prepared(req).await
+                        },
+                    ))),
+                };
+            }
+            Err(err) => return Pending::new_err(err),
+        }
+
+        self.execute_prepared(req)
+    }
+
+    // The rest of `execute_request`, once interceptors have run and the body
+    // is compressed.
+    fn execute_prepared(&self, req: Request) -> Pending {
+        let timeout_disabled = req.is_timeout_disabled();
+
+        let (method, mut url, mut headers, body, version, extensions) = req.pieces();
         if url.scheme() != "http" && url.scheme() != "https" {
             return Pending::new_err(error::url_bad_scheme(url));
         }
 
         // check if we're in https_only mode and check the scheme of the current URL
-        if self.inner.https_only && url.scheme() != "https" {
+        if crate::util::is_insecure_disallowed(
+            &url,
+            self.inner.https_only,
+            &self.inner.http_allowed_hosts,
+        ) {
             return Pending::new_err(error::url_bad_scheme(url));
         }
 
         // insert default headers in the request headers
-        // without overwriting already appended headers.
-        for (key, value) in &self.inner.headers {
-            if let Entry::Vacant(entry) = headers.entry(key) {
-                entry.insert(value.clone());
+        // without overwriting already appended headers,
+        // or adding back ones the request removed.
+        let removed = RequestConfig::<RemovedHeaders>::get(&extensions)
+            .map(Vec::as_slice)
+            .unwrap_or_default();
+        let no_default_headers = RequestConfig::<NoDefaultHeaders>::get(&extensions).is_some();
+        let hold_permit = RequestConfig::<Batched>::get(&extensions).is_none();
+        // before the defaults, which have an `Accept` of their own
+        #[cfg(feature = "json")]
+        if self.inner.json_accept
+            && RequestConfig::<JsonAccept>::get(&extensions).is_some()
+            && !headers.contains_key(ACCEPT)
+            && !removed.contains(&ACCEPT)
+        {
+            headers.insert(ACCEPT, HeaderValue::from_static("application/json"));
+        }
+        if !no_default_headers {
+            for key in self.inner.headers.keys() {
+                if removed.contains(key) {
+                    continue;
+                }
+                if let Entry::Vacant(entry) = headers.entry(key) {
+                    let mut values = self.inner.headers.get_all(key).iter().cloned();
+                    let first = values.next().expect("a key has at least one value");
+                    let mut entry = entry.insert_entry(first);
+                    for value in values {
+                        entry.append(value);
+                    }
+                }
             }
         }
 
+        crate::util::mark_sensitive(&mut headers, &self.inner.sensitive_headers);
+
+        // likewise for default query parameters, in case the request
+        // wasn't built by a `RequestBuilder` of this client
+        self.merge_default_query(&mut url);
+
         let accept_encoding = self.inner.accepts.as_str();
 
         if let Some(accept_encoding) = accept_encoding {
-            if !headers.contains_key(ACCEPT_ENCODING) && !headers.contains_key(RANGE) {
+            if !no_default_headers
+                && !headers.contains_key(ACCEPT_ENCODING)
+                && !headers.contains_key(RANGE)
+                && !removed.contains(&ACCEPT_ENCODING)
+            {
                 headers.insert(ACCEPT_ENCODING, HeaderValue::from_static(accept_encoding));
             }
         }
 
+        #[cfg(feature = "cache")]
+        let cache = match self.inner.cache {
+            Some(ref cache) if body.is_none() && cache::is_cacheable_request(&method, &headers) => {
+                match cache::lookup(&**cache, &url, &mut headers) {
+                    cache::Lookup::Hit(res) => {
+                        for interceptor in self.inner.interceptors.iter().rev() {
+                            interceptor.on_response(&res);
+                        }
+                        return Pending {
+                            inner: PendingInner::Boxed(Box::pin(sync_wrapper::SyncFuture::new(
+                                std::future::ready(Ok(res)),
+                            ))),
+                        }
+                    }
+                    cache::Lookup::Miss(stale) => Some((
+                        cache.clone(),
+                        url.clone(),
+                        stale,
+                        cache::has_credentials(&headers),
+                    )),
+                }
+            }
+            _ => None,
+        };
+
         let uri = match try_uri(&url) {
             Ok(uri) => uri,
             _ => return Pending::new_err(error::url_invalid_uri(url)),
@@ -2506,7 +3275,36 @@ impl Client {
             .method(method.clone())
             .uri(uri)
             .version(version);
-
+        #[cfg(feature = "cookies")]
+        let builder = match extensions.get::<cookie::NoCookies>() {
+            Some(&no_cookies) => builder.extension(no_cookies),
+            None => builder,
+        };
+        #[cfg(feature = "cookies")]
+        let builder = match extensions.get::<cookie::Credentials>() {
+            Some(&credentials) => builder.extension(credentials),
+            None => builder,
+        };
+        let builder = match extensions.get::<redirect::RedirectMode>() {
+            Some(&mode) => builder.extension(mode),
+            None => builder,
+        };
+        let redirects = redirect::RedirectRecorder::default();
+        let builder = builder.extension(redirects.clone());
+
+        let start = Instant::now();
+        // The query is left out, as are headers, since they may hold secrets.
+        #[cfg(feature = "tracing")]
+        let span = tracing::debug_span!(
+            "request",
+            method = %method,
+            host = url.host_str().unwrap_or_default(),
+            path = url.path(),
+            status = tracing::field::Empty,
+            elapsed_ms = tracing::field::Empty,
+        );
+        #[cfg(not(feature = "tracing"))]
+        let span = ();
         let in_flight = match version {
             #[cfg(feature = "http3")]
             http::Version::HTTP_3 if self.inner.h3_client.is_some() => {
@@ -2523,13 +3321,30 @@ impl Client {
             }
         };
 
-        let total_timeout = self
-            .inner
-            .total_timeout
-            .fetch(&extensions)
-            .copied()
-            .map(tokio::time::sleep)
-            .map(Box::pin);
+        let timeout = if timeout_disabled {
+            None
+        } else {
+            self.inner.total_timeout.fetch(&extensions).copied()
+        };
+        // A single deadline covers every redirect hop, and the body. A
+        // timeout too large to be represented never elapses.
+        let deadline = timeout.and_then(|timeout| start.checked_add(timeout));
+        let deadline = match RequestConfig::<Deadline>::get(&extensions) {
+            Some(&at) => {
+                let at = Instant::from_std(at);
+                Some(deadline.map_or(at, |deadline| deadline.min(at)))
+            }
+            None => deadline,
+        };
+        let total_timeout = deadline.map(tokio::time::sleep_until).map(Box::pin);
+        let acquire_permit = self.inner.max_concurrent.clone().map(|semaphore| {
+            Box::pin(sync_wrapper::SyncFuture::new(async move {
+                semaphore
+                    .acquire_owned()
+                    .await
+                    .expect("max_concurrent semaphore is never closed")
+            })) as AcquirePermit
+        });
 
         let read_timeout_fut = self
             .inner
@@ -2537,7 +3352,7 @@ impl Client {
             .map(tokio::time::sleep)
             .map(Box::pin);
 
-        Pending {
+        let pending = Pending {
             inner: PendingInner::Request(Box::pin(PendingRequest {
                 method,
                 url,
@@ -2549,8 +3364,39 @@ impl Client {
                 total_timeout,
                 read_timeout_fut,
                 read_timeout: self.inner.read_timeout,
+                start,
+                span,
+                redirects,
+                acquire_permit,
+                permit: None,
+                hold_permit,
+                take_token: self.inner.rate_limit.is_some(),
+                deadline,
+                token_wait: None,
             })),
+        };
+
+        #[cfg(feature = "cache")]
+        if let Some((cache, url, stale, credentialed)) = cache {
+            return Pending {
+                inner: PendingInner::Boxed(Box::pin(sync_wrapper::SyncFuture::new(cache::store(
+                    cache,
+                    url,
+                    stale,
+                    credentialed,
+                    pending,
+                )))),
+            };
         }
+        pending
+    }
+
+    pub(super) fn merge_default_query(&self, url: &mut Url) {
+        crate::util::merge_default_query(url, &self.inner.query);
+    }
+
+    pub(super) fn base_url(&self) -> Option<&Url> {
+        self.inner.base_url.as_ref()
     }
 
     fn proxy_auth(&self, dst: &Uri, headers: &mut HeaderMap) {
@@ -2667,7 +3513,58 @@ impl Config {
             f.field("referer", &true);
         }
 
-        f.field("default_headers", &self.headers);
+        if !self.sensitive_headers.is_empty() {
+            f.field("sensitive_headers", &self.sensitive_headers);
+        }
+
+        let mut headers = self.headers.clone();
+        crate::util::mark_sensitive(&mut headers, &self.sensitive_headers);
+        f.field("default_headers", &crate::util::RedactedHeaders(&headers));
+
+        if self.default_user_agent {
+            f.field("default_user_agent", &true);
+        }
+
+        if self.warn_on_unread_body {
+            f.field("warn_on_unread_body", &true);
+        }
+
+        if !self.query.is_empty() {
+            f.field("default_query", &self.query);
+        }
+
+        if !self.json_accept {
+            f.field("json_accept", &false);
+        }
+
+        if self.early_hints.is_some() {
+            f.field("early_hints", &true);
+        }
+
+        if !self.interceptors.is_empty() {
+            f.field("interceptors", &self.interceptors.len());
+        }
+
+        if self.rate_limit.is_some() {
+            f.field("rate_limit", &true);
+        }
+
+        if self.max_concurrent.is_some() {
+            f.field("max_concurrent", &true);
+        }
+
+        #[cfg(feature = "cache")]
+        if self.cache.is_some() {
+            f.field("cache", &true);
+        }
+
+        if self.transport.is_some() {
+            f.field("transport", &true);
+        }
+
+        if let Some(ref base_url) = self.base_url {
+            f.field("base_url", &base_url.as_str());
+        }
 
         if self.http1_title_case_headers {
             f.field("http1_title_case_headers", &true);
@@ -2722,8 +3619,10 @@ impl Config {
             f.field("interface", v);
         }
 
-        if self.nodelay {
-            f.field("tcp_nodelay", &true);
+        f.field("tcp_nodelay", &self.nodelay);
+
+        if let Some(ref d) = self.tcp_keepalive {
+            f.field("tcp_keepalive", d);
         }
 
         #[cfg(feature = "__tls")]
@@ -2785,11 +3684,13 @@ type LayeredService<T> = FollowRedirect<
 >;
 type LayeredFuture<T> = <LayeredService<T> as Service<http::Request<Body>>>::Future;
 
+#[derive(Clone)]
 struct ClientRef {
     accepts: Accepts,
     #[cfg(feature = "cookies")]
     cookie_store: Option<Arc<dyn cookie::CookieStore>>,
     headers: HeaderMap,
+    query: Vec<(String, String)>,
     hyper: LayeredService<HyperService>,
     #[cfg(feature = "http3")]
     h3_client: Option<LayeredService<H3Client>>,
@@ -2800,7 +3701,19 @@ struct ClientRef {
     proxies_maybe_http_auth: bool,
     proxies_maybe_http_custom_headers: bool,
     https_only: bool,
+    http_allowed_hosts: Arc<Vec<String>>,
+    sensitive_headers: Vec<HeaderName>,
+    json_accept: bool,
+    warn_on_unread_body: bool,
+    early_hints: Option<EarlyHintsCallback>,
+    interceptors: Vec<Arc<dyn Interceptor>>,
+    rate_limit: Option<Arc<RateLimiter>>,
+    max_concurrent: Option<Arc<Semaphore>>,
+    #[cfg(feature = "cache")]
+    cache: Option<Arc<dyn HttpCache>>,
+    base_url: Option<Url>,
     redirect_policy_desc: Option<String>,
+    config: Config,
 }
 
 impl ClientRef {
@@ -2829,7 +3742,47 @@ impl ClientRef {
             f.field("referer", &true);
         }
 
-        f.field("default_headers", &self.headers);
+        if !self.json_accept {
+            f.field("json_accept", &false);
+        }
+
+        if self.early_hints.is_some() {
+            f.field("early_hints", &true);
+        }
+
+        if !self.interceptors.is_empty() {
+            f.field("interceptors", &self.interceptors.len());
+        }
+
+        if self.rate_limit.is_some() {
+            f.field("rate_limit", &true);
+        }
+
+        if self.max_concurrent.is_some() {
+            f.field("max_concurrent", &true);
+        }
+
+        #[cfg(feature = "cache")]
+        if self.cache.is_some() {
+            f.field("cache", &true);
+        }
+
+        if let Some(ref base_url) = self.base_url {
+            f.field("base_url", &base_url.as_str());
+        }
+
+        if !self.sensitive_headers.is_empty() {
+            f.field("sensitive_headers", &self.sensitive_headers);
+        }
+
+        f.field(
+            "default_headers",
+            &crate::util::RedactedHeaders(&self.headers),
+        );
+
+        if !self.query.is_empty() {
+            f.field("default_query", &self.query);
+        }
 
         self.total_timeout.fmt_as_field(f);
 
@@ -2849,6 +3802,8 @@ pin_project! {
 enum PendingInner {
     Request(Pin<Box<PendingRequest>>),
     Error(Option<crate::Error>),
+    #[cfg(any(feature = "gzip", feature = "brotli", feature = "cache"))]
+    Boxed(Pin<Box<dyn Future<Output = Result<Response, crate::Error>> + Send + Sync>>),
 }
 
 pin_project! {
@@ -2866,9 +3821,32 @@ pin_project! {
         #[pin]
         read_timeout_fut: Option<Pin<Box<Sleep>>>,
         read_timeout: Option<Duration>,
+        start: Instant,
+        span: RequestSpan,
+        redirects: redirect::RedirectRecorder,
+
+        // for `max_concurrent`, a permit is acquired before sending
+        acquire_permit: Option<AcquirePermit>,
+        permit: Option<OwnedSemaphorePermit>,
+        // whether the response body keeps the permit until it's read
+        hold_permit: bool,
+        // for `rate_limit`, a token is taken on the first poll with a permit,
+        // and sending waits until it's due
+        take_token: bool,
+        deadline: Option<Instant>,
+        token_wait: Option<Pin<Box<Sleep>>>,
     }
 }
 
+type AcquirePermit = Pin<Box<dyn Future<Output = OwnedSemaphorePermit> + Send + Sync>>;
+
+// A zero-sized placeholder without the `tracing` feature, since fields of a
+// `pin_project!` struct can't be `cfg`d out.
+#[cfg(feature = "tracing")]
+type RequestSpan = tracing::Span;
+#[cfg(not(feature = "tracing"))]
+type RequestSpan = ();
+
 enum ResponseFuture {
     Default(LayeredFuture<HyperService>),
     #[cfg(feature = "http3")]
@@ -2911,6 +3889,8 @@ impl Future for Pending {
             PendingInner::Error(ref mut err) => Poll::Ready(Err(err
                 .take()
                 .expect("Pending error polled more than once"))),
+            #[cfg(any(feature = "gzip", feature = "brotli", feature = "cache"))]
+            PendingInner::Boxed(ref mut fut) => fut.as_mut().poll(cx),
         }
     }
 }
@@ -2918,7 +3898,39 @@ impl Future for Pending {
 impl Future for PendingRequest {
     type Output = Result<Response, crate::Error>;
 
+    #[cfg(feature = "tracing")]
     fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let span = self.span.clone();
+        let _entered = span.enter();
+        let result = ready!(self.as_mut().poll_inner(cx));
+        span.record("elapsed_ms", self.start.elapsed().as_millis() as u64);
+        match result {
+            Ok(ref res) => {
+                span.record("status", res.status().as_u16());
+            }
+            // Errors are displayed with their URL, so only their kind is
+            // logged.
+            Err(ref err) => tracing::debug!(
+                timeout = err.is_timeout(),
+                connect = err.is_connect(),
+                redirect = err.is_redirect(),
+                "request failed"
+            ),
+        }
+        Poll::Ready(result)
+    }
+
+    #[cfg(not(feature = "tracing"))]
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.poll_inner(cx)
+    }
+}
+
+impl PendingRequest {
+    fn poll_inner(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Result<Response, crate::Error>> {
         if let Some(delay) = self.as_mut().total_timeout().as_mut().as_pin_mut() {
             if let Poll::Ready(()) = delay.poll(cx) {
                 return Poll::Ready(Err(
@@ -2935,12 +3947,38 @@ impl Future for PendingRequest {
             }
         }
 
+        let this = self.as_mut().project();
+        if let Some(acquire) = this.acquire_permit {
+            *this.permit = Some(ready!(acquire.as_mut().poll(cx)));
+            *this.acquire_permit = None;
+        }
+
+        if *this.take_token {
+            *this.take_token = false;
+            let limiter = this.client.rate_limit.as_ref().expect("rate limited client");
+            match limiter.acquire(*this.deadline) {
+                None => {
+                    return Poll::Ready(Err(
+                        crate::error::request(crate::error::TimedOut).with_url(this.url.clone())
+                    ));
+                }
+                Some(at) if at > Instant::now() => {
+                    *this.token_wait = Some(Box::pin(tokio::time::sleep_until(at)));
+                }
+                Some(_) => (),
+            }
+        }
+        if let Some(wait) = this.token_wait {
+            ready!(wait.as_mut().poll(cx));
+            *this.token_wait = None;
+        }
+
         let res = match self.as_mut().in_flight().get_mut() {
             ResponseFuture::Default(r) => match ready!(Pin::new(r).poll(cx)) {
                 Err(e) => {
                     return Poll::Ready(Err(e.if_no_url(|| self.url.clone())));
                 }
-                Ok(res) => res.map(super::body::boxed),
+                Ok(res) => res,
             },
             #[cfg(feature = "http3")]
             ResponseFuture::H3(r) => match ready!(Pin::new(r).poll(cx)) {
@@ -2950,6 +3988,12 @@ impl Future for PendingRequest {
                 Ok(res) => res,
             },
         };
+        let mut res = match self.as_mut().project().permit.take() {
+            Some(permit) if self.hold_permit => {
+                res.map(|body| super::body::boxed(super::body::with_permit(body, permit)))
+            }
+            _ => res,
+        };
 
         if let Some(url) = &res
             .extensions()
@@ -2961,13 +4005,43 @@ impl Future for PendingRequest {
             }
         };
 
+        if self.client.warn_on_unread_body && self.method != Method::HEAD {
+            let url = self.url.clone();
+            res = res.map(|body| super::body::boxed(super::body::warn_if_unread(body, url)));
+        }
+
+        // hyper already rejects these for HTTP/1 and HTTP/2, but not every
+        // response goes through its parser.
+        if crate::util::has_conflicting_content_length(res.headers()) {
+            return Poll::Ready(Err(crate::error::request(
+                "conflicting Content-Length headers in response",
+            )
+            .with_url(self.url.clone())));
+        }
+
+        let redirects = self.redirects.take();
+        if !redirects.is_empty() {
+            res.extensions_mut()
+                .insert(redirect::RedirectChain(redirects));
+        }
+
+        crate::util::mark_sensitive(res.headers_mut(), &self.client.sensitive_headers);
+
+        #[cfg(feature = "websocket")]
+        let res = crate::websocket::remember_key(res, &self.headers);
+
         let res = Response::new(
             res,
             self.url.clone(),
             self.client.accepts,
             self.total_timeout.take(),
             self.read_timeout,
+            self.start.elapsed(),
+            self.method == Method::HEAD,
         );
+        for interceptor in self.client.interceptors.iter().rev() {
+            interceptor.on_response(&res);
+        }
         Poll::Ready(Ok(res))
     }
 }
@@ -2981,6 +4055,8 @@ impl fmt::Debug for Pending {
                 .field("url", &req.url)
                 .finish(),
             PendingInner::Error(ref err) => f.debug_struct("Pending").field("error", err).finish(),
+            #[cfg(any(feature = "gzip", feature = "brotli", feature = "cache"))]
+            PendingInner::Boxed(_) => f.debug_struct("Pending").finish_non_exhaustive(),
         }
     }
 }
@@ -3014,9 +4090,55 @@ mod tests {
         assert_eq!(url_str, err.url().unwrap().as_str());
     }
 
+    #[test]
+    fn conflicting_content_length() {
+        use crate::header::{HeaderMap, CONTENT_LENGTH};
+        use crate::util::has_conflicting_content_length;
+
+        let mut headers = HeaderMap::new();
+        assert!(!has_conflicting_content_length(&headers));
+
+        headers.append(CONTENT_LENGTH, "5".parse().unwrap());
+        assert!(!has_conflicting_content_length(&headers));
+
+        headers.append(CONTENT_LENGTH, "5".parse().unwrap());
+        assert!(!has_conflicting_content_length(&headers));
+
+        headers.append(CONTENT_LENGTH, "11".parse().unwrap());
+        assert!(has_conflicting_content_length(&headers));
+    }
+
+    #[test]
+    fn tcp_options_stored() {
+        let builder = crate::Client::builder()
+            .tcp_nodelay(false)
+            .tcp_keepalive(std::time::Duration::from_secs(60));
+        let debug = format!("{builder:?}");
+        assert!(debug.contains("tcp_nodelay: false"), "{debug}");
+        assert!(debug.contains("tcp_keepalive: 60s"), "{debug}");
+
+        let debug = format!("{:?}", crate::Client::builder().tcp_keepalive(None));
+        assert!(debug.contains("tcp_nodelay: true"), "{debug}");
+        assert!(!debug.contains("tcp_keepalive"), "{debug}");
+    }
+
     #[test]
     fn test_future_size() {
         let s = std::mem::size_of::<super::Pending>();
         assert!(s < 128, "size_of::<Pending>() == {s}, too big");
     }
 }
+
+#[cfg(test)]
+#[cfg(all(feature = "__rustls", not(feature = "__rustls-ring")))]
+mod no_provider_tests {
+    #[test]
+    fn build_without_crypto_provider_errors() {
+        // nothing in these tests installs a process-wide default provider
+        let err = super::Client::builder()
+            .use_rustls_tls()
+            .build()
+            .unwrap_err();
+        assert!(err.is_builder());
+    }
+}
@@ -182,7 +182,6 @@ impl fmt::Debug for Decoder {
 }
 
 impl Decoder {
-    #[cfg(feature = "blocking")]
     pub(crate) fn empty() -> Decoder {
         Decoder {
             inner: Inner::PlainText(empty()),
@@ -471,13 +470,6 @@ fn poll_inner_should_be_empty(
     }
 }
 
-#[cfg(any(
-    feature = "gzip",
-    feature = "zstd",
-    feature = "brotli",
-    feature = "deflate",
-    feature = "blocking",
-))]
 fn empty() -> ResponseBody {
     use http_body_util::{combinators::BoxBody, BodyExt, Empty};
     BoxBody::new(Empty::new().map_err(|never| match never {}))
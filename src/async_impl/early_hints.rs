@@ -0,0 +1,222 @@
+//! Parsing of `103 Early Hints` responses.
+
+use std::fmt;
+use std::sync::Arc;
+
+use http::header::LINK;
+use http::HeaderMap;
+use url::Url;
+
+/// A preload link received in a `103 Early Hints` response.
+///
+/// See [`ClientBuilder::on_early_hints`](crate::ClientBuilder::on_early_hints).
+#[derive(Clone, PartialEq, Eq)]
+pub struct Link {
+    url: Url,
+    params: Vec<(String, String)>,
+}
+
+pub(crate) type EarlyHintsCallback = Arc<dyn Fn(&[Link]) + Send + Sync>;
+
+impl Link {
+    /// The target of the link, resolved against the URL of the request the
+    /// hints were sent for, which after a redirect is the redirect's target.
+    pub fn url(&self) -> &Url {
+        &self.url
+    }
+
+    /// The value of the `as` parameter, the kind of resource being preloaded,
+    /// such as `"style"` or `"script"`.
+    pub fn as_destination(&self) -> Option<&str> {
+        self.param("as")
+    }
+
+    /// Get the value of a link parameter, such as `"crossorigin"`.
+    ///
+    /// Parameter names are matched case-insensitively. A parameter without a
+    /// value returns an empty string.
+    pub fn param(&self, name: &str) -> Option<&str> {
+        self.params
+            .iter()
+            .find(|(k, _)| k.eq_ignore_ascii_case(name))
+            .map(|(_, v)| v.as_str())
+    }
+}
+
+impl fmt::Debug for Link {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Link")
+            .field("url", &self.url.as_str())
+            .field("params", &self.params)
+            .finish()
+    }
+}
+
+/// Calls `callback` with the preload links of each `103 Early Hints` response
+/// to `req`, resolved against `base`, the URL `req` is sent to.
+pub(crate) fn on_informational<B>(
+    req: &mut http::Request<B>,
+    callback: EarlyHintsCallback,
+    base: Url,
+) {
+    hyper::ext::on_informational(req, move |res| {
+        if res.status() != http::StatusCode::EARLY_HINTS {
+            return;
+        }
+        let links = preload_links(res.headers(), &base);
+        if !links.is_empty() {
+            callback(&links);
+        }
+    });
+}
+
+/// Collects the `rel=preload` links from the `Link` headers of a response.
+///
+/// Links that can't be parsed are skipped.
+pub(crate) fn preload_links(headers: &HeaderMap, base: &Url) -> Vec<Link> {
+    headers
+        .get_all(LINK)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(split_links)
+        .filter_map(|link| parse_link(link, base))
+        .filter(|link| {
+            link.param("rel").map_or(false, |rel| {
+                rel.split_ascii_whitespace()
+                    .any(|rel| rel.eq_ignore_ascii_case("preload"))
+            })
+        })
+        .collect()
+}
+
+// Splits on the commas between links, skipping those inside a `<...>` target
+// or a quoted parameter value.
+fn split_links(value: &str) -> Vec<&str> {
+    let mut links = Vec::new();
+    let mut start = 0;
+    let mut in_target = false;
+    let mut in_quotes = false;
+    let mut escaped = false;
+    for (i, c) in value.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' if in_quotes => escaped = true,
+            '"' if !in_target => in_quotes = !in_quotes,
+            '<' if !in_quotes => in_target = true,
+            '>' if !in_quotes => in_target = false,
+            ',' if !in_target && !in_quotes => {
+                links.push(&value[start..i]);
+                start = i + 1;
+            }
+            _ => (),
+        }
+    }
+    links.push(&value[start..]);
+    links
+}
+
+fn parse_link(link: &str, base: &Url) -> Option<Link> {
+    let link = link.trim();
+    let rest = link.strip_prefix('<')?;
+    let end = rest.find('>')?;
+    let url = base.join(rest[..end].trim()).ok()?;
+
+    let mut params = Vec::new();
+    for param in split_params(&rest[end + 1..]) {
+        let param = param.trim();
+        if param.is_empty() {
+            continue;
+        }
+        let (name, value) = match param.split_once('=') {
+            Some((name, value)) => (name.trim(), unquote(value.trim())),
+            None => (param, String::new()),
+        };
+        params.push((name.to_ascii_lowercase(), value));
+    }
+    Some(Link { url, params })
+}
+
+// Splits on the semicolons between parameters, skipping quoted ones.
+fn split_params(value: &str) -> Vec<&str> {
+    let mut params = Vec::new();
+    let mut start = 0;
+    let mut in_quotes = false;
+    let mut escaped = false;
+    for (i, c) in value.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' if in_quotes => escaped = true,
+            '"' => in_quotes = !in_quotes,
+            ';' if !in_quotes => {
+                params.push(&value[start..i]);
+                start = i + 1;
+            }
+            _ => (),
+        }
+    }
+    params.push(&value[start..]);
+    params
+}
+
+fn unquote(value: &str) -> String {
+    match value
+        .strip_prefix('"')
+        .and_then(|value| value.strip_suffix('"'))
+    {
+        Some(quoted) => {
+            let mut unquoted = String::with_capacity(quoted.len());
+            let mut chars = quoted.chars();
+            while let Some(c) = chars.next() {
+                if c == '\\' {
+                    unquoted.extend(chars.next());
+                } else {
+                    unquoted.push(c);
+                }
+            }
+            unquoted
+        }
+        None => value.to_owned(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_preload_links() {
+        let base = Url::parse("https://example.com/app/page").unwrap();
+        let mut headers = HeaderMap::new();
+        headers.append(
+            LINK,
+            "</style.css>; rel=preload; as=style, <font.woff2>; rel=\"preload\"; as=font; crossorigin"
+                .parse()
+                .unwrap(),
+        );
+        headers.append(
+            LINK,
+            "<https://cdn.example.com>; rel=preconnect, <https://cdn.example.com/a,b.js>; REL=\"prefetch preload\"; title=\"x; y\""
+                .parse()
+                .unwrap(),
+        );
+        headers.append(LINK, "not a link; rel=preload".parse().unwrap());
+
+        let links = preload_links(&headers, &base);
+        assert_eq!(links.len(), 3);
+
+        assert_eq!(links[0].url().as_str(), "https://example.com/style.css");
+        assert_eq!(links[0].as_destination(), Some("style"));
+        assert_eq!(links[0].param("crossorigin"), None);
+
+        assert_eq!(
+            links[1].url().as_str(),
+            "https://example.com/app/font.woff2"
+        );
+        assert_eq!(links[1].as_destination(), Some("font"));
+        assert_eq!(links[1].param("crossorigin"), Some(""));
+
+        assert_eq!(links[2].url().as_str(), "https://cdn.example.com/a,b.js");
+        assert_eq!(links[2].as_destination(), None);
+        assert_eq!(links[2].param("title"), Some("x; y"));
+    }
+}
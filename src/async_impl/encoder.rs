@@ -0,0 +1,113 @@
+//! Compression of request bodies.
+
+use std::io::{self, Write};
+
+use http::header::{CONTENT_ENCODING, CONTENT_LENGTH};
+use http::HeaderValue;
+
+use super::body::Body;
+use super::request::Request;
+
+/// Bodies larger than this are compressed on a blocking thread, so as not to
+/// hold up the executor.
+const BLOCKING_THRESHOLD: usize = 64 * 1024;
+
+/// A content coding to compress a request body with.
+///
+/// See [`RequestBuilder::compress`](crate::RequestBuilder::compress).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Encoding {
+    /// `gzip`, requires the `gzip` feature.
+    #[cfg(feature = "gzip")]
+    Gzip,
+    /// `br`, requires the `brotli` feature.
+    #[cfg(feature = "brotli")]
+    Brotli,
+}
+
+impl Encoding {
+    fn as_str(self) -> &'static str {
+        match self {
+            #[cfg(feature = "gzip")]
+            Encoding::Gzip => "gzip",
+            #[cfg(feature = "brotli")]
+            Encoding::Brotli => "br",
+        }
+    }
+
+    fn compress(self, data: &[u8]) -> io::Result<Vec<u8>> {
+        match self {
+            #[cfg(feature = "gzip")]
+            Encoding::Gzip => {
+                let mut encoder =
+                    flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+                encoder.write_all(data)?;
+                encoder.finish()
+            }
+            #[cfg(feature = "brotli")]
+            Encoding::Brotli => {
+                let mut encoder = brotli_crate::CompressorWriter::new(Vec::new(), 4096, 5, 22);
+                encoder.write_all(data)?;
+                encoder.flush()?;
+                Ok(encoder.into_inner())
+            }
+        }
+    }
+}
+
+/// Compresses the body of `req` with the encoding it was marked with, if any.
+///
+/// Only in-memory bodies can be compressed for now; a streaming body is an
+/// error. A body over `BLOCKING_THRESHOLD` is left as it is, and its encoding
+/// returned to compress it with `compress_blocking` instead.
+pub(crate) fn compress_body(req: &mut Request) -> crate::Result<Option<Encoding>> {
+    let encoding = match req.extensions_mut().remove::<Encoding>() {
+        Some(encoding) => encoding,
+        None => return Ok(None),
+    };
+
+    let compressed = match req.body().map(|body| body.as_bytes()) {
+        None => return Ok(None),
+        Some(Some(bytes)) if bytes.len() > BLOCKING_THRESHOLD => return Ok(Some(encoding)),
+        Some(Some(bytes)) => encoding
+            .compress(bytes)
+            .map_err(|e| crate::error::builder(e).with_url(req.url().clone()))?,
+        Some(None) => {
+            return Err(
+                crate::error::builder("streaming request bodies can't be compressed")
+                    .with_url(req.url().clone()),
+            )
+        }
+    };
+
+    set_body(req, encoding, compressed);
+    Ok(None)
+}
+
+/// Compresses the in-memory body of `req` on a blocking thread.
+pub(crate) async fn compress_blocking(
+    mut req: Request,
+    encoding: Encoding,
+) -> crate::Result<Request> {
+    let body = req
+        .body()
+        .and_then(Body::try_clone)
+        .expect("compress_body checked the body is in memory");
+    let compressed = tokio::task::spawn_blocking(move || {
+        encoding.compress(body.as_bytes().expect("body is in memory"))
+    })
+    .await
+    .map_err(|e| crate::error::builder(e).with_url(req.url().clone()))?
+    .map_err(|e| crate::error::builder(e).with_url(req.url().clone()))?;
+
+    set_body(&mut req, encoding, compressed);
+    Ok(req)
+}
+
+fn set_body(req: &mut Request, encoding: Encoding, compressed: Vec<u8>) {
+    *req.body_mut() = Some(compressed.into());
+    let headers = req.headers_mut();
+    headers.insert(CONTENT_ENCODING, HeaderValue::from_static(encoding.as_str()));
+    headers.remove(CONTENT_LENGTH);
+}
@@ -212,7 +212,7 @@ impl PoolClient {
         let (head, mut req_body) = req.into_parts();
         let mut req = Request::from_parts(head, ());
 
-        if let Some(n) = req_body.size_hint().exact() {
+        if let Some(n) = req_body.len() {
             if n > 0 {
                 req.headers_mut()
                     .insert(http::header::CONTENT_LENGTH, n.into());
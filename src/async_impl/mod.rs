@@ -1,18 +1,28 @@
 pub use self::body::Body;
+#[cfg(feature = "stream")]
+pub use self::body::BodySender;
 pub use self::client::{Client, ClientBuilder};
+pub use self::early_hints::Link;
+#[cfg(any(feature = "gzip", feature = "brotli"))]
+pub use self::encoder::Encoding;
 pub use self::request::{Request, RequestBuilder};
-pub use self::response::Response;
+pub use self::response::{AcceptRanges, Response};
 pub use self::upgrade::Upgraded;
 
 #[cfg(feature = "blocking")]
 pub(crate) use self::decoder::Decoder;
 
+pub mod api;
 pub mod body;
 pub mod client;
 pub mod decoder;
+pub(crate) mod early_hints;
+#[cfg(any(feature = "gzip", feature = "brotli"))]
+pub(crate) mod encoder;
 pub mod h3_client;
 #[cfg(feature = "multipart")]
 pub mod multipart;
+mod rate_limit;
 pub(crate) mod request;
 mod response;
 mod upgrade;
@@ -72,6 +72,35 @@ impl Form {
         self.inner.boundary()
     }
 
+    /// Use a fixed boundary instead of a randomly generated one.
+    ///
+    /// This is mostly useful to produce deterministic bodies, such as in
+    /// snapshot tests. The boundary **must not** appear in the content of
+    /// any of the parts, or the receiver will not be able to parse the form.
+    ///
+    /// # Errors
+    ///
+    /// Errors if the boundary is empty, longer than 70 characters, or
+    /// contains characters other than ASCII letters, digits, and any of
+    /// `'+_-.`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # fn run() -> Result<(), reqwest::Error> {
+    /// let form = reqwest::multipart::Form::new()
+    ///     .with_boundary("my-fixed-boundary")?
+    ///     .text("username", "seanmonstar");
+    /// assert_eq!(form.boundary(), "my-fixed-boundary");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_boundary(self, boundary: &str) -> crate::Result<Form> {
+        Ok(Form {
+            inner: self.inner.with_boundary(boundary)?,
+        })
+    }
+
     /// Add a data field with supplied name and value.
     ///
     /// # Examples
@@ -126,17 +155,26 @@ impl Form {
 
     /// Configure this `Form` to percent-encode using the `path-segment` rules.
     pub fn percent_encode_path_segment(self) -> Form {
-        self.with_inner(|inner| inner.percent_encode_path_segment())
+        self.percent_encoding(PercentEncoding::PathSegment)
     }
 
     /// Configure this `Form` to percent-encode using the `attr-char` rules.
     pub fn percent_encode_attr_chars(self) -> Form {
-        self.with_inner(|inner| inner.percent_encode_attr_chars())
+        self.percent_encoding(PercentEncoding::AttrChar)
     }
 
     /// Configure this `Form` to skip percent-encoding
     pub fn percent_encode_noop(self) -> Form {
-        self.with_inner(|inner| inner.percent_encode_noop())
+        self.percent_encoding(PercentEncoding::NoOp)
+    }
+
+    /// Configure how this `Form` percent-encodes the names of its fields.
+    ///
+    /// A name that needs encoding is sent in the `name*=utf-8''` form of the
+    /// `Content-Disposition` header. File names are never percent-encoded,
+    /// only quoted. The default is [`PercentEncoding::PathSegment`].
+    pub fn percent_encoding(self, encoding: PercentEncoding) -> Form {
+        self.with_inner(move |inner| inner.percent_encoding(encoding))
     }
 
     /// Consume this instance and transform into an instance of Body for use in a request.
@@ -157,17 +195,21 @@ impl Form {
             return empty_stream;
         }
 
-        // create initial part to init reduce chain
-        let (name, part) = self.inner.fields.remove(0);
-        let start = Box::pin(self.part_stream(name, part))
-            as Pin<Box<dyn Stream<Item = crate::Result<Bytes>> + Send + Sync>>;
-
-        let fields = self.inner.take_fields();
-        // for each field, chain an additional stream
-        let stream = fields.into_iter().fold(start, |memo, (name, part)| {
-            let part_stream = self.part_stream(name, part);
-            Box::pin(memo.chain(part_stream))
-                as Pin<Box<dyn Stream<Item = crate::Result<Bytes>> + Send + Sync>>
+        // Part headers are small, so they're all encoded up front, while the
+        // part bodies are only polled once the stream reaches them. Flattening
+        // keeps the cost of each poll the same, however many parts there are.
+        let parts = self
+            .inner
+            .take_fields()
+            .into_iter()
+            .map(|(name, part)| (self.part_header(name, &part.meta), part.value))
+            .collect::<Vec<_>>();
+        let stream = stream::iter(parts).flat_map(|(header, value)| {
+            // start with boundary and headers, then append form data
+            // followed by terminating CRLF
+            stream::once(future::ready(Ok(header)))
+                .chain(value.into_stream())
+                .chain(stream::once(future::ready(Ok(Bytes::from_static(b"\r\n")))))
         });
         // append special ending boundary
         let last = stream::once(future::ready(Ok(
@@ -176,33 +218,19 @@ impl Form {
         Box::pin(stream.chain(last))
     }
 
-    /// Generate a hyper::Body stream for a single Part instance of a Form request.
-    pub(crate) fn part_stream<T>(
-        &mut self,
-        name: T,
-        part: Part,
-    ) -> impl Stream<Item = Result<Bytes, crate::Error>>
+    /// Encode the boundary and headers that start a part.
+    fn part_header<T>(&self, name: T, meta: &PartMetadata) -> Bytes
     where
         T: Into<Cow<'static, str>>,
     {
-        // start with boundary
-        let boundary = stream::once(future::ready(Ok(
-            format!("--{}\r\n", self.boundary()).into()
-        )));
-        // append headers
-        let header = stream::once(future::ready(Ok({
-            let mut h = self
-                .inner
+        let mut h = format!("--{}\r\n", self.boundary()).into_bytes();
+        h.extend(
+            self.inner
                 .percent_encoding
-                .encode_headers(&name.into(), &part.meta);
-            h.extend_from_slice(b"\r\n\r\n");
-            h.into()
-        })));
-        // then append form data followed by terminating CRLF
-        boundary
-            .chain(header)
-            .chain(part.value.into_stream())
-            .chain(stream::once(future::ready(Ok("\r\n".into()))))
+                .encode_headers(&name.into(), meta),
+        );
+        h.extend_from_slice(b"\r\n\r\n");
+        h.into()
     }
 
     pub(crate) fn compute_length(&mut self) -> Option<u64> {
@@ -349,7 +377,7 @@ impl PartProps for Part {
         if self.body_length.is_some() {
             self.body_length
         } else {
-            self.value.content_length()
+            self.value.len()
         }
     }
 
@@ -366,7 +394,7 @@ impl<P: PartProps> FormParts<P> {
             boundary: gen_boundary(),
             computed_headers: Vec::new(),
             fields: Vec::new(),
-            percent_encoding: PercentEncoding::PathSegment,
+            percent_encoding: PercentEncoding::default(),
         }
     }
 
@@ -374,6 +402,13 @@ impl<P: PartProps> FormParts<P> {
         &self.boundary
     }
 
+    /// Replace the generated boundary with a user supplied one.
+    pub(crate) fn with_boundary(mut self, boundary: &str) -> crate::Result<Self> {
+        validate_boundary(boundary)?;
+        self.boundary = boundary.to_owned();
+        Ok(self)
+    }
+
     /// Adds a customized Part.
     pub(crate) fn part<T>(mut self, name: T, part: P) -> Self
     where
@@ -383,21 +418,9 @@ impl<P: PartProps> FormParts<P> {
         self
     }
 
-    /// Configure this `Form` to percent-encode using the `path-segment` rules.
-    pub(crate) fn percent_encode_path_segment(mut self) -> Self {
-        self.percent_encoding = PercentEncoding::PathSegment;
-        self
-    }
-
-    /// Configure this `Form` to percent-encode using the `attr-char` rules.
-    pub(crate) fn percent_encode_attr_chars(mut self) -> Self {
-        self.percent_encoding = PercentEncoding::AttrChar;
-        self
-    }
-
-    /// Configure this `Form` to skip percent-encoding
-    pub(crate) fn percent_encode_noop(mut self) -> Self {
-        self.percent_encoding = PercentEncoding::NoOp;
+    /// Configure how this `Form` percent-encodes field names.
+    pub(crate) fn percent_encoding(mut self, encoding: PercentEncoding) -> Self {
+        self.percent_encoding = encoding;
         self
     }
 
@@ -524,9 +547,17 @@ const ATTR_CHAR_ENCODE_SET: &AsciiSet = &NON_ALPHANUMERIC
     .remove(b'|')
     .remove(b'~');
 
-pub(crate) enum PercentEncoding {
+/// How a multipart [`Form`] percent-encodes the names of its fields.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum PercentEncoding {
+    /// Encode using the `path-segment` rules of the URL standard.
+    ///
+    /// This is the default, and is understood by most servers.
+    #[default]
     PathSegment,
+    /// Encode using the `attr-char` rules of RFC 5987.
     AttrChar,
+    /// Don't encode, sending names as they are.
     NoOp,
 }
 
@@ -598,6 +629,29 @@ fn gen_boundary() -> String {
     format!("{a:016x}-{b:016x}-{c:016x}-{d:016x}")
 }
 
+// https://www.rfc-editor.org/rfc/rfc2046#section-5.1.1
+//
+// Only the subset of `bchars` that are also valid in an unquoted `token` is
+// allowed, since the boundary is written as-is into the `Content-Type`.
+fn validate_boundary(boundary: &str) -> crate::Result<()> {
+    if boundary.is_empty() || boundary.len() > 70 {
+        return Err(crate::error::builder(
+            "multipart boundary must be between 1 and 70 characters",
+        ));
+    }
+
+    let valid = boundary
+        .bytes()
+        .all(|b| b.is_ascii_alphanumeric() || b"'+_-.".contains(&b));
+    if !valid {
+        return Err(crate::error::builder(
+            "multipart boundary contains invalid characters",
+        ));
+    }
+
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -712,6 +766,81 @@ mod tests {
         assert_eq!(std::str::from_utf8(&out).unwrap(), expected);
     }
 
+    #[test]
+    fn stream_with_fixed_boundary() {
+        let form = Form::new()
+            .with_boundary("fixed-boundary")
+            .unwrap()
+            .text("key1", "value1");
+        assert_eq!(form.boundary(), "fixed-boundary");
+
+        let expected = "--fixed-boundary\r\n\
+                        Content-Disposition: form-data; name=\"key1\"\r\n\r\n\
+                        value1\r\n\
+                        --fixed-boundary--\r\n";
+        let rt = runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .expect("new rt");
+        let body = form.stream().into_stream();
+        let s = body.map(|try_c| try_c.map(|r| r.to_vec())).try_concat();
+
+        let out = rt.block_on(s).unwrap();
+        assert_eq!(std::str::from_utf8(&out).unwrap(), expected);
+    }
+
+    #[test]
+    fn stream_large_parts_lazily() {
+        const CHUNK: usize = 8 * 1024;
+        const CHUNKS: usize = 128;
+
+        fn large_part(byte: u8) -> Part {
+            let chunks =
+                stream::iter((0..CHUNKS).map(move |_| Ok::<_, crate::Error>(vec![byte; CHUNK])));
+            Part::stream(Body::stream(chunks))
+        }
+
+        let mut form = Form::new()
+            .part("a", large_part(b'a'))
+            .part("b", large_part(b'b'));
+        form.inner.boundary = "boundary".to_string();
+
+        let rt = runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .expect("new rt");
+        let chunks = rt
+            .block_on(form.stream().into_stream().try_collect::<Vec<_>>())
+            .unwrap();
+
+        // each chunk of a part is passed through as is, never buffered
+        assert!(chunks.len() >= 2 * CHUNKS);
+        assert!(chunks.iter().all(|chunk| chunk.len() <= CHUNK));
+
+        let out = chunks.concat();
+        let mut expected = Vec::new();
+        for name in ["a", "b"] {
+            expected.extend_from_slice(
+                format!("--boundary\r\nContent-Disposition: form-data; name=\"{name}\"\r\n\r\n")
+                    .as_bytes(),
+            );
+            expected.extend(std::iter::repeat(name.as_bytes()[0]).take(CHUNK * CHUNKS));
+            expected.extend_from_slice(b"\r\n");
+        }
+        expected.extend_from_slice(b"--boundary--\r\n");
+        assert_eq!(out, expected);
+    }
+
+    #[test]
+    fn invalid_boundary() {
+        assert!(Form::new().with_boundary("").unwrap_err().is_builder());
+        assert!(Form::new().with_boundary(&"a".repeat(71)).is_err());
+        assert!(Form::new().with_boundary("has space").is_err());
+        assert!(Form::new().with_boundary("semi;colon").is_err());
+        assert!(Form::new().with_boundary("ünïcode").is_err());
+        assert!(Form::new().with_boundary(&"a".repeat(70)).is_ok());
+    }
+
     #[test]
     fn correct_content_length() {
         // Setup an arbitrary data stream
@@ -735,6 +864,37 @@ mod tests {
         assert_eq!(body_part.value_len().unwrap(), bytes_len as u64);
     }
 
+    #[test]
+    fn form_percent_encoding() {
+        let rt = runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .expect("new rt");
+        let cases = [
+            (PercentEncoding::PathSegment, "name*=utf-8''my%20f%C3%AEle"),
+            (PercentEncoding::AttrChar, "name*=utf-8''my%20f%C3%AEle"),
+            (PercentEncoding::NoOp, "name=\"my fîle\""),
+        ];
+        for (encoding, name) in cases {
+            let mut form = Form::new()
+                .part("my fîle", Part::text("x").file_name("ünï côde.txt"))
+                .percent_encoding(encoding);
+            form.inner.boundary = "boundary".to_string();
+            let expected = format!(
+                "--boundary\r\n\
+                 Content-Disposition: form-data; {name}; filename=\"ünï côde.txt\"\r\n\r\n\
+                 x\r\n--boundary--\r\n"
+            );
+            let body = form.stream().into_stream();
+            let out = rt.block_on(body.map_ok(|c| c.to_vec()).try_concat());
+            assert_eq!(
+                std::str::from_utf8(&out.unwrap()).unwrap(),
+                expected,
+                "{encoding:?}"
+            );
+        }
+    }
+
     #[test]
     fn header_percent_encoding() {
         let name = "start%'\"\r\nßend";
@@ -0,0 +1,79 @@
+//! Throttling of the requests a `Client` sends.
+
+use std::sync::Mutex;
+use std::time::Duration;
+
+use tokio::time::Instant;
+
+/// A token bucket of `requests` tokens, refilled at `requests` per `per`.
+///
+/// Instead of counting tokens, it tracks when the bucket would be full again,
+/// which is equivalent and needs no refill timer.
+pub(crate) struct RateLimiter {
+    interval: Duration,
+    burst: Duration,
+    full_at: Mutex<Instant>,
+}
+
+impl RateLimiter {
+    pub(crate) fn new(requests: u32, per: Duration) -> RateLimiter {
+        debug_assert!(requests > 0);
+        let interval = per / requests;
+        RateLimiter {
+            interval,
+            burst: per - interval,
+            full_at: Mutex::new(Instant::now()),
+        }
+    }
+
+    /// Takes a token, returning when it's available.
+    ///
+    /// Nothing is taken if that would be after `deadline`.
+    pub(crate) fn acquire(&self, deadline: Option<Instant>) -> Option<Instant> {
+        let now = Instant::now();
+        let mut full_at = self.full_at.lock().unwrap();
+        let full = (*full_at).max(now);
+        let at = full
+            .checked_sub(self.burst)
+            .map_or(now, |at| at.max(now));
+        if deadline.map_or(false, |deadline| at > deadline) {
+            return None;
+        }
+        *full_at = full + self.interval;
+        Some(at)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn bursts_then_throttles() {
+        tokio::time::pause();
+        let limiter = RateLimiter::new(3, Duration::from_secs(3));
+        let start = limiter.acquire(None).unwrap();
+        for _ in 0..2 {
+            assert_eq!(limiter.acquire(None), Some(start));
+        }
+        assert_eq!(limiter.acquire(None), Some(start + Duration::from_secs(1)));
+
+        // a deadline before the next token leaves it in the bucket
+        let deadline = Some(start + Duration::from_millis(1500));
+        assert_eq!(limiter.acquire(deadline), None);
+        assert_eq!(limiter.acquire(None), Some(start + Duration::from_secs(2)));
+    }
+
+    #[tokio::test]
+    async fn refills_while_idle() {
+        tokio::time::pause();
+        let limiter = RateLimiter::new(2, Duration::from_millis(20));
+        limiter.acquire(None);
+        limiter.acquire(None);
+        tokio::time::advance(Duration::from_millis(30)).await;
+        let now = Instant::now();
+        for _ in 0..2 {
+            assert_eq!(limiter.acquire(None), Some(now));
+        }
+    }
+}
@@ -1,7 +1,7 @@
 use std::convert::TryFrom;
 use std::fmt;
 use std::future::Future;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use serde::Serialize;
 #[cfg(feature = "json")]
@@ -12,7 +12,11 @@ use super::client::{Client, Pending};
 #[cfg(feature = "multipart")]
 use super::multipart;
 use super::response::Response;
-use crate::config::{RequestConfig, TotalTimeout};
+use crate::config::{
+    Deadline, NoDefaultHeaders, RemovedHeaders, RequestConfig, TotalTimeout, TotalTimeoutDisabled,
+};
+#[cfg(feature = "json")]
+use crate::config::JsonAccept;
 #[cfg(feature = "multipart")]
 use crate::header::CONTENT_LENGTH;
 use crate::header::{HeaderMap, HeaderName, HeaderValue, CONTENT_TYPE};
@@ -71,6 +75,24 @@ impl Request {
     }
 
     /// Get a mutable reference to the url.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn run() -> Result<(), reqwest::Error> {
+    /// let client = reqwest::Client::new();
+    /// let (client, req) = client
+    ///     .get("https://example.com/search?q=old")
+    ///     .build_split();
+    /// let mut req = req?;
+    ///
+    /// req.url_mut().query_pairs_mut().clear().append_pair("q", "new");
+    /// assert_eq!(req.url().as_str(), "https://example.com/search?q=new");
+    /// # let _ = client;
+    /// # Ok(())
+    /// # }
+    /// # run().unwrap();
+    /// ```
     #[inline]
     pub fn url_mut(&mut self) -> &mut Url {
         &mut self.url
@@ -112,18 +134,56 @@ impl Request {
         &mut self.extensions
     }
 
-    /// Get the timeout.
+    /// Get the timeout of this request.
+    ///
+    /// This is the timeout set with [`RequestBuilder::timeout`], or `None` if
+    /// the client's applies.
     #[inline]
     pub fn timeout(&self) -> Option<&Duration> {
         RequestConfig::<TotalTimeout>::get(&self.extensions)
     }
 
     /// Get a mutable reference to the timeout.
+    ///
+    /// The value left here is the one used when the request is executed, so
+    /// middleware can adjust it after the request is built. Setting `None`
+    /// falls back to the client's timeout, unless the request disabled it
+    /// with `RequestBuilder::no_timeout`.
     #[inline]
     pub fn timeout_mut(&mut self) -> &mut Option<Duration> {
         RequestConfig::<TotalTimeout>::get_mut(&mut self.extensions)
     }
 
+    pub(crate) fn set_timeout(&mut self, timeout: Option<Duration>) {
+        *self.timeout_mut() = timeout;
+        *RequestConfig::<TotalTimeoutDisabled>::get_mut(&mut self.extensions) =
+            timeout.map_or(Some(()), |_| None);
+    }
+
+    /// Whether the timeout was disabled with `RequestBuilder::no_timeout`,
+    /// and not set again since.
+    pub(crate) fn is_timeout_disabled(&self) -> bool {
+        RequestConfig::<TotalTimeoutDisabled>::get(&self.extensions).is_some()
+            && self.timeout().is_none()
+    }
+
+    /// Removes a header, and keeps the client from adding a default for it.
+    pub(crate) fn remove_header(&mut self, key: HeaderName) {
+        self.headers.remove(&key);
+        RequestConfig::<RemovedHeaders>::get_mut(&mut self.extensions)
+            .get_or_insert_with(Vec::new)
+            .push(key);
+    }
+
+    pub(crate) fn set_no_default_headers(&mut self) {
+        *RequestConfig::<NoDefaultHeaders>::get_mut(&mut self.extensions) = Some(());
+    }
+
+    #[cfg(feature = "json")]
+    pub(crate) fn set_json_accept(&mut self) {
+        *RequestConfig::<JsonAccept>::get_mut(&mut self.extensions) = Some(());
+    }
+
     /// Get the http version.
     #[inline]
     pub fn version(&self) -> Version {
@@ -201,6 +261,42 @@ impl RequestBuilder {
         self.header_sensitive(key, value, false)
     }
 
+    /// Remove a `Header` from this Request.
+    ///
+    /// This also keeps the client's default headers from setting it, like a
+    /// default `User-Agent`. Setting the header again afterwards still adds
+    /// it.
+    pub fn remove_header<K>(mut self, key: K) -> RequestBuilder
+    where
+        HeaderName: TryFrom<K>,
+        <HeaderName as TryFrom<K>>::Error: Into<http::Error>,
+    {
+        let mut error = None;
+        if let Ok(ref mut req) = self.request {
+            match <HeaderName as TryFrom<K>>::try_from(key) {
+                Ok(key) => req.remove_header(key),
+                Err(e) => error = Some(crate::error::builder(e.into())),
+            }
+        }
+        if let Some(err) = error {
+            self.request = Err(err);
+        }
+        self
+    }
+
+    /// Don't add the client's default headers to this request.
+    ///
+    /// The request is sent with only the headers set on it, apart from
+    /// those the connection itself needs, like `Host`, and proxy
+    /// authorization. This includes the `User-Agent`, `Accept` and
+    /// `Accept-Encoding` headers the client adds by default.
+    pub fn no_default_headers(mut self) -> RequestBuilder {
+        if let Ok(ref mut req) = self.request {
+            req.set_no_default_headers();
+        }
+        self
+    }
+
     /// Add a `Header` to this Request with ability to define if `header_value` is sensitive.
     fn header_sensitive<K, V>(mut self, key: K, value: V, sensitive: bool) -> RequestBuilder
     where
@@ -275,6 +371,121 @@ impl RequestBuilder {
         self.header_sensitive(crate::header::AUTHORIZATION, header_value, true)
     }
 
+    /// Override the `Host` header of the request.
+    ///
+    /// The request is still sent to the host of its URL, and with HTTPS, that
+    /// host is also the one used for SNI and certificate verification. Only
+    /// the header changes, which is useful to reach a virtual host by
+    /// address. To instead connect to another address while keeping the
+    /// URL's host everywhere, see
+    /// [`ClientBuilder::resolve`](crate::ClientBuilder::resolve).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # async fn run() -> Result<(), reqwest::Error> {
+    /// let res = reqwest::Client::new()
+    ///     .get("http://127.0.0.1:8080/")
+    ///     .host_header("internal.example")
+    ///     .send()
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn host_header<V>(self, host: V) -> RequestBuilder
+    where
+        HeaderValue: TryFrom<V>,
+        <HeaderValue as TryFrom<V>>::Error: Into<http::Error>,
+    {
+        self.header(crate::header::HOST, host)
+    }
+
+    /// Set the `If-Range` header, making a range request conditional.
+    ///
+    /// The validator is either an entity tag, such as `"\"xyzzy\""`, or an
+    /// HTTP-date, such as `"Wed, 21 Oct 2015 07:28:00 GMT"`, as previously
+    /// returned in the `ETag` or `Last-Modified` headers. If the resource
+    /// still matches, the server responds with just the requested range;
+    /// otherwise it sends the full, current body. Use
+    /// [`Response::is_partial`][is_partial] to tell the two apart.
+    ///
+    /// This is only meaningful together with a `Range` header.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # async fn run() -> Result<(), reqwest::Error> {
+    /// let res = reqwest::Client::new()
+    ///     .get("https://hyper.rs/download")
+    ///     .header(reqwest::header::RANGE, "bytes=1024-")
+    ///     .if_range("\"xyzzy\"")
+    ///     .send()
+    ///     .await?;
+    ///
+    /// if !res.is_partial() {
+    ///     // the resource changed, start over
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [is_partial]: crate::Response::is_partial
+    pub fn if_range<V>(self, validator: V) -> RequestBuilder
+    where
+        HeaderValue: TryFrom<V>,
+        <HeaderValue as TryFrom<V>>::Error: Into<http::Error>,
+    {
+        self.header(crate::header::IF_RANGE, validator)
+    }
+
+    /// Set the `If-None-Match` header, making the request conditional on the
+    /// resource having changed.
+    ///
+    /// The entity tag is one previously returned in the `ETag` header, such
+    /// as `"\"xyzzy\""`, quotes included. If the resource still has it, the
+    /// server answers with `304 Not Modified` and no body, which
+    /// [`Response::is_not_modified`][is_not_modified] checks for.
+    ///
+    /// [is_not_modified]: crate::Response::is_not_modified
+    pub fn if_none_match<V>(self, etag: V) -> RequestBuilder
+    where
+        HeaderValue: TryFrom<V>,
+        <HeaderValue as TryFrom<V>>::Error: Into<http::Error>,
+    {
+        self.header(crate::header::IF_NONE_MATCH, etag)
+    }
+
+    /// Set the `If-Modified-Since` header, making the request conditional on
+    /// the resource having changed since `date`.
+    ///
+    /// The date is a `SystemTime`, or a string in the HTTP date format as
+    /// previously returned in the `Last-Modified` header. If the resource
+    /// hasn't changed, the server answers with `304 Not Modified` and no
+    /// body, which [`Response::is_not_modified`][is_not_modified] checks for.
+    ///
+    /// # Errors
+    ///
+    /// Sending the request fails with a builder error if `date` isn't a valid
+    /// HTTP date.
+    ///
+    /// [is_not_modified]: crate::Response::is_not_modified
+    pub fn if_modified_since<T: crate::IntoHttpDate>(mut self, date: T) -> RequestBuilder {
+        let mut error = None;
+        if let Ok(ref mut req) = self.request {
+            match date.into_header_value() {
+                Ok(value) => {
+                    req.headers_mut()
+                        .insert(crate::header::IF_MODIFIED_SINCE, value);
+                }
+                Err(e) => error = Some(e),
+            }
+        }
+        if let Some(err) = error {
+            self.request = Err(err);
+        }
+        self
+    }
+
     /// Set the request body.
     pub fn body<T: Into<Body>>(mut self, body: T) -> RequestBuilder {
         if let Ok(ref mut req) = self.request {
@@ -283,6 +494,99 @@ impl RequestBuilder {
         self
     }
 
+    /// Don't send cookies from the client's cookie store with this request,
+    /// nor store the cookies its response sets, including across redirects.
+    ///
+    /// This is useful when a client with a cookie store also talks to
+    /// third-party endpoints.
+    ///
+    /// # Optional
+    ///
+    /// This requires the optional `cookies` feature to be enabled.
+    #[cfg(feature = "cookies")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "cookies")))]
+    pub fn no_cookies(mut self) -> RequestBuilder {
+        if let Ok(ref mut req) = self.request {
+            req.extensions_mut().insert(crate::cookie::NoCookies);
+        }
+        self
+    }
+
+    /// Set whether this request uses the client's cookie store, like the
+    /// Fetch standard's `credentials` request option.
+    ///
+    /// See [`Credentials`](crate::cookie::Credentials) for the modes.
+    ///
+    /// # Optional
+    ///
+    /// This requires the optional `cookies` feature to be enabled.
+    #[cfg(feature = "cookies")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "cookies")))]
+    pub fn fetch_credentials_mode(
+        mut self,
+        credentials: crate::cookie::Credentials,
+    ) -> RequestBuilder {
+        if let Ok(ref mut req) = self.request {
+            crate::cookie::set_credentials(req.extensions_mut(), credentials);
+        }
+        self
+    }
+
+    /// Set how this request handles redirects, overriding the client's
+    /// redirect policy, like the Fetch standard's `redirect` request option.
+    ///
+    /// See [`RedirectMode`](crate::redirect::RedirectMode) for the modes.
+    pub fn fetch_redirect_mode(mut self, mode: crate::redirect::RedirectMode) -> RequestBuilder {
+        if let Ok(ref mut req) = self.request {
+            req.extensions_mut().insert(mode);
+        }
+        self
+    }
+
+    /// Compress the body of this request with `encoding`, and set the
+    /// `Content-Encoding` header.
+    ///
+    /// The body is compressed when the request is sent, so this can be
+    /// called before or after setting it. Only in-memory bodies can be
+    /// compressed; sending a request with a streaming body returns an error.
+    ///
+    /// # Optional
+    ///
+    /// This requires the optional `gzip` or `brotli` feature to be enabled.
+    #[cfg(any(feature = "gzip", feature = "brotli"))]
+    #[cfg_attr(docsrs, doc(cfg(any(feature = "gzip", feature = "brotli"))))]
+    pub fn compress(mut self, encoding: crate::Encoding) -> RequestBuilder {
+        if let Ok(ref mut req) = self.request {
+            req.extensions_mut().insert(encoding);
+        }
+        self
+    }
+
+    /// Ask the server to upgrade the connection to a WebSocket.
+    ///
+    /// This sets the `Connection`, `Upgrade`, `Sec-WebSocket-Version` and a
+    /// fresh `Sec-WebSocket-Key` header. Use
+    /// [`Response::into_websocket`](crate::Response::into_websocket) to
+    /// complete the handshake.
+    ///
+    /// # Optional
+    ///
+    /// This requires the optional `websocket` feature to be enabled.
+    #[cfg(feature = "websocket")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "websocket")))]
+    pub fn upgrade(mut self) -> RequestBuilder {
+        let mut error = None;
+        if let Ok(ref mut req) = self.request {
+            if let Err(e) = crate::websocket::handshake_headers(req.headers_mut()) {
+                error = Some(e);
+            }
+        }
+        if let Some(err) = error {
+            self.request = Err(err);
+        }
+        self
+    }
+
     /// Enables a request timeout.
     ///
     /// The timeout is applied from when the request starts connecting until the
@@ -290,7 +594,30 @@ impl RequestBuilder {
     /// the timeout configured using `ClientBuilder::timeout()`.
     pub fn timeout(mut self, timeout: Duration) -> RequestBuilder {
         if let Ok(ref mut req) = self.request {
-            *req.timeout_mut() = Some(timeout);
+            req.set_timeout(Some(timeout));
+        }
+        self
+    }
+
+    /// Disables the timeout for this request, including the one configured
+    /// using `ClientBuilder::timeout()`.
+    pub fn no_timeout(mut self) -> RequestBuilder {
+        if let Ok(ref mut req) = self.request {
+            req.set_timeout(None);
+        }
+        self
+    }
+
+    /// Sets an absolute deadline for this request.
+    ///
+    /// Like [`timeout`](Self::timeout), it covers connecting, every redirect,
+    /// and reading the response body, but as an `Instant` rather than from
+    /// when the request starts. That lets several requests, such as the
+    /// attempts of a retry loop, share a single deadline. If a timeout
+    /// applies too, the earlier of the two wins.
+    pub fn deadline(mut self, deadline: Instant) -> RequestBuilder {
+        if let Ok(ref mut req) = self.request {
+            *RequestConfig::<Deadline>::get_mut(req.extensions_mut()) = Some(deadline);
         }
         self
     }
@@ -376,7 +703,39 @@ impl RequestBuilder {
         self
     }
 
+    /// Append key-value pairs to the query string of the URL.
+    ///
+    /// Like [`query`](Self::query), but taking the pairs directly, for when
+    /// building a serializable value is overkill. Keys and values are
+    /// percent-encoded, existing parameters are kept, and a key can appear
+    /// more than once.
+    ///
+    /// ```rust
+    /// # fn run() {
+    /// let client = reqwest::Client::new();
+    /// let req = client
+    ///     .get("http://httpbin.org/get?page=2")
+    ///     .query_pairs([("tag", "rust"), ("tag", "http & co")]);
+    /// # }
+    /// ```
+    pub fn query_pairs<I, K, V>(self, pairs: I) -> RequestBuilder
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: AsRef<str>,
+        V: AsRef<str>,
+    {
+        let pairs = pairs.into_iter().collect::<Vec<_>>();
+        let pairs = pairs
+            .iter()
+            .map(|(key, value)| (key.as_ref(), value.as_ref()))
+            .collect::<Vec<_>>();
+        self.query(&pairs)
+    }
+
     /// Set HTTP version
+    ///
+    /// The request isn't downgraded if the connection can't use this
+    /// version; sending it fails with an error instead.
     pub fn version(mut self, version: Version) -> RequestBuilder {
         if let Ok(ref mut req) = self.request {
             req.version = version;
@@ -434,6 +793,9 @@ impl RequestBuilder {
 
     /// Send a JSON body.
     ///
+    /// Unless the request has an `Accept` header of its own when it's sent,
+    /// it also gets `Accept: application/json`. See `ClientBuilder::json_accept`.
+    ///
     /// # Optional
     ///
     /// This requires the optional `json` feature enabled.
@@ -453,6 +815,7 @@ impl RequestBuilder {
                         req.headers_mut()
                             .insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
                     }
+                    req.set_json_accept();
                     *req.body_mut() = Some(body.into());
                 }
                 Err(err) => error = Some(crate::error::builder(err)),
@@ -479,8 +842,11 @@ impl RequestBuilder {
 
     /// Build a `Request`, which can be inspected, modified and executed with
     /// `Client::execute()`.
+    ///
+    /// The client's default query parameters are merged in, so the request's
+    /// `url()` is the URL that will be requested.
     pub fn build(self) -> crate::Result<Request> {
-        self.request
+        self.build_split().1
     }
 
     /// Build a `Request`, which can be inspected, modified and executed with
@@ -489,7 +855,12 @@ impl RequestBuilder {
     /// This is similar to [`RequestBuilder::build()`], but also returns the
     /// embedded `Client`.
     pub fn build_split(self) -> (Client, crate::Result<Request>) {
-        (self.client, self.request)
+        let client = self.client;
+        let request = self.request.map(|mut req| {
+            client.merge_default_query(req.url_mut());
+            req
+        });
+        (client, request)
     }
 
     /// Constructs the Request and sends it to the target URL, returning a
@@ -520,6 +891,70 @@ impl RequestBuilder {
         }
     }
 
+    /// Sends the request, and reads and discards the response body,
+    /// returning just the status.
+    ///
+    /// This is for requests whose response body doesn't matter, such as
+    /// webhooks or pings. Reading the body to the end lets the connection be
+    /// reused, which dropping an unread `Response` doesn't.
+    ///
+    /// # Errors
+    ///
+    /// This method fails like [`send`](RequestBuilder::send), or if the
+    /// response body couldn't be read.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # async fn run() -> Result<(), reqwest::Error> {
+    /// let status = reqwest::Client::new()
+    ///     .post("https://hooks.example/notify")
+    ///     .send_and_drain()
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn send_and_drain(self) -> crate::Result<crate::StatusCode> {
+        let mut res = self.send().await?;
+        while res.chunk().await?.is_some() {}
+        Ok(res.status())
+    }
+
+    /// Sends the request, failing fast on an error status while leaving
+    /// successful responses to be streamed.
+    ///
+    /// The body of a successful response is not read, so it can be streamed,
+    /// for instance with [`Response::chunk`]. If the server
+    /// responds with a 4xx or 5xx status instead, the (usually small) body is
+    /// read, and an error is returned like with
+    /// [`Response::error_for_status`]. The body remains available through
+    /// [`Error::response_body`](crate::Error::response_body).
+    ///
+    /// # Errors
+    ///
+    /// This method fails if there was an error while sending request,
+    /// redirect loop was detected, redirect limit was exhausted, or the
+    /// server returned an error status.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # async fn run() -> Result<(), reqwest::Error> {
+    /// let mut response = reqwest::Client::new()
+    ///     .get("https://hyper.rs/large-download")
+    ///     .send_streaming()
+    ///     .await?;
+    ///
+    /// while let Some(chunk) = response.chunk().await? {
+    ///     println!("Chunk: {chunk:?}");
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn send_streaming(self) -> crate::Result<Response> {
+        self.send().await?.error_for_status_with_body().await
+    }
+
     /// Attempt to clone the RequestBuilder.
     ///
     /// `None` is returned if the RequestBuilder can not be cloned,
@@ -573,7 +1008,7 @@ fn fmt_request_fields<'a, 'b>(
 ) -> &'a mut fmt::DebugStruct<'a, 'b> {
     f.field("method", &req.method)
         .field("url", &req.url)
-        .field("headers", &req.headers)
+        .field("headers", &crate::util::RedactedHeaders(&req.headers))
 }
 
 /// Check the request URL for a "username:password" type authority, and if
@@ -924,6 +1359,30 @@ mod tests {
         assert_eq!(req.version(), Version::HTTP_11);
     }
 
+    #[test]
+    fn convert_http_request_round_trip() {
+        let http_request = HttpRequest::builder()
+            .method("PUT")
+            .uri("https://example.com/items/1?q=2")
+            .header("X-Custom", "value")
+            .body("data")
+            .unwrap();
+        let req = Request::try_from(http_request).unwrap();
+        let http_request = HttpRequest::try_from(req).unwrap();
+
+        assert_eq!(http_request.method(), Method::PUT);
+        assert_eq!(http_request.uri(), "https://example.com/items/1?q=2");
+        assert_eq!(http_request.headers()["X-Custom"], "value");
+        assert_eq!(http_request.body().as_bytes(), Some(&b"data"[..]));
+    }
+
+    #[test]
+    fn convert_from_http_request_relative_uri() {
+        let http_request = HttpRequest::builder().uri("/relative").body("").unwrap();
+        let err = Request::try_from(http_request).unwrap_err();
+        assert!(err.is_builder());
+    }
+
     #[test]
     fn builder_split_reassemble() {
         let builder = {
@@ -3,12 +3,12 @@ use std::net::SocketAddr;
 use std::pin::Pin;
 use std::time::Duration;
 
-use bytes::Bytes;
+use bytes::{Bytes, BytesMut};
 use http_body_util::BodyExt;
 use hyper::{HeaderMap, StatusCode, Version};
 use hyper_util::client::legacy::connect::HttpInfo;
 #[cfg(feature = "json")]
-use serde::de::DeserializeOwned;
+use serde::de::{DeserializeOwned, DeserializeSeed};
 #[cfg(feature = "json")]
 use serde_json;
 use tokio::time::Sleep;
@@ -25,12 +25,19 @@ use encoding_rs::{Encoding, UTF_8};
 #[cfg(feature = "charset")]
 use mime::Mime;
 
+/// The longest line `Response::json_stream` will buffer before failing.
+#[cfg(all(feature = "stream", feature = "json"))]
+const MAX_JSON_LINE_LEN: usize = 4 * 1024 * 1024;
+
 /// A Response to a submitted `Request`.
 pub struct Response {
     pub(super) res: hyper::Response<Decoder>,
     // Boxed to save space (11 words to 1 word), and it's not accessed
     // frequently internally.
     url: Box<Url>,
+    elapsed: Duration,
+    // A response to a `HEAD` request never has a body.
+    head: bool,
 }
 
 impl Response {
@@ -40,18 +47,28 @@ impl Response {
         accepts: Accepts,
         total_timeout: Option<Pin<Box<Sleep>>>,
         read_timeout: Option<Duration>,
+        elapsed: Duration,
+        head: bool,
     ) -> Response {
         let (mut parts, body) = res.into_parts();
-        let decoder = Decoder::detect(
-            &mut parts.headers,
-            super::body::response(body, total_timeout, read_timeout),
-            accepts,
-        );
+        // The headers of a `HEAD` response describe the body a `GET` would
+        // get, so they're kept as is.
+        let decoder = if head {
+            Decoder::empty()
+        } else {
+            Decoder::detect(
+                &mut parts.headers,
+                super::body::response(body, total_timeout, read_timeout),
+                accepts,
+            )
+        };
         let res = hyper::Response::from_parts(parts, decoder);
 
         Response {
             res,
             url: Box::new(url),
+            elapsed,
+            head,
         }
     }
 
@@ -61,6 +78,87 @@ impl Response {
         self.res.status()
     }
 
+    /// Get the reason phrase of the status, such as `"Not Found"`.
+    ///
+    /// This is the phrase the server sent if it differs from the canonical
+    /// one, which is only possible over HTTP/1. Otherwise, it's the canonical
+    /// reason for the status code, if there is one.
+    pub fn status_reason(&self) -> Option<&str> {
+        self.res
+            .extensions()
+            .get::<hyper::ext::ReasonPhrase>()
+            .and_then(|reason| std::str::from_utf8(reason.as_bytes()).ok())
+            .or_else(|| self.status().canonical_reason())
+    }
+
+    /// Get the URLs this response was redirected from, in order.
+    ///
+    /// The final URL, [`url()`](Response::url), isn't included, so this is
+    /// empty if the request wasn't redirected.
+    pub fn redirect_chain(&self) -> &[Url] {
+        self.extensions()
+            .get::<crate::redirect::RedirectChain>()
+            .map_or(&[], |chain| &chain.0)
+    }
+
+    /// Get the time it took from sending the request until this response's
+    /// headers were received, including any redirects and retries.
+    ///
+    /// This is zero for a `Response` converted from an `http::Response`,
+    /// unless that one was converted from a `Response` itself.
+    pub fn elapsed(&self) -> Duration {
+        self.elapsed
+    }
+
+    /// Returns true if the server sent only part of the content, with
+    /// `206 Partial Content`, in answer to a range request.
+    #[inline]
+    pub fn is_partial(&self) -> bool {
+        self.status() == StatusCode::PARTIAL_CONTENT
+    }
+
+    /// Returns true if the server answered a conditional request with
+    /// `304 Not Modified`, meaning a previously received copy of the resource
+    /// is still current.
+    #[inline]
+    pub fn is_not_modified(&self) -> bool {
+        self.status() == StatusCode::NOT_MODIFIED
+    }
+
+    /// Get whether the server supports range requests for this resource,
+    /// from the `Accept-Ranges` header.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+    /// use reqwest::AcceptRanges;
+    ///
+    /// let res = reqwest::Client::new()
+    ///     .head("https://hyper.rs/download")
+    ///     .send()
+    ///     .await?;
+    /// if res.accept_ranges() == AcceptRanges::Bytes {
+    ///     // resume the download with a `Range` header
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn accept_ranges(&self) -> AcceptRanges {
+        AcceptRanges::from_headers(self.headers())
+    }
+
+    /// Returns whether the connection this response arrived on was reused
+    /// from the connection pool, rather than newly established.
+    ///
+    /// Returns `None` when this isn't known, such as for responses received
+    /// over HTTP/3 or built from an `http::Response`.
+    pub fn connection_reused(&self) -> Option<bool> {
+        self.extensions()
+            .get::<crate::connect::ConnectionReused>()
+            .map(|reused| reused.0)
+    }
+
     /// Get the HTTP `Version` of this `Response`.
     #[inline]
     pub fn version(&self) -> Version {
@@ -85,18 +183,85 @@ impl Response {
     /// header, but rather the size of the response's body. To read the header's
     /// value, please use the [`Response::headers`] method instead.
     ///
+    /// For a response to a `HEAD` request, which has no body, this is the
+    /// length given by the `Content-Length` header instead: the length of the
+    /// body the same `GET` request would get.
+    ///
     /// Reasons it may not be known:
     ///
-    /// - The response does not include a body (e.g. it responds to a `HEAD`
-    ///   request).
+    /// - The response doesn't include a body, and isn't for a `HEAD` request
+    ///   with a `Content-Length` header.
     /// - The response is gzipped and automatically decoded (thus changing the
     ///   actual decoded length).
     pub fn content_length(&self) -> Option<u64> {
         use hyper::body::Body;
 
+        if self.head {
+            return self
+                .headers()
+                .get(crate::header::CONTENT_LENGTH)?
+                .to_str()
+                .ok()?
+                .parse()
+                .ok();
+        }
         Body::size_hint(self.res.body()).exact()
     }
 
+    /// Get the parsed `Content-Type` of this `Response`, if present.
+    ///
+    /// Returns `None` if the header is missing, or if its value cannot be
+    /// parsed as a media type.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+    /// let res = reqwest::get("http://httpbin.org/json").await?;
+    /// let is_json = res
+    ///     .content_type()
+    ///     .map_or(false, |mime| mime.subtype() == "json");
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Optional
+    ///
+    /// This requires the optional `charset` feature enabled.
+    #[cfg(feature = "charset")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "charset")))]
+    pub fn content_type(&self) -> Option<Mime> {
+        self.headers()
+            .get(crate::header::CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.parse::<Mime>().ok())
+    }
+
+    /// Get how long the server asked to wait before retrying, from the
+    /// `Retry-After` header.
+    ///
+    /// Both forms of the header are supported: a number of seconds, or an
+    /// HTTP-date. A date is measured relative to the response's `Date` header
+    /// if present, or the current time otherwise, and a date in the past
+    /// yields a zero duration.
+    ///
+    /// Returns `None` if the header is missing or cannot be parsed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+    /// let res = reqwest::get("http://httpbin.org/status/503").await?;
+    /// if let Some(wait) = res.retry_after() {
+    ///     tokio::time::sleep(wait).await;
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn retry_after(&self) -> Option<Duration> {
+        crate::util::retry_after(self.headers(), std::time::SystemTime::now())
+    }
+
     /// Retrieve the cookies contained in the response.
     ///
     /// Note that invalid 'Set-Cookie' headers will be ignored.
@@ -116,6 +281,17 @@ impl Response {
         &self.url
     }
 
+    /// Get a mutable reference to the final `Url` of this `Response`.
+    ///
+    /// This lets middleware normalize or rewrite the URL before the response
+    /// reaches the caller. Errors created from this response afterwards,
+    /// such as by [`error_for_status`](Response::error_for_status), carry
+    /// the new URL.
+    #[inline]
+    pub fn url_mut(&mut self) -> &mut Url {
+        &mut self.url
+    }
+
     /// Get the remote address used to get this `Response`.
     pub fn remote_addr(&self) -> Option<SocketAddr> {
         self.res
@@ -124,7 +300,36 @@ impl Response {
             .map(|info| info.remote_addr())
     }
 
+    /// Get the TLS information of the connection this `Response` arrived on.
+    ///
+    /// Returns `None` unless the request was sent over TLS by a `Client`
+    /// built with [`ClientBuilder::tls_info`](crate::ClientBuilder::tls_info)
+    /// enabled.
+    ///
+    /// # Optional
+    ///
+    /// This requires the optional `default-tls`, `native-tls`, or `rustls-tls(-...)`
+    /// feature to be enabled.
+    #[cfg(feature = "__tls")]
+    #[cfg_attr(
+        docsrs,
+        doc(cfg(any(
+            feature = "default-tls",
+            feature = "native-tls",
+            feature = "rustls-tls"
+        )))
+    )]
+    pub fn tls_info(&self) -> Option<&crate::tls::TlsInfo> {
+        self.extensions().get::<crate::tls::TlsInfo>()
+    }
+
     /// Returns a reference to the associated extensions.
+    ///
+    /// Besides what the connection and TLS layers record, such as
+    /// [`tls_info`](Response::tls_info), middleware can stash typed data here
+    /// for later layers. Extensions are kept when converting to and from an
+    /// `http::Response`, as are the [`url`](Response::url) and
+    /// [`elapsed`](Response::elapsed) time.
     pub fn extensions(&self) -> &http::Extensions {
         self.res.extensions()
     }
@@ -142,6 +347,7 @@ impl Response {
     /// and with malformed sequences replaced with the
     /// [`char::REPLACEMENT_CHARACTER`].
     /// Encoding is determined from the `charset` parameter of `Content-Type` header,
+    /// whatever the media type, such as `application/json; charset=iso-8859-1`,
     /// and defaults to `utf-8` if not presented.
     ///
     /// Note that the BOM is stripped from the returned String.
@@ -178,6 +384,46 @@ impl Response {
         }
     }
 
+    /// Get the full response text, replacing malformed sequences.
+    ///
+    /// This is the same as [`text`](Response::text), named to contrast with
+    /// [`text_strict`](Response::text_strict).
+    pub async fn text_lossy(self) -> crate::Result<String> {
+        self.text().await
+    }
+
+    /// Get the full response text, failing if it isn't valid UTF-8.
+    ///
+    /// Unlike [`text`](Response::text), malformed sequences are an error
+    /// rather than replaced, so corrupted content can be detected. The body
+    /// is always decoded as UTF-8, whatever the `charset` parameter of the
+    /// `Content-Type` header. A UTF-8 BOM is stripped.
+    ///
+    /// # Errors
+    ///
+    /// This method fails if the body isn't valid UTF-8, or couldn't be read.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+    /// let content = reqwest::get("http://httpbin.org/range/26")
+    ///     .await?
+    ///     .text_strict()
+    ///     .await?;
+    ///
+    /// println!("text: {content:?}");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn text_strict(self) -> crate::Result<String> {
+        let full = self.bytes().await?;
+        let full = full.strip_prefix(b"\xEF\xBB\xBF").unwrap_or(&full);
+        std::str::from_utf8(full)
+            .map(str::to_owned)
+            .map_err(crate::error::decode)
+    }
+
     /// Get the full response text given a specific encoding.
     ///
     /// This method decodes the response body with BOM sniffing
@@ -186,6 +432,10 @@ impl Response {
     /// `charset` parameter of `Content-Type` header is still prioritized. For more information
     /// about the possible encoding name, please go to [`encoding_rs`] docs.
     ///
+    /// If the body starts with a UTF-8 or UTF-16 byte order mark, the BOM is
+    /// authoritative and overrides both the `charset` parameter and the default
+    /// encoding, matching how browsers behave.
+    ///
     /// Note that the BOM is stripped from the returned String.
     ///
     /// [`encoding_rs`]: https://docs.rs/encoding_rs/0.8/encoding_rs/#relationship-with-windows-code-pages
@@ -210,11 +460,7 @@ impl Response {
     #[cfg(feature = "charset")]
     #[cfg_attr(docsrs, doc(cfg(feature = "charset")))]
     pub async fn text_with_charset(self, default_encoding: &str) -> crate::Result<String> {
-        let content_type = self
-            .headers()
-            .get(crate::header::CONTENT_TYPE)
-            .and_then(|value| value.to_str().ok())
-            .and_then(|value| value.parse::<Mime>().ok());
+        let content_type = self.content_type();
         let encoding_name = content_type
             .as_ref()
             .and_then(|mime| mime.get_param("charset").map(|charset| charset.as_str()))
@@ -223,7 +469,63 @@ impl Response {
 
         let full = self.bytes().await?;
 
-        let (text, _, _) = encoding.decode(&full);
+        Ok(decode_text(&full, encoding))
+    }
+
+    /// Get the full response text, falling back to other encodings if the
+    /// declared one doesn't fit.
+    ///
+    /// The body is first decoded with the `charset` parameter of the
+    /// `Content-Type` header, or UTF-8 if there is none. If that produces
+    /// malformed sequences, which happens when a server mislabels its
+    /// content, each encoding in `fallbacks` is tried in order, and the first
+    /// one decoding cleanly is used. Unknown encoding labels are skipped.
+    ///
+    /// If none of them decodes cleanly, the result of the declared encoding
+    /// is returned, with malformed sequences replaced with the
+    /// [`char::REPLACEMENT_CHARACTER`].
+    ///
+    /// # Optional
+    ///
+    /// This requires the optional `encoding_rs` feature enabled.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+    /// let content = reqwest::get("http://httpbin.org/range/26")
+    ///     .await?
+    ///     .text_with_fallbacks(&["windows-1252"])
+    ///     .await?;
+    ///
+    /// println!("text: {content:?}");
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "charset")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "charset")))]
+    pub async fn text_with_fallbacks(self, fallbacks: &[&str]) -> crate::Result<String> {
+        let content_type = self.content_type();
+        let encoding = content_type
+            .as_ref()
+            .and_then(|mime| mime.get_param("charset"))
+            .and_then(|charset| Encoding::for_label(charset.as_str().as_bytes()))
+            .unwrap_or(UTF_8);
+
+        let full = self.bytes().await?;
+
+        let (text, _, malformed) = encoding.decode(&full);
+        if malformed {
+            for fallback in fallbacks
+                .iter()
+                .filter_map(|l| Encoding::for_label(l.as_bytes()))
+            {
+                let (text, _, malformed) = fallback.decode(&full);
+                if !malformed {
+                    return Ok(text.into_owned());
+                }
+            }
+        }
         Ok(text.into_owned())
     }
 
@@ -276,6 +578,98 @@ impl Response {
         serde_json::from_slice(&full).map_err(crate::error::decode)
     }
 
+    /// Try to deserialize the response body as JSON, using a stateful
+    /// [`DeserializeSeed`].
+    ///
+    /// This is useful when deserialization needs access to external state,
+    /// which a plain [`DeserializeOwned`] type cannot carry.
+    ///
+    /// # Optional
+    ///
+    /// This requires the optional `json` feature enabled.
+    ///
+    /// # Errors
+    ///
+    /// This method fails whenever the response body is not in JSON format,
+    /// or the `seed` fails to deserialize it.
+    #[cfg(feature = "json")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "json")))]
+    pub async fn json_with_seed<S, T>(self, seed: S) -> crate::Result<T>
+    where
+        S: for<'de> DeserializeSeed<'de, Value = T>,
+    {
+        let full = self.bytes().await?;
+
+        let mut de = serde_json::Deserializer::from_slice(&full);
+        let value = seed.deserialize(&mut de).map_err(crate::error::decode)?;
+        de.end().map_err(crate::error::decode)?;
+        Ok(value)
+    }
+
+    /// Try to deserialize the response body as JSON, falling back to its
+    /// text.
+    ///
+    /// The body is read once. If it isn't valid JSON for `T`, it's decoded
+    /// like [`text`](Response::text) and returned as `Err`, so the caller
+    /// can see what the server actually sent, such as an HTML error page.
+    ///
+    /// # Optional
+    ///
+    /// This requires the optional `json` feature enabled.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # extern crate reqwest;
+    /// # extern crate serde;
+    /// #
+    /// # use reqwest::Error;
+    /// # use serde::Deserialize;
+    /// #
+    /// #[derive(Deserialize)]
+    /// struct Ip {
+    ///     origin: String,
+    /// }
+    ///
+    /// # async fn run() -> Result<(), Error> {
+    /// let res = reqwest::get("http://httpbin.org/ip").await?;
+    /// match res.json_or_text::<Ip>().await? {
+    ///     Ok(ip) => println!("ip: {}", ip.origin),
+    ///     Err(text) => println!("unexpected response: {text}"),
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This method fails only if the body can't be read.
+    #[cfg(feature = "json")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "json")))]
+    pub async fn json_or_text<T: DeserializeOwned>(self) -> crate::Result<Result<T, String>> {
+        #[cfg(feature = "charset")]
+        let encoding = self
+            .content_type()
+            .as_ref()
+            .and_then(|mime| mime.get_param("charset"))
+            .and_then(|charset| Encoding::for_label(charset.as_str().as_bytes()))
+            .unwrap_or(UTF_8);
+
+        let full = self.bytes().await?;
+
+        Ok(serde_json::from_slice(&full).map_err(|_| {
+            #[cfg(feature = "charset")]
+            {
+                decode_text(&full, encoding)
+            }
+
+            #[cfg(not(feature = "charset"))]
+            {
+                String::from_utf8_lossy(&full).into_owned()
+            }
+        }))
+    }
+
     /// Get the full response body as `Bytes`.
     ///
     /// # Example
@@ -294,9 +688,130 @@ impl Response {
     pub async fn bytes(self) -> crate::Result<Bytes> {
         use http_body_util::BodyExt;
 
+        let url = self.url;
         BodyExt::collect(self.res.into_body())
             .await
             .map(|buf| buf.to_bytes())
+            .map_err(|e| e.with_url(*url))
+    }
+
+    /// Get the full response body as `Bytes`, failing if it's larger than
+    /// `max` bytes.
+    ///
+    /// This is useful to protect against unexpectedly large responses from an
+    /// endpoint known to send small ones. Reading stops as soon as the limit
+    /// is exceeded, or right away if the `Content-Length` is over it.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+    /// let bytes = reqwest::get("http://httpbin.org/ip")
+    ///     .await?
+    ///     .bytes_limited(64 * 1024)
+    ///     .await?;
+    ///
+    /// println!("bytes: {bytes:?}");
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This method fails with a body error if the body is larger than `max`,
+    /// or if it can't be read, like [`bytes()`](Response::bytes).
+    pub async fn bytes_limited(mut self, max: usize) -> crate::Result<Bytes> {
+        let too_large = |url: &Url| {
+            crate::error::body(crate::error::BodyLimitExceeded(max)).with_url(url.clone())
+        };
+
+        if self.content_length().map_or(false, |len| len > max as u64) {
+            return Err(too_large(&self.url));
+        }
+
+        let mut buf = BytesMut::new();
+        while let Some(chunk) = self.chunk().await? {
+            if buf.len() + chunk.len() > max {
+                return Err(too_large(&self.url));
+            }
+            buf.extend_from_slice(&chunk);
+        }
+        Ok(buf.freeze())
+    }
+
+    /// Get the full response body as `Bytes`, reporting progress along the
+    /// way.
+    ///
+    /// `progress` is called with the total number of bytes received so far
+    /// each time a chunk of the body arrives. Together with
+    /// [`content_length()`](Response::content_length), this is enough to
+    /// show a download progress bar.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+    /// let res = reqwest::get("http://httpbin.org/bytes/4096").await?;
+    /// let total = res.content_length();
+    /// let bytes = res
+    ///     .bytes_with_progress(|received| match total {
+    ///         Some(total) => println!("{received}/{total} bytes"),
+    ///         None => println!("{received} bytes"),
+    ///     })
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn bytes_with_progress<F>(mut self, mut progress: F) -> crate::Result<Bytes>
+    where
+        F: FnMut(u64),
+    {
+        let mut buf = BytesMut::new();
+        while let Some(chunk) = self.chunk().await? {
+            buf.extend_from_slice(&chunk);
+            progress(buf.len() as u64);
+        }
+        Ok(buf.freeze())
+    }
+
+    /// Convert the response into an `http::Response` with the full body.
+    ///
+    /// The status, version, headers and extensions are kept, and the URL is
+    /// added as an extension, so that converting back with
+    /// [`Response::from`] gives the same [`url()`](Response::url).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+    /// let res: http::Response<bytes::Bytes> = reqwest::get("http://httpbin.org/ip")
+    ///     .await?
+    ///     .into_http()
+    ///     .await?;
+    ///
+    /// println!("status: {}, body: {:?}", res.status(), res.body());
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This method fails if the response body can't be read, in the same
+    /// way as [`bytes()`](Response::bytes).
+    pub async fn into_http(self) -> crate::Result<http::Response<Bytes>> {
+        use http_body_util::BodyExt;
+
+        let url = self.url;
+        let (mut parts, body) = self.res.into_parts();
+        let body = BodyExt::collect(body)
+            .await
+            .map(|buf| buf.to_bytes())
+            .map_err(|e| e.with_url((*url).clone()))?;
+        parts.extensions.insert(crate::response::ResponseUrl(*url));
+        parts
+            .extensions
+            .insert(crate::response::ResponseElapsed(self.elapsed));
+        Ok(http::Response::from_parts(parts, body))
     }
 
     /// Stream a chunk of the response body.
@@ -321,7 +836,7 @@ impl Response {
         // loop to ignore unrecognized frames
         loop {
             if let Some(res) = self.res.body_mut().frame().await {
-                let frame = res?;
+                let frame = res.map_err(|e| e.with_url((*self.url).clone()))?;
                 if let Ok(buf) = frame.into_data() {
                     return Ok(Some(buf));
                 }
@@ -351,13 +866,168 @@ impl Response {
     /// # }
     /// ```
     ///
+    /// Chunks are never empty, so when the length of the body is known, such
+    /// as from a `Content-Length` header, the stream's `size_hint` bounds the
+    /// number of chunks left by the number of bytes left.
+    ///
+    /// An error while reading the body, such as the connection closing
+    /// mid-body, is yielded as an `Err` item carrying the response URL, after
+    /// the chunks received before it.
+    ///
     /// # Optional
     ///
     /// This requires the optional `stream` feature to be enabled.
     #[cfg(feature = "stream")]
     #[cfg_attr(docsrs, doc(cfg(feature = "stream")))]
     pub fn bytes_stream(self) -> impl futures_core::Stream<Item = crate::Result<Bytes>> {
-        super::body::DataStream(self.res.into_body())
+        super::body::DataStream::new(self.res.into_body()).with_url(self.url)
+    }
+
+    /// Convert the response into a `Stream` of values deserialized from
+    /// newline-delimited JSON, such as [NDJSON] or [JSON Lines].
+    ///
+    /// A value is yielded as soon as its line has arrived, however the body
+    /// is split into chunks. Blank lines are skipped. A last line without a
+    /// trailing newline is deserialized too, so a body cut off in the middle
+    /// of a value ends with an error.
+    ///
+    /// [NDJSON]: https://github.com/ndjson/ndjson-spec
+    /// [JSON Lines]: https://jsonlines.org
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use futures_util::StreamExt;
+    ///
+    /// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut events = reqwest::get("http://httpbin.org/stream/3")
+    ///     .await?
+    ///     .json_stream::<serde_json::Value>();
+    ///
+    /// while let Some(event) = events.next().await {
+    ///     println!("event: {}", event?);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Optional
+    ///
+    /// This requires the optional `stream` and `json` features to be enabled.
+    #[cfg(all(feature = "stream", feature = "json"))]
+    #[cfg_attr(docsrs, doc(cfg(all(feature = "stream", feature = "json"))))]
+    pub fn json_stream<T: DeserializeOwned>(
+        self,
+    ) -> impl futures_core::Stream<Item = crate::Result<T>> {
+        use futures_util::StreamExt;
+
+        let body = super::body::DataStream::new(self.res.into_body()).with_url(self.url);
+        // The start of `buf` known to have no newline, so it isn't searched
+        // again as more chunks arrive.
+        let scanned = 0;
+        let state = (body, BytesMut::new(), scanned, false);
+        // Boxed to be `Unpin`, like `bytes_stream`.
+        Box::pin(futures_util::stream::unfold(
+            state,
+            |(mut body, mut buf, mut scanned, mut done)| async move {
+                loop {
+                    let line = if let Some(end) = buf[scanned..].iter().position(|&b| b == b'\n') {
+                        let line = buf.split_to(scanned + end + 1);
+                        scanned = 0;
+                        line
+                    } else if done {
+                        scanned = 0;
+                        buf.split()
+                    } else if buf.len() > MAX_JSON_LINE_LEN {
+                        // A server that never ends the line can't make the
+                        // buffer grow without bound.
+                        buf.clear();
+                        let err = crate::error::decode("JSON line too long");
+                        return Some((Err(err), (body, buf, 0, true)));
+                    } else {
+                        scanned = buf.len();
+                        match body.next().await {
+                            Some(Ok(chunk)) => buf.extend_from_slice(&chunk),
+                            Some(Err(err)) => {
+                                buf.clear();
+                                return Some((Err(err), (body, buf, 0, true)));
+                            }
+                            None => done = true,
+                        }
+                        continue;
+                    };
+
+                    if line.iter().all(u8::is_ascii_whitespace) {
+                        if done && buf.is_empty() {
+                            return None;
+                        }
+                        continue;
+                    }
+                    let value = if line.len() > MAX_JSON_LINE_LEN {
+                        Err(crate::error::decode("JSON line too long"))
+                    } else {
+                        serde_json::from_slice(&line).map_err(crate::error::decode)
+                    };
+                    return Some((value, (body, buf, scanned, done)));
+                }
+            },
+        ))
+    }
+
+    /// Convert the response into a `Stream` of [Server-Sent Events][sse].
+    ///
+    /// The body is parsed in the `text/event-stream` format, whatever the
+    /// `Content-Type` of the response is. Comment lines are skipped, and the
+    /// lines of multi-line `data` fields are joined. An event is yielded once
+    /// the blank line ending it has arrived, so an event left unfinished when
+    /// the body ends is dropped.
+    ///
+    /// [sse]: crate::sse
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use futures_util::StreamExt;
+    ///
+    /// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut events = reqwest::get("http://example.com/notifications")
+    ///     .await?
+    ///     .sse();
+    ///
+    /// while let Some(event) = events.next().await {
+    ///     let event = event?;
+    ///     println!("{}: {}", event.event(), event.data());
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Optional
+    ///
+    /// This requires the optional `sse` feature to be enabled.
+    #[cfg(feature = "sse")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "sse")))]
+    pub fn sse(self) -> impl futures_core::Stream<Item = crate::Result<crate::sse::Event>> {
+        use futures_util::StreamExt;
+
+        let body = super::body::DataStream::new(self.res.into_body()).with_url(self.url);
+        let state = (body, crate::sse::Parser::default(), false);
+        // Boxed to be `Unpin`, like `bytes_stream`.
+        Box::pin(futures_util::stream::unfold(state, |(mut body, mut parser, mut done)| async move {
+            loop {
+                if let Some(event) = parser.next_event(done) {
+                    return Some((Ok(event), (body, parser, done)));
+                }
+                if done {
+                    return None;
+                }
+                match body.next().await {
+                    Some(Ok(chunk)) => parser.feed(&chunk),
+                    Some(Err(err)) => return Some((Err(err), (body, parser, true))),
+                    None => done = true,
+                }
+            }
+        }))
     }
 
     // util methods
@@ -424,6 +1094,42 @@ impl Response {
         }
     }
 
+    /// Turn a response into an error if the server returned an error,
+    /// keeping the body of the error response.
+    ///
+    /// This is like [`Response::error_for_status`], but on a 4xx or 5xx
+    /// status the body is read, and is available from
+    /// [`Error::response_body`](crate::Error::response_body). Many APIs put
+    /// details about what went wrong there. The start of the body is also
+    /// shown when the error is displayed.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # async fn run() -> Result<(), reqwest::Error> {
+    /// let res = reqwest::get("http://httpbin.org/status/400").await?;
+    /// match res.error_for_status_with_body().await {
+    ///     Ok(res) => println!("{}", res.text().await?),
+    ///     Err(err) => eprintln!("{err}"),
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn error_for_status_with_body(self) -> crate::Result<Self> {
+        let status = self.status();
+        if !status.is_client_error() && !status.is_server_error() {
+            return Ok(self);
+        }
+
+        let reason = self.extensions().get::<hyper::ext::ReasonPhrase>().cloned();
+        let err = crate::error::status_code((*self.url).clone(), status, reason);
+        // The status is the error that matters, even if the body fails.
+        match self.bytes().await {
+            Ok(body) => Err(err.with_response_body(body)),
+            Err(_) => Err(err),
+        }
+    }
+
     // private
 
     // The Response's body is an implementation detail.
@@ -437,12 +1143,63 @@ impl Response {
     }
 }
 
+/// Decode a full body as text with `encoding`, replacing malformed sequences.
+///
+/// A BOM is stripped, and wins over `encoding`, matching how browsers behave.
+#[cfg(feature = "charset")]
+fn decode_text(full: &Bytes, encoding: &'static Encoding) -> String {
+    let (text, _, _) = encoding.decode(full);
+    text.into_owned()
+}
+
+/// Whether a server supports range requests, as advertised in the
+/// `Accept-Ranges` header.
+///
+/// See [`Response::accept_ranges`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum AcceptRanges {
+    /// Ranges in bytes are supported.
+    Bytes,
+    /// Range requests are explicitly not supported.
+    None,
+    /// Only range units other than bytes are supported.
+    Other,
+    /// The server didn't say, so range requests may or may not work.
+    Unknown,
+}
+
+impl AcceptRanges {
+    pub(crate) fn from_headers(headers: &HeaderMap) -> AcceptRanges {
+        let mut ranges = AcceptRanges::Unknown;
+        for value in headers.get_all(crate::header::ACCEPT_RANGES) {
+            let value = match value.to_str() {
+                Ok(value) => value,
+                Err(_) => continue,
+            };
+            for unit in value.split(',').map(str::trim) {
+                if unit.eq_ignore_ascii_case("bytes") {
+                    return AcceptRanges::Bytes;
+                } else if unit.eq_ignore_ascii_case("none") {
+                    ranges = AcceptRanges::None;
+                } else if !unit.is_empty() && ranges == AcceptRanges::Unknown {
+                    ranges = AcceptRanges::Other;
+                }
+            }
+        }
+        ranges
+    }
+}
+
 impl fmt::Debug for Response {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        f.debug_struct("Response")
-            .field("url", &self.url().as_str())
-            .field("status", &self.status())
-            .field("headers", self.headers())
+        let mut f = f.debug_struct("Response");
+        f.field("url", &self.url().as_str())
+            .field("status", &self.status());
+        if let Some(reason) = self.status_reason() {
+            f.field("reason", &reason);
+        }
+        f.field("headers", &crate::util::RedactedHeaders(self.headers()))
             .finish()
     }
 }
@@ -458,7 +1215,7 @@ impl From<Response> for Body {
 // to use `http::Response`, not `reqwest::Response`.
 impl<T: Into<Body>> From<http::Response<T>> for Response {
     fn from(r: http::Response<T>) -> Response {
-        use crate::response::ResponseUrl;
+        use crate::response::{ResponseElapsed, ResponseUrl};
 
         let (mut parts, body) = r.into_parts();
         let body: crate::async_impl::body::Body = body.into();
@@ -472,10 +1229,16 @@ impl<T: Into<Body>> From<http::Response<T>> for Response {
             .remove::<ResponseUrl>()
             .unwrap_or_else(|| ResponseUrl(Url::parse("http://no.url.provided.local").unwrap()));
         let url = url.0;
+        let elapsed = parts
+            .extensions
+            .remove::<ResponseElapsed>()
+            .map_or(Duration::ZERO, |elapsed| elapsed.0);
         let res = hyper::Response::from_parts(parts, decoder);
         Response {
             res,
             url: Box::new(url),
+            elapsed,
+            head: false,
         }
     }
 }
@@ -484,7 +1247,13 @@ impl<T: Into<Body>> From<http::Response<T>> for Response {
 // It's supposed to be the inverse of the conversion above.
 impl From<Response> for http::Response<Body> {
     fn from(r: Response) -> http::Response<Body> {
-        let (parts, body) = r.res.into_parts();
+        let (mut parts, body) = r.res.into_parts();
+        parts
+            .extensions
+            .insert(crate::response::ResponseUrl(*r.url));
+        parts
+            .extensions
+            .insert(crate::response::ResponseElapsed(r.elapsed));
         let body = Body::wrap(body);
         http::Response::from_parts(parts, body)
     }
@@ -495,6 +1264,7 @@ mod tests {
     use super::Response;
     use crate::ResponseBuilderExt;
     use http::response::Builder;
+    use std::time::Duration;
     use url::Url;
 
     #[test]
@@ -510,4 +1280,317 @@ mod tests {
         assert_eq!(response.status(), 200);
         assert_eq!(*response.url(), url);
     }
+
+    #[cfg(feature = "charset")]
+    #[test]
+    fn test_content_type() {
+        let response = Builder::new()
+            .header("content-type", "application/json; charset=utf-8")
+            .body("{}")
+            .unwrap();
+        let response = Response::from(response);
+
+        let mime = response.content_type().expect("content type");
+        assert_eq!(mime.essence_str(), "application/json");
+        assert_eq!(mime.get_param("charset").unwrap(), "utf-8");
+
+        let missing = Response::from(Builder::new().body("").unwrap());
+        assert!(missing.content_type().is_none());
+
+        let invalid = Builder::new()
+            .header("content-type", "not a mime")
+            .body("")
+            .unwrap();
+        assert!(Response::from(invalid).content_type().is_none());
+    }
+
+    #[test]
+    fn test_error_for_status() {
+        let response = Builder::new().status(404).body("").unwrap();
+        let err = Response::from(response).error_for_status().unwrap_err();
+        assert!(err.is_status());
+        assert_eq!(err.status(), Some(http::StatusCode::NOT_FOUND));
+
+        let response = Builder::new().status(204).body("").unwrap();
+        assert!(Response::from(response).error_for_status().is_ok());
+    }
+
+    #[test]
+    fn test_error_for_status_ref() {
+        let response = Builder::new().status(503).body("").unwrap();
+        let response = Response::from(response);
+        let err = response.error_for_status_ref().unwrap_err();
+        assert!(err.is_status());
+        assert_eq!(err.status(), Some(http::StatusCode::SERVICE_UNAVAILABLE));
+
+        let response = Builder::new().status(302).body("").unwrap();
+        assert!(Response::from(response).error_for_status_ref().is_ok());
+    }
+
+    #[cfg(feature = "charset")]
+    #[tokio::test]
+    async fn test_text_with_fallbacks() {
+        // Labeled as UTF-8, but actually latin-1.
+        let response = Builder::new()
+            .header("content-type", "text/plain; charset=utf-8")
+            .body(&b"caf\xe9"[..])
+            .unwrap();
+        let text = Response::from(response)
+            .text_with_fallbacks(&["not-an-encoding", "latin1"])
+            .await
+            .unwrap();
+        assert_eq!(text, "café");
+
+        // A clean decode with the declared charset never falls back.
+        let response = Builder::new()
+            .header("content-type", "text/plain; charset=utf-8")
+            .body("café".as_bytes())
+            .unwrap();
+        let text = Response::from(response)
+            .text_with_fallbacks(&["latin1"])
+            .await
+            .unwrap();
+        assert_eq!(text, "café");
+
+        // Without a clean fallback, the lossy declared decode is kept.
+        let response = Builder::new().body(&b"caf\xe9"[..]).unwrap();
+        let text = Response::from(response)
+            .text_with_fallbacks(&["utf-8"])
+            .await
+            .unwrap();
+        assert_eq!(text, "caf\u{FFFD}");
+    }
+
+    #[tokio::test]
+    async fn test_into_http() {
+        let url = Url::parse("http://example.com/page").unwrap();
+        let response = Builder::new()
+            .status(201)
+            .header("x-custom", "value")
+            .url(url.clone())
+            .body("hello")
+            .unwrap();
+
+        let http = Response::from(response).into_http().await.unwrap();
+        assert_eq!(http.status(), 201);
+        assert_eq!(http.headers()["x-custom"], "value");
+        assert_eq!(http.body(), "hello");
+
+        let response = Response::from(http.map(crate::Body::from));
+        assert_eq!(response.url(), &url);
+    }
+
+    #[tokio::test]
+    async fn test_into_streaming_http_response() {
+        use http_body_util::BodyExt;
+
+        let response = Builder::new()
+            .status(202)
+            .version(http::Version::HTTP_2)
+            .header("x-custom", "value")
+            .body("streamed")
+            .unwrap();
+
+        let http: http::Response<crate::Body> = Response::from(response).into();
+        assert_eq!(http.status(), 202);
+        assert_eq!(http.version(), http::Version::HTTP_2);
+        assert_eq!(http.headers()["x-custom"], "value");
+
+        let body = http.into_body().collect().await.unwrap().to_bytes();
+        assert_eq!(body, "streamed");
+    }
+
+    #[test]
+    fn test_accept_ranges() {
+        use super::AcceptRanges;
+
+        let accept_ranges = |value: Option<&str>| {
+            let mut builder = Builder::new();
+            if let Some(value) = value {
+                builder = builder.header("accept-ranges", value);
+            }
+            Response::from(builder.body("").unwrap()).accept_ranges()
+        };
+
+        assert_eq!(accept_ranges(Some("bytes")), AcceptRanges::Bytes);
+        assert_eq!(accept_ranges(Some("none")), AcceptRanges::None);
+        assert_eq!(accept_ranges(Some("pages")), AcceptRanges::Other);
+        assert_eq!(accept_ranges(Some("pages, Bytes")), AcceptRanges::Bytes);
+        assert_eq!(accept_ranges(None), AcceptRanges::Unknown);
+    }
+
+    #[cfg(feature = "cookies")]
+    #[test]
+    fn test_cookies_skip_malformed() {
+        let response = Builder::new()
+            .header("set-cookie", "session=abc; Domain=example.com; Path=/app")
+            .header("set-cookie", "=no-name")
+            .header(
+                "set-cookie",
+                "theme=dark; Expires=Wed, 21 Oct 2015 07:28:00 GMT; HttpOnly",
+            )
+            .body("")
+            .unwrap();
+        let response = Response::from(response);
+        let cookies = response.cookies().collect::<Vec<_>>();
+
+        assert_eq!(cookies.len(), 2);
+        assert_eq!(cookies[0].name(), "session");
+        assert_eq!(cookies[0].value(), "abc");
+        assert_eq!(cookies[0].domain(), Some("example.com"));
+        assert_eq!(cookies[0].path(), Some("/app"));
+        assert_eq!(cookies[0].expires(), None);
+        assert_eq!(cookies[1].name(), "theme");
+        assert_eq!(cookies[1].value(), "dark");
+        assert_eq!(
+            cookies[1].expires(),
+            Some(std::time::UNIX_EPOCH + Duration::from_secs(1_445_412_480))
+        );
+        assert!(cookies[1].http_only());
+    }
+
+    #[cfg(feature = "__tls")]
+    #[test]
+    fn test_tls_info() {
+        let response = Builder::new()
+            .extension(crate::tls::TlsInfo {
+                peer_certificate: Some(b"der".to_vec()),
+                protocol_version: Some("TLSv1_3"),
+                cipher_suite: Some("TLS13_AES_128_GCM_SHA256"),
+            })
+            .body("")
+            .unwrap();
+        let response = Response::from(response);
+        let tls_info = response.tls_info().expect("tls info");
+        assert_eq!(tls_info.peer_certificate(), Some(&b"der"[..]));
+        assert_eq!(tls_info.protocol_version(), Some("TLSv1_3"));
+        assert_eq!(tls_info.cipher_suite(), Some("TLS13_AES_128_GCM_SHA256"));
+
+        let response = Response::from(Builder::new().body("").unwrap());
+        assert!(response.tls_info().is_none());
+    }
+
+    #[test]
+    fn test_retry_after_seconds() {
+        let response = Builder::new()
+            .header("retry-after", "120")
+            .body("")
+            .unwrap();
+        let response = Response::from(response);
+        assert_eq!(response.retry_after(), Some(Duration::from_secs(120)));
+    }
+
+    #[test]
+    fn test_retry_after_http_date() {
+        let response = Builder::new()
+            .header("date", "Wed, 21 Oct 2015 07:28:00 GMT")
+            .header("retry-after", "Wed, 21 Oct 2015 07:29:30 GMT")
+            .body("")
+            .unwrap();
+        let response = Response::from(response);
+        assert_eq!(response.retry_after(), Some(Duration::from_secs(90)));
+
+        // Without a `Date`, a date in the past is measured against now.
+        let response = Builder::new()
+            .header("retry-after", "Wed, 21 Oct 2015 07:29:30 GMT")
+            .body("")
+            .unwrap();
+        let response = Response::from(response);
+        assert_eq!(response.retry_after(), Some(Duration::ZERO));
+    }
+
+    #[test]
+    fn test_retry_after_invalid() {
+        let missing = Response::from(Builder::new().body("").unwrap());
+        assert_eq!(missing.retry_after(), None);
+
+        for value in ["soon", "-5", "1.5", ""] {
+            let response = Builder::new()
+                .header("retry-after", value)
+                .body("")
+                .unwrap();
+            assert_eq!(Response::from(response).retry_after(), None, "{value:?}");
+        }
+    }
+
+    #[cfg(feature = "json")]
+    #[tokio::test]
+    async fn test_json_error_source_chain() {
+        use std::error::Error as _;
+
+        let response = Builder::new().body("{\"key\": ").unwrap();
+        let err = Response::from(response)
+            .json::<serde_json::Value>()
+            .await
+            .unwrap_err();
+
+        assert!(err.is_decode());
+        // `Display` stays on one line, leaving the cause to `source()`.
+        assert_eq!(err.to_string(), "error decoding response body");
+
+        let mut chain = Vec::new();
+        let mut source = err.source();
+        while let Some(cause) = source {
+            chain.push(cause);
+            source = cause.source();
+        }
+        assert_eq!(chain.len(), 1, "{chain:?}");
+        let json = chain[0].downcast_ref::<serde_json::Error>().unwrap();
+        assert!(json.is_eof());
+    }
+
+    #[cfg(feature = "json")]
+    #[tokio::test]
+    async fn test_json_with_seed() {
+        use serde::de::{Deserialize, DeserializeSeed, Deserializer};
+
+        struct WithDefault(u32);
+
+        impl<'de> DeserializeSeed<'de> for WithDefault {
+            type Value = u32;
+
+            fn deserialize<D: Deserializer<'de>>(self, de: D) -> Result<u32, D::Error> {
+                Ok(Option::<u32>::deserialize(de)?.unwrap_or(self.0))
+            }
+        }
+
+        let response = Response::from(Builder::new().body("null").unwrap());
+        let value = response.json_with_seed(WithDefault(7)).await.unwrap();
+        assert_eq!(value, 7);
+
+        let response = Response::from(Builder::new().body("42").unwrap());
+        let value = response.json_with_seed(WithDefault(7)).await.unwrap();
+        assert_eq!(value, 42);
+
+        let response = Response::from(Builder::new().body("42 trailing").unwrap());
+        let err = response.json_with_seed(WithDefault(7)).await.unwrap_err();
+        assert!(err.is_decode());
+    }
+
+    #[cfg(feature = "charset")]
+    #[tokio::test]
+    async fn test_text_bom_overrides_charset() {
+        // "hi" in UTF-16 LE and BE, each with a BOM.
+        let bodies: [&'static [u8]; 2] = [b"\xFF\xFEh\x00i\x00", b"\xFE\xFF\x00h\x00i"];
+
+        for body in bodies {
+            let response = Builder::new()
+                .header("content-type", "text/plain; charset=utf-8")
+                .body(body)
+                .unwrap();
+            let text = Response::from(response).text().await.unwrap();
+            assert_eq!(text, "hi");
+        }
+    }
+
+    #[cfg(feature = "charset")]
+    #[tokio::test]
+    async fn test_text_utf8_bom_stripped() {
+        let response = Builder::new()
+            .header("content-type", "text/plain; charset=iso-8859-1")
+            .body(&b"\xEF\xBB\xBFcaf\xC3\xA9"[..])
+            .unwrap();
+        let text = Response::from(response).text().await.unwrap();
+        assert_eq!(text, "café");
+    }
 }
@@ -1,5 +1,6 @@
 #[cfg(any(feature = "native-tls", feature = "__rustls",))]
 use std::any::Any;
+use std::collections::HashMap;
 use std::convert::TryInto;
 use std::fmt;
 use std::future::Future;
@@ -33,7 +34,7 @@ use crate::tls::CertificateRevocationList;
 use crate::Certificate;
 #[cfg(any(feature = "native-tls", feature = "__rustls"))]
 use crate::Identity;
-use crate::{async_impl, header, redirect, IntoUrl, Method, Proxy};
+use crate::{async_impl, header, redirect, IntoUrl, Method, Proxy, Url};
 
 /// A `Client` to make Requests with.
 ///
@@ -146,6 +147,80 @@ impl ClientBuilder {
         self.with_inner(move |inner| inner.user_agent(value))
     }
 
+    /// Send a `User-Agent` of `reqwest/<version>` when no other one is set.
+    ///
+    /// The default is sent unless [`user_agent`](ClientBuilder::user_agent)
+    /// or [`default_headers`](ClientBuilder::default_headers) set one, no
+    /// matter in which order they're called. A request setting its own
+    /// `User-Agent` still replaces it.
+    ///
+    /// Default is `false`, sending no `User-Agent` at all.
+    pub fn default_user_agent_if_unset(self, enabled: bool) -> ClientBuilder {
+        self.with_inner(move |inner| inner.default_user_agent_if_unset(enabled))
+    }
+
+    /// Log a warning when a response body is dropped without being read.
+    ///
+    /// A `Response` dropped before any of its reading methods started
+    /// reading its body holds on to the connection for nothing, and often
+    /// hints at a bug. The warning is emitted with `tracing` if the `tracing`
+    /// feature is enabled, and `log` otherwise. Bodies that are known to be
+    /// empty, and responses to `HEAD` requests, never warn.
+    ///
+    /// Default is `false`.
+    pub fn warn_on_unread_body(self, enabled: bool) -> ClientBuilder {
+        self.with_inner(move |inner| inner.warn_on_unread_body(enabled))
+    }
+
+    /// Limit the client to sending `requests` requests `per` period.
+    ///
+    /// Requests are throttled with a token bucket: up to `requests` of them
+    /// are sent right away, and then one more every `per / requests`.
+    /// Sending waits until the bucket has a token, which counts towards the
+    /// [`timeout`](ClientBuilder::timeout). A request that couldn't get one
+    /// before its timeout fails right away, without waiting.
+    ///
+    /// Default is no limit.
+    ///
+    /// # Errors
+    ///
+    /// Building the `Client` fails if `requests` is zero.
+    pub fn rate_limit(self, requests: u32, per: Duration) -> ClientBuilder {
+        self.with_inner(move |inner| inner.rate_limit(requests, per))
+    }
+
+    /// Limit the number of requests this client has in flight at once.
+    ///
+    /// A request over the limit waits for another one to finish, which counts
+    /// towards the [`timeout`](ClientBuilder::timeout). A request is in
+    /// flight until its `Response` is dropped or its body fully read.
+    ///
+    /// Default is no limit.
+    ///
+    /// # Errors
+    ///
+    /// Building the `Client` fails if `max` is zero.
+    pub fn max_concurrent(self, max: usize) -> ClientBuilder {
+        self.with_inner(move |inner| inner.max_concurrent(max))
+    }
+
+    /// Cache the responses to `GET` requests in the given [`HttpCache`].
+    ///
+    /// A request for a URL with a fresh response in the cache is answered
+    /// from it, without a network call, and a stale one is revalidated. See
+    /// the [`cache`](crate::cache) module for which responses are stored.
+    ///
+    /// [`HttpCache`]: crate::cache::HttpCache
+    ///
+    /// # Optional
+    ///
+    /// This requires the optional `cache` feature to be enabled.
+    #[cfg(feature = "cache")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "cache")))]
+    pub fn cache(self, cache: Arc<dyn crate::cache::HttpCache>) -> ClientBuilder {
+        self.with_inner(move |inner| inner.cache(cache))
+    }
+
     /// Sets the default headers for every request.
     ///
     /// # Example
@@ -174,6 +249,72 @@ impl ClientBuilder {
         self.with_inner(move |inner| inner.default_headers(headers))
     }
 
+    /// Adds a default header for every request, keeping the default values
+    /// it already has.
+    ///
+    /// Where [`default_headers`](ClientBuilder::default_headers) replaces a
+    /// default header, this allows one that can repeat, like `Accept`, to
+    /// have several values. As with other default headers, a request that
+    /// sets the header itself doesn't get any of them.
+    ///
+    /// Appending to `Accept` keeps the client's own `*/*` default, so replace
+    /// that first if it isn't wanted.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use reqwest::header::{self, ACCEPT};
+    /// # fn doc() -> Result<(), reqwest::Error> {
+    /// let mut headers = header::HeaderMap::new();
+    /// headers.insert(ACCEPT, header::HeaderValue::from_static("application/json"));
+    ///
+    /// let client = reqwest::blocking::Client::builder()
+    ///     .default_headers(headers)
+    ///     .default_header_append(ACCEPT, "text/plain")
+    ///     .build()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn default_header_append<K, V>(self, key: K, value: V) -> ClientBuilder
+    where
+        header::HeaderName: TryFrom<K>,
+        <header::HeaderName as TryFrom<K>>::Error: Into<http::Error>,
+        header::HeaderValue: TryFrom<V>,
+        <header::HeaderValue as TryFrom<V>>::Error: Into<http::Error>,
+    {
+        self.with_inner(move |inner| inner.default_header_append(key, value))
+    }
+
+    /// Sets a default `Authorization: Bearer` header for every request.
+    ///
+    /// Like other default headers, it isn't added to a request that sets its
+    /// own `Authorization` header, such as with
+    /// [`RequestBuilder::bearer_auth`](crate::blocking::RequestBuilder::bearer_auth).
+    ///
+    /// # Errors
+    ///
+    /// Building the `Client` fails if the token isn't a valid header value.
+    pub fn bearer_auth<T>(self, token: T) -> ClientBuilder
+    where
+        T: fmt::Display,
+    {
+        self.with_inner(move |inner| inner.bearer_auth(token))
+    }
+
+    /// Sets default query parameters to add to the URL of every request.
+    ///
+    /// A default parameter is only added if the request's URL doesn't already
+    /// have a parameter with the same name. Calling this again adds to the
+    /// parameters set before.
+    ///
+    /// # Errors
+    ///
+    /// Building the `Client` fails if `query` can't be serialized as query
+    /// parameters.
+    pub fn default_query<T: serde::Serialize + ?Sized>(self, query: &T) -> ClientBuilder {
+        self.with_inner(move |inner| inner.default_query(query))
+    }
+
     /// Enable a persistent cookie store for the client.
     ///
     /// Cookies received in responses will be preserved and included in
@@ -367,6 +508,20 @@ impl ClientBuilder {
         self.with_inner(move |inner| inner.proxy(proxy))
     }
 
+    /// Add the proxies configured by environment variables to the list of
+    /// proxies the `Client` will use.
+    ///
+    /// This reads `HTTP_PROXY`, `HTTPS_PROXY`, `ALL_PROXY` and `NO_PROXY`
+    /// (or their lowercase versions) when this is called.
+    ///
+    /// # Note
+    ///
+    /// Like adding any proxy, this disables the automatic usage of the
+    /// "system" proxy.
+    pub fn proxy_from_env(self) -> ClientBuilder {
+        self.with_inner(move |inner| inner.proxy_from_env())
+    }
+
     /// Clear all `Proxies`, so `Client` will use no proxy anymore.
     ///
     /// # Note
@@ -384,7 +539,8 @@ impl ClientBuilder {
     ///
     /// Default is 30 seconds.
     ///
-    /// Pass `None` to disable timeout.
+    /// Pass `None` to disable timeout. A timeout too large to be represented,
+    /// such as `Duration::MAX`, saturates and behaves the same as `None`.
     pub fn timeout<T>(mut self, timeout: T) -> ClientBuilder
     where
         T: Into<Option<Duration>>,
@@ -1115,6 +1271,23 @@ impl ClientBuilder {
         self.with_inner(|inner| inner.no_hickory_dns())
     }
 
+    /// Set whether requests built with
+    /// [`RequestBuilder::json`](crate::blocking::RequestBuilder::json) are
+    /// sent with an `Accept: application/json` header, when they don't have
+    /// an `Accept` header of their own. It takes the place of the `Accept`
+    /// among the default headers.
+    ///
+    /// Default is `true`.
+    ///
+    /// # Optional
+    ///
+    /// This requires the optional `json` feature enabled.
+    #[cfg(feature = "json")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "json")))]
+    pub fn json_accept(self, enabled: bool) -> ClientBuilder {
+        self.with_inner(|inner| inner.json_accept(enabled))
+    }
+
     /// Restrict the Client to be used with HTTPS only requests.
     ///
     /// Defaults to false.
@@ -1122,6 +1295,47 @@ impl ClientBuilder {
         self.with_inner(|inner| inner.https_only(enabled))
     }
 
+    /// Allow requests to these hosts to use plain `http`, even when
+    /// [`https_only`](ClientBuilder::https_only) is enabled.
+    ///
+    /// This is meant for development against a local server, such as
+    /// `localhost` or `127.0.0.1`. Hosts are compared case-insensitively,
+    /// without their port. It applies to redirects as well.
+    ///
+    /// Defaults to no hosts.
+    pub fn http_allowed_hosts(self, hosts: Vec<String>) -> ClientBuilder {
+        self.with_inner(|inner| inner.http_allowed_hosts(hosts))
+    }
+
+    /// Treat the values of these headers as sensitive, like those of
+    /// `Authorization`, `Proxy-Authorization`, `Cookie` and `Set-Cookie`.
+    ///
+    /// Their values are marked with
+    /// [`HeaderValue::set_sensitive`](crate::header::HeaderValue::set_sensitive)
+    /// in the default headers, in every request the client sends, and in
+    /// every response it receives, which leaves them out of `Debug` output.
+    pub fn sensitive_headers<I>(self, names: I) -> ClientBuilder
+    where
+        I: IntoIterator<Item = header::HeaderName>,
+    {
+        self.with_inner(|inner| inner.sensitive_headers(names))
+    }
+
+    /// Set a base URL that the URLs of requests are resolved against.
+    ///
+    /// URLs passed to [`Client::request`] and its shorthands may then be
+    /// relative, such as `"/v1/users"`. They are joined to the base like
+    /// links in a web page, with [`Url::join`](crate::Url::join), so an
+    /// absolute URL replaces the base entirely, and a relative path replaces
+    /// the last segment of the base path unless it ends with a `/`.
+    ///
+    /// # Errors
+    ///
+    /// Building the `Client` fails if the base URL is invalid.
+    pub fn base_url<U: IntoUrl>(self, base_url: U) -> ClientBuilder {
+        self.with_inner(|inner| inner.base_url(base_url))
+    }
+
     /// Override DNS resolution for specific domains to a particular IP address.
     ///
     /// Set the port to `0` to use the conventional port for the given scheme (e.g. 80 for http).
@@ -1235,6 +1449,27 @@ impl Client {
         self.request(Method::GET, url)
     }
 
+    /// Convenience method to make a `GET` request to a URL expanded from a
+    /// template.
+    ///
+    /// Each `{name}` placeholder in `template` is replaced by the matching
+    /// value in `params`, percent-encoded so that characters such as `/`,
+    /// `?` or spaces can't change the structure of the URL.
+    ///
+    /// If the client has a [`base_url`](ClientBuilder::base_url), the expanded `Url` is
+    /// resolved against it, as with [`request`](Client::request).
+    ///
+    /// # Errors
+    ///
+    /// This method fails if a placeholder has no matching parameter, or the
+    /// expanded `Url` cannot be parsed.
+    pub fn get_template(&self, template: &str, params: &HashMap<&str, &str>) -> RequestBuilder {
+        let req = crate::util::expand_url_template(template, params)
+            .and_then(|url| crate::into_url::resolve(self.inner.base_url.as_ref(), url))
+            .map(|url| Request::new(Method::GET, url));
+        RequestBuilder::new(self.clone(), req)
+    }
+
     /// Convenience method to make a `POST` request to a URL.
     ///
     /// # Errors
@@ -1285,14 +1520,29 @@ impl Client {
     /// Returns a `RequestBuilder`, which will allow setting headers and
     /// request body before sending.
     ///
+    /// If the client has a [`base_url`](ClientBuilder::base_url), the `Url`
+    /// is resolved against it.
+    ///
     /// # Errors
     ///
     /// This method fails whenever supplied `Url` cannot be parsed.
     pub fn request<U: IntoUrl>(&self, method: Method, url: U) -> RequestBuilder {
-        let req = url.into_url().map(move |url| Request::new(method, url));
+        let req = crate::into_url::resolve(self.inner.base_url.as_ref(), url)
+            .map(move |url| Request::new(method, url));
         RequestBuilder::new(self.clone(), req)
     }
 
+    /// Start building a `Request` with the `Method` and an already parsed
+    /// `Url`.
+    ///
+    /// Unlike [`request()`](Client::request), this skips converting and
+    /// checking the URL, and the client's base URL isn't applied. An
+    /// unsupported URL, such as one without a host, is still rejected when
+    /// the request is sent.
+    pub fn request_url(&self, method: Method, url: Url) -> RequestBuilder {
+        RequestBuilder::new(self.clone(), Ok(Request::new(method, url)))
+    }
+
     /// Executes a `Request`.
     ///
     /// A `Request` can be built manually with `Request::new()` or obtained
@@ -1308,6 +1558,10 @@ impl Client {
     pub fn execute(&self, request: Request) -> crate::Result<Response> {
         self.inner.execute_request(request)
     }
+
+    pub(crate) fn merge_default_query(&self, url: &mut Url) {
+        crate::util::merge_default_query(url, &self.inner.default_query);
+    }
 }
 
 impl fmt::Debug for Client {
@@ -1329,6 +1583,8 @@ impl fmt::Debug for ClientBuilder {
 #[derive(Clone)]
 struct ClientHandle {
     timeout: Timeout,
+    base_url: Option<Url>,
+    default_query: Vec<(String, String)>,
     inner: Arc<InnerClientHandle>,
 }
 
@@ -1359,6 +1615,8 @@ impl Drop for InnerClientHandle {
 impl ClientHandle {
     fn new(builder: ClientBuilder) -> crate::Result<ClientHandle> {
         let timeout = builder.timeout;
+        let base_url = builder.inner.get_base_url().cloned();
+        let default_query = builder.inner.get_default_query().to_vec();
         let builder = builder.inner;
         let (tx, rx) = mpsc::unbounded_channel::<(async_impl::Request, OneshotResponse)>();
         let (spawn_tx, spawn_rx) = oneshot::channel::<crate::Result<()>>();
@@ -1427,6 +1685,8 @@ impl ClientHandle {
 
         Ok(ClientHandle {
             timeout,
+            base_url,
+            default_query,
             inner: inner_handle,
         })
     }
@@ -1435,7 +1695,11 @@ impl ClientHandle {
         let (tx, rx) = oneshot::channel();
         let (req, body) = req.into_async();
         let url = req.url().clone();
-        let timeout = req.timeout().copied().or(self.timeout.0);
+        let timeout = if req.is_timeout_disabled() {
+            None
+        } else {
+            req.timeout().copied().or(self.timeout.0)
+        };
 
         self.inner
             .tx
@@ -45,6 +45,7 @@ use std::path::Path;
 use mime_guess::{self, Mime};
 
 use super::Body;
+pub use crate::async_impl::multipart::PercentEncoding;
 use crate::async_impl::multipart::{FormParts, PartMetadata, PartProps};
 use crate::header::HeaderMap;
 
@@ -79,6 +80,23 @@ impl Form {
         self.inner.boundary()
     }
 
+    /// Use a fixed boundary instead of a randomly generated one.
+    ///
+    /// This is mostly useful to produce deterministic bodies, such as in
+    /// snapshot tests. The boundary **must not** appear in the content of
+    /// any of the parts, or the receiver will not be able to parse the form.
+    ///
+    /// # Errors
+    ///
+    /// Errors if the boundary is empty, longer than 70 characters, or
+    /// contains characters other than ASCII letters, digits, and any of
+    /// `'+_-.`.
+    pub fn with_boundary(self, boundary: &str) -> crate::Result<Form> {
+        Ok(Form {
+            inner: self.inner.with_boundary(boundary)?,
+        })
+    }
+
     /// Add a data field with supplied name and value.
     ///
     /// # Examples
@@ -131,17 +149,26 @@ impl Form {
 
     /// Configure this `Form` to percent-encode using the `path-segment` rules.
     pub fn percent_encode_path_segment(self) -> Form {
-        self.with_inner(|inner| inner.percent_encode_path_segment())
+        self.percent_encoding(PercentEncoding::PathSegment)
     }
 
     /// Configure this `Form` to percent-encode using the `attr-char` rules.
     pub fn percent_encode_attr_chars(self) -> Form {
-        self.with_inner(|inner| inner.percent_encode_attr_chars())
+        self.percent_encoding(PercentEncoding::AttrChar)
     }
 
     /// Configure this `Form` to skip percent-encoding
     pub fn percent_encode_noop(self) -> Form {
-        self.with_inner(|inner| inner.percent_encode_noop())
+        self.percent_encoding(PercentEncoding::NoOp)
+    }
+
+    /// Configure how this `Form` percent-encodes the names of its fields.
+    ///
+    /// A name that needs encoding is sent in the `name*=utf-8''` form of the
+    /// `Content-Disposition` header. File names are never percent-encoded,
+    /// only quoted. The default is [`PercentEncoding::PathSegment`].
+    pub fn percent_encoding(self, encoding: PercentEncoding) -> Form {
+        self.with_inner(move |inner| inner.percent_encoding(encoding))
     }
 
     pub(crate) fn reader(self) -> Reader {
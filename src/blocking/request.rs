@@ -1,6 +1,6 @@
 use std::convert::TryFrom;
 use std::fmt;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use http::{request::Parts, Request as HttpRequest, Version};
 use serde::Serialize;
@@ -12,6 +12,7 @@ use super::body::{self, Body};
 #[cfg(feature = "multipart")]
 use super::multipart;
 use super::Client;
+use crate::config::{Deadline, RequestConfig};
 use crate::header::{HeaderMap, HeaderName, HeaderValue, CONTENT_TYPE};
 use crate::{async_impl, Method, Url};
 
@@ -101,13 +102,21 @@ impl Request {
         &mut self.body
     }
 
-    /// Get the timeout.
+    /// Get the timeout of this request.
+    ///
+    /// This is the timeout set with [`RequestBuilder::timeout`], or `None` if
+    /// the client's applies.
     #[inline]
     pub fn timeout(&self) -> Option<&Duration> {
         self.inner.timeout()
     }
 
     /// Get a mutable reference to the timeout.
+    ///
+    /// The value left here is the one used when the request is executed, so
+    /// middleware can adjust it after the request is built. Setting `None`
+    /// falls back to the client's timeout, unless the request disabled it
+    /// with `RequestBuilder::no_timeout`.
     #[inline]
     pub fn timeout_mut(&mut self) -> &mut Option<Duration> {
         self.inner.timeout_mut()
@@ -199,6 +208,54 @@ impl RequestBuilder {
         self.header_sensitive(key, value, false)
     }
 
+    /// Remove a `Header` from this Request.
+    ///
+    /// This also keeps the client's default headers from setting it, like a
+    /// default `User-Agent`. Setting the header again afterwards still adds
+    /// it.
+    ///
+    /// ```rust
+    /// # fn run() -> Result<(), Box<dyn std::error::Error>> {
+    /// let res = reqwest::blocking::Client::builder()
+    ///     .user_agent("my-app/1.0")
+    ///     .build()?
+    ///     .get("https://www.rust-lang.org")
+    ///     .remove_header(reqwest::header::USER_AGENT)
+    ///     .send()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn remove_header<K>(mut self, key: K) -> RequestBuilder
+    where
+        HeaderName: TryFrom<K>,
+        <HeaderName as TryFrom<K>>::Error: Into<http::Error>,
+    {
+        let mut error = None;
+        if let Ok(ref mut req) = self.request {
+            match <HeaderName as TryFrom<K>>::try_from(key) {
+                Ok(key) => req.inner.remove_header(key),
+                Err(e) => error = Some(crate::error::builder(e.into())),
+            }
+        }
+        if let Some(err) = error {
+            self.request = Err(err);
+        }
+        self
+    }
+
+    /// Don't add the client's default headers to this request.
+    ///
+    /// The request is sent with only the headers set on it, apart from
+    /// those the connection itself needs, like `Host`, and proxy
+    /// authorization. This includes the `User-Agent`, `Accept` and
+    /// `Accept-Encoding` headers the client adds by default.
+    pub fn no_default_headers(mut self) -> RequestBuilder {
+        if let Ok(ref mut req) = self.request {
+            req.inner.set_no_default_headers();
+        }
+        self
+    }
+
     /// Add a `Header` to this Request with ability to define if header_value is sensitive.
     fn header_sensitive<K, V>(mut self, key: K, value: V, sensitive: bool) -> RequestBuilder
     where
@@ -302,6 +359,102 @@ impl RequestBuilder {
         self.header_sensitive(crate::header::AUTHORIZATION, &*header_value, true)
     }
 
+    /// Override the `Host` header of the request.
+    ///
+    /// The request is still sent to the host of its URL, and with HTTPS, that
+    /// host is also the one used for SNI and certificate verification. Only
+    /// the header changes, which is useful to reach a virtual host by
+    /// address. To instead connect to another address while keeping the
+    /// URL's host everywhere, see
+    /// [`ClientBuilder::resolve`](crate::blocking::ClientBuilder::resolve).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # fn run() -> Result<(), reqwest::Error> {
+    /// let res = reqwest::blocking::Client::new()
+    ///     .get("http://127.0.0.1:8080/")
+    ///     .host_header("internal.example")
+    ///     .send()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn host_header<V>(self, host: V) -> RequestBuilder
+    where
+        HeaderValue: TryFrom<V>,
+        <HeaderValue as TryFrom<V>>::Error: Into<http::Error>,
+    {
+        self.header(crate::header::HOST, host)
+    }
+
+    /// Set the `If-Range` header, making a range request conditional.
+    ///
+    /// The validator is either an entity tag, such as `"\"xyzzy\""`, or an
+    /// HTTP-date, such as `"Wed, 21 Oct 2015 07:28:00 GMT"`, as previously
+    /// returned in the `ETag` or `Last-Modified` headers. If the resource
+    /// still matches, the server responds with just the requested range;
+    /// otherwise it sends the full, current body. Use
+    /// [`Response::is_partial`][is_partial] to tell the two apart.
+    ///
+    /// This is only meaningful together with a `Range` header.
+    ///
+    /// [is_partial]: crate::blocking::Response::is_partial
+    pub fn if_range<V>(self, validator: V) -> RequestBuilder
+    where
+        HeaderValue: TryFrom<V>,
+        <HeaderValue as TryFrom<V>>::Error: Into<http::Error>,
+    {
+        self.header(crate::header::IF_RANGE, validator)
+    }
+
+    /// Set the `If-None-Match` header, making the request conditional on the
+    /// resource having changed.
+    ///
+    /// The entity tag is one previously returned in the `ETag` header, such
+    /// as `"\"xyzzy\""`, quotes included. If the resource still has it, the
+    /// server answers with `304 Not Modified` and no body, which
+    /// [`Response::is_not_modified`][is_not_modified] checks for.
+    ///
+    /// [is_not_modified]: crate::blocking::Response::is_not_modified
+    pub fn if_none_match<V>(self, etag: V) -> RequestBuilder
+    where
+        HeaderValue: TryFrom<V>,
+        <HeaderValue as TryFrom<V>>::Error: Into<http::Error>,
+    {
+        self.header(crate::header::IF_NONE_MATCH, etag)
+    }
+
+    /// Set the `If-Modified-Since` header, making the request conditional on
+    /// the resource having changed since `date`.
+    ///
+    /// The date is a `SystemTime`, or a string in the HTTP date format as
+    /// previously returned in the `Last-Modified` header. If the resource
+    /// hasn't changed, the server answers with `304 Not Modified` and no
+    /// body, which [`Response::is_not_modified`][is_not_modified] checks for.
+    ///
+    /// # Errors
+    ///
+    /// Sending the request fails with a builder error if `date` isn't a valid
+    /// HTTP date.
+    ///
+    /// [is_not_modified]: crate::blocking::Response::is_not_modified
+    pub fn if_modified_since<T: crate::IntoHttpDate>(mut self, date: T) -> RequestBuilder {
+        let mut error = None;
+        if let Ok(ref mut req) = self.request {
+            match date.into_header_value() {
+                Ok(value) => {
+                    req.headers_mut()
+                        .insert(crate::header::IF_MODIFIED_SINCE, value);
+                }
+                Err(e) => error = Some(e),
+            }
+        }
+        if let Some(err) = error {
+            self.request = Err(err);
+        }
+        self
+    }
+
     /// Set the request body.
     ///
     /// # Examples
@@ -352,6 +505,74 @@ impl RequestBuilder {
         self
     }
 
+    /// Don't send cookies from the client's cookie store with this request,
+    /// nor store the cookies its response sets, including across redirects.
+    ///
+    /// This is useful when a client with a cookie store also talks to
+    /// third-party endpoints.
+    ///
+    /// # Optional
+    ///
+    /// This requires the optional `cookies` feature to be enabled.
+    #[cfg(feature = "cookies")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "cookies")))]
+    pub fn no_cookies(mut self) -> RequestBuilder {
+        if let Ok(ref mut req) = self.request {
+            req.inner.extensions_mut().insert(crate::cookie::NoCookies);
+        }
+        self
+    }
+
+    /// Set whether this request uses the client's cookie store, like the
+    /// Fetch standard's `credentials` request option.
+    ///
+    /// See [`Credentials`](crate::cookie::Credentials) for the modes.
+    ///
+    /// # Optional
+    ///
+    /// This requires the optional `cookies` feature to be enabled.
+    #[cfg(feature = "cookies")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "cookies")))]
+    pub fn fetch_credentials_mode(
+        mut self,
+        credentials: crate::cookie::Credentials,
+    ) -> RequestBuilder {
+        if let Ok(ref mut req) = self.request {
+            crate::cookie::set_credentials(req.inner.extensions_mut(), credentials);
+        }
+        self
+    }
+
+    /// Set how this request handles redirects, overriding the client's
+    /// redirect policy, like the Fetch standard's `redirect` request option.
+    ///
+    /// See [`RedirectMode`](crate::redirect::RedirectMode) for the modes.
+    pub fn fetch_redirect_mode(mut self, mode: crate::redirect::RedirectMode) -> RequestBuilder {
+        if let Ok(ref mut req) = self.request {
+            req.inner.extensions_mut().insert(mode);
+        }
+        self
+    }
+
+    /// Compress the body of this request with `encoding`, and set the
+    /// `Content-Encoding` header.
+    ///
+    /// The body is compressed when the request is sent, so this can be
+    /// called before or after setting it. Bodies created from a reader can't
+    /// be compressed; sending such a request returns an error.
+    ///
+    /// # Optional
+    ///
+    /// This requires the optional `gzip` or `brotli` feature to be enabled.
+    #[cfg(any(feature = "gzip", feature = "brotli"))]
+    #[cfg_attr(docsrs, doc(cfg(any(feature = "gzip", feature = "brotli"))))]
+    pub fn compress(mut self, encoding: crate::Encoding) -> RequestBuilder {
+        if let Ok(ref mut req) = self.request {
+            req.inner.extensions_mut().insert(encoding);
+        }
+        self
+    }
+
     /// Enables a request timeout.
     ///
     /// The timeout is applied from when the request starts connecting until the
@@ -359,7 +580,30 @@ impl RequestBuilder {
     /// the timeout configured using `ClientBuilder::timeout()`.
     pub fn timeout(mut self, timeout: Duration) -> RequestBuilder {
         if let Ok(ref mut req) = self.request {
-            *req.timeout_mut() = Some(timeout);
+            req.inner.set_timeout(Some(timeout));
+        }
+        self
+    }
+
+    /// Disables the timeout for this request, including the one configured
+    /// using `ClientBuilder::timeout()`.
+    pub fn no_timeout(mut self) -> RequestBuilder {
+        if let Ok(ref mut req) = self.request {
+            req.inner.set_timeout(None);
+        }
+        self
+    }
+
+    /// Sets an absolute deadline for this request.
+    ///
+    /// Like [`timeout`](Self::timeout), it covers connecting, every redirect,
+    /// and reading the response body, but as an `Instant` rather than from
+    /// when the request starts. That lets several requests, such as the
+    /// attempts of a retry loop, share a single deadline. If a timeout
+    /// applies too, the earlier of the two wins.
+    pub fn deadline(mut self, deadline: Instant) -> RequestBuilder {
+        if let Ok(ref mut req) = self.request {
+            *RequestConfig::<Deadline>::get_mut(req.inner.extensions_mut()) = Some(deadline);
         }
         self
     }
@@ -416,6 +660,35 @@ impl RequestBuilder {
         self
     }
 
+    /// Append key-value pairs to the query string of the URL.
+    ///
+    /// Like [`query`](Self::query), but taking the pairs directly, for when
+    /// building a serializable value is overkill. Keys and values are
+    /// percent-encoded, existing parameters are kept, and a key can appear
+    /// more than once.
+    ///
+    /// ```rust
+    /// # fn run() {
+    /// let client = reqwest::blocking::Client::new();
+    /// let req = client
+    ///     .get("http://httpbin.org/get?page=2")
+    ///     .query_pairs([("tag", "rust"), ("tag", "http & co")]);
+    /// # }
+    /// ```
+    pub fn query_pairs<I, K, V>(self, pairs: I) -> RequestBuilder
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: AsRef<str>,
+        V: AsRef<str>,
+    {
+        let pairs = pairs.into_iter().collect::<Vec<_>>();
+        let pairs = pairs
+            .iter()
+            .map(|(key, value)| (key.as_ref(), value.as_ref()))
+            .collect::<Vec<_>>();
+        self.query(&pairs)
+    }
+
     /// Set HTTP version
     pub fn version(mut self, version: Version) -> RequestBuilder {
         if let Ok(ref mut req) = self.request {
@@ -474,7 +747,9 @@ impl RequestBuilder {
     /// Send a JSON body.
     ///
     /// Sets the body to the JSON serialization of the passed value, and
-    /// also sets the `Content-Type: application/json` header.
+    /// also sets the `Content-Type: application/json` header. Unless the
+    /// request has an `Accept` header of its own when it's sent, it also gets
+    /// `Accept: application/json`, see `ClientBuilder::json_accept`.
     ///
     /// # Optional
     ///
@@ -513,6 +788,7 @@ impl RequestBuilder {
                         req.headers_mut()
                             .insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
                     }
+                    req.inner.set_json_accept();
                     *req.body_mut() = Some(body.into());
                 }
                 Err(err) => error = Some(crate::error::builder(err)),
@@ -561,8 +837,11 @@ impl RequestBuilder {
 
     /// Build a `Request`, which can be inspected, modified and executed with
     /// `Client::execute()`.
+    ///
+    /// The client's default query parameters are merged in, so the request's
+    /// `url()` is the URL that will be requested.
     pub fn build(self) -> crate::Result<Request> {
-        self.request
+        self.build_split().1
     }
 
     /// Build a `Request`, which can be inspected, modified and executed with
@@ -571,7 +850,12 @@ impl RequestBuilder {
     /// This is similar to [`RequestBuilder::build()`], but also returns the
     /// embedded `Client`.
     pub fn build_split(self) -> (Client, crate::Result<Request>) {
-        (self.client, self.request)
+        let client = self.client;
+        let request = self.request.map(|mut req| {
+            client.merge_default_query(req.url_mut());
+            req
+        });
+        (client, request)
     }
 
     /// Constructs the Request and sends it the target URL, returning a Response.
@@ -584,6 +868,23 @@ impl RequestBuilder {
         self.client.execute(self.request?)
     }
 
+    /// Sends the request, and reads and discards the response body,
+    /// returning just the status.
+    ///
+    /// This is for requests whose response body doesn't matter, such as
+    /// webhooks or pings. Reading the body to the end lets the connection be
+    /// reused, which dropping an unread `Response` doesn't.
+    ///
+    /// # Errors
+    ///
+    /// This method fails like [`send`](RequestBuilder::send), or if the
+    /// response body couldn't be read.
+    pub fn send_and_drain(self) -> crate::Result<crate::StatusCode> {
+        let mut res = self.send()?;
+        std::io::copy(&mut res, &mut std::io::sink()).map_err(crate::error::decode_io)?;
+        Ok(res.status())
+    }
+
     /// Attempts to clone the `RequestBuilder`.
     ///
     /// None is returned if a body is which can not be cloned. This can be because the body is a
@@ -676,7 +977,7 @@ fn fmt_request_fields<'a, 'b>(
 ) -> &'a mut fmt::DebugStruct<'a, 'b> {
     f.field("method", req.method())
         .field("url", req.url())
-        .field("headers", req.headers())
+        .field("headers", &crate::util::RedactedHeaders(req.headers()))
 }
 
 #[cfg(test)]
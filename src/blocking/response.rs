@@ -9,7 +9,7 @@ use bytes::Bytes;
 use http;
 use hyper::header::HeaderMap;
 #[cfg(feature = "json")]
-use serde::de::DeserializeOwned;
+use serde::de::{DeserializeOwned, DeserializeSeed};
 
 use super::client::KeepCoreThreadAlive;
 use super::wait;
@@ -93,6 +93,58 @@ impl Response {
         self.inner.status()
     }
 
+    /// Get the reason phrase of the status, such as `"Not Found"`.
+    ///
+    /// This is the phrase the server sent if it differs from the canonical
+    /// one, which is only possible over HTTP/1. Otherwise, it's the canonical
+    /// reason for the status code, if there is one.
+    pub fn status_reason(&self) -> Option<&str> {
+        self.inner.status_reason()
+    }
+
+    /// Get the URLs this response was redirected from, in order.
+    ///
+    /// The final URL, [`url()`](Response::url), isn't included, so this is
+    /// empty if the request wasn't redirected.
+    pub fn redirect_chain(&self) -> &[Url] {
+        self.inner.redirect_chain()
+    }
+
+    /// Get the time it took from sending the request until this response's
+    /// headers were received, including any redirects and retries.
+    pub fn elapsed(&self) -> Duration {
+        self.inner.elapsed()
+    }
+
+    /// Get whether the server supports range requests for this resource,
+    /// from the `Accept-Ranges` header.
+    pub fn accept_ranges(&self) -> crate::AcceptRanges {
+        self.inner.accept_ranges()
+    }
+
+    /// Returns whether the connection this response arrived on was reused
+    /// from the connection pool, rather than newly established.
+    ///
+    /// Returns `None` when this isn't known.
+    pub fn connection_reused(&self) -> Option<bool> {
+        self.inner.connection_reused()
+    }
+
+    /// Returns true if the server sent only part of the content, with
+    /// `206 Partial Content`, in answer to a range request.
+    #[inline]
+    pub fn is_partial(&self) -> bool {
+        self.inner.is_partial()
+    }
+
+    /// Returns true if the server answered a conditional request with
+    /// `304 Not Modified`, meaning a previously received copy of the resource
+    /// is still current.
+    #[inline]
+    pub fn is_not_modified(&self) -> bool {
+        self.inner.is_not_modified()
+    }
+
     /// Get the `Headers` of this `Response`.
     ///
     /// # Example
@@ -128,6 +180,33 @@ impl Response {
         self.inner.headers_mut()
     }
 
+    /// Get the parsed `Content-Type` of this `Response`, if present.
+    ///
+    /// Returns `None` if the header is missing, or if its value cannot be
+    /// parsed as a media type.
+    ///
+    /// # Optional
+    ///
+    /// This requires the optional `charset` feature enabled.
+    #[cfg(feature = "charset")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "charset")))]
+    pub fn content_type(&self) -> Option<mime::Mime> {
+        self.inner.content_type()
+    }
+
+    /// Get how long the server asked to wait before retrying, from the
+    /// `Retry-After` header.
+    ///
+    /// Both forms of the header are supported: a number of seconds, or an
+    /// HTTP-date. A date is measured relative to the response's `Date` header
+    /// if present, or the current time otherwise, and a date in the past
+    /// yields a zero duration.
+    ///
+    /// Returns `None` if the header is missing or cannot be parsed.
+    pub fn retry_after(&self) -> Option<Duration> {
+        self.inner.retry_after()
+    }
+
     /// Retrieve the cookies contained in the response.
     ///
     /// Note that invalid 'Set-Cookie' headers will be ignored.
@@ -163,6 +242,17 @@ impl Response {
         self.inner.url()
     }
 
+    /// Get a mutable reference to the final `Url` of this `Response`.
+    ///
+    /// This lets middleware normalize or rewrite the URL before the response
+    /// reaches the caller. Errors created from this response afterwards,
+    /// such as by [`error_for_status`](Response::error_for_status), carry
+    /// the new URL.
+    #[inline]
+    pub fn url_mut(&mut self) -> &mut Url {
+        self.inner.url_mut()
+    }
+
     /// Get the remote address used to get this `Response`.
     ///
     /// # Example
@@ -178,7 +268,33 @@ impl Response {
         self.inner.remote_addr()
     }
 
+    /// Get the TLS information of the connection this `Response` arrived on.
+    ///
+    /// Returns `None` unless the request was sent over TLS by a `Client`
+    /// built with [`ClientBuilder::tls_info`](crate::blocking::ClientBuilder::tls_info)
+    /// enabled.
+    ///
+    /// # Optional
+    ///
+    /// This requires the optional `default-tls`, `native-tls`, or `rustls-tls(-...)`
+    /// feature to be enabled.
+    #[cfg(feature = "__tls")]
+    #[cfg_attr(
+        docsrs,
+        doc(cfg(any(
+            feature = "default-tls",
+            feature = "native-tls",
+            feature = "rustls-tls"
+        )))
+    )]
+    pub fn tls_info(&self) -> Option<&crate::tls::TlsInfo> {
+        self.inner.tls_info()
+    }
+
     /// Returns a reference to the associated extensions.
+    ///
+    /// Besides what the connection and TLS layers record, middleware can
+    /// stash typed data here for later layers.
     pub fn extensions(&self) -> &http::Extensions {
         self.inner.extensions()
     }
@@ -250,6 +366,81 @@ impl Response {
         })
     }
 
+    /// Try to deserialize the response body as JSON, falling back to its
+    /// text.
+    ///
+    /// The body is read once. If it isn't valid JSON for `T`, it's decoded
+    /// like [`text`](Response::text) and returned as `Err`, so the caller
+    /// can see what the server actually sent, such as an HTML error page.
+    ///
+    /// # Optional
+    ///
+    /// This requires the optional `json` feature enabled.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate reqwest;
+    /// # extern crate serde;
+    /// #
+    /// # use reqwest::Error;
+    /// # use serde::Deserialize;
+    /// #
+    /// #[derive(Deserialize)]
+    /// struct Ip {
+    ///     origin: String,
+    /// }
+    ///
+    /// # fn run() -> Result<(), Error> {
+    /// let res = reqwest::blocking::get("http://httpbin.org/ip")?;
+    /// match res.json_or_text::<Ip>()? {
+    ///     Ok(ip) => println!("ip: {}", ip.origin),
+    ///     Err(text) => println!("unexpected response: {text}"),
+    /// }
+    /// # Ok(())
+    /// # }
+    /// #
+    /// # fn main() { }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This method fails only if the body can't be read.
+    #[cfg(feature = "json")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "json")))]
+    pub fn json_or_text<T: DeserializeOwned>(self) -> crate::Result<Result<T, String>> {
+        wait::timeout(self.inner.json_or_text(), self.timeout).map_err(|e| match e {
+            wait::Waited::TimedOut(e) => crate::error::decode(e),
+            wait::Waited::Inner(e) => e,
+        })
+    }
+
+    /// Try and deserialize the response body as JSON, using a stateful
+    /// [`DeserializeSeed`].
+    ///
+    /// This is useful when deserialization needs access to external state,
+    /// which a plain [`DeserializeOwned`] type cannot carry.
+    ///
+    /// # Optional
+    ///
+    /// This requires the optional `json` feature enabled.
+    ///
+    /// # Errors
+    ///
+    /// This method fails whenever the response body is not in JSON format,
+    /// or the `seed` fails to deserialize it.
+    #[cfg(feature = "json")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "json")))]
+    pub fn json_with_seed<S, T>(self, seed: S) -> crate::Result<T>
+    where
+        S: for<'de> DeserializeSeed<'de, Value = T>,
+    {
+        wait::timeout(self.inner.json_with_seed(seed), self.timeout).map_err(|e| match e {
+            wait::Waited::TimedOut(e) => crate::error::decode(e),
+            wait::Waited::Inner(e) => e,
+        })
+    }
+
     /// Get the full response body as `Bytes`.
     ///
     /// # Example
@@ -269,11 +460,44 @@ impl Response {
         })
     }
 
+    /// Get the full response body as `Bytes`, failing if it's larger than
+    /// `max` bytes.
+    ///
+    /// Reading stops as soon as the limit is exceeded, or right away if the
+    /// `Content-Length` is over it.
+    ///
+    /// # Errors
+    ///
+    /// This method fails with a body error if the body is larger than `max`,
+    /// or if it can't be read, like [`bytes()`](Response::bytes).
+    pub fn bytes_limited(self, max: usize) -> crate::Result<Bytes> {
+        wait::timeout(self.inner.bytes_limited(max), self.timeout).map_err(|e| match e {
+            wait::Waited::TimedOut(e) => crate::error::decode(e),
+            wait::Waited::Inner(e) => e,
+        })
+    }
+
+    /// Get the full response body as `Bytes`, reporting progress along the
+    /// way.
+    ///
+    /// `progress` is called with the total number of bytes received so far
+    /// each time a chunk of the body arrives.
+    pub fn bytes_with_progress<F>(self, progress: F) -> crate::Result<Bytes>
+    where
+        F: FnMut(u64),
+    {
+        wait::timeout(self.inner.bytes_with_progress(progress), self.timeout).map_err(|e| match e {
+            wait::Waited::TimedOut(e) => crate::error::decode(e),
+            wait::Waited::Inner(e) => e,
+        })
+    }
+
     /// Get the response text.
     ///
     /// This method decodes the response body with BOM sniffing
     /// and with malformed sequences replaced with the [`char::REPLACEMENT_CHARACTER`].
     /// Encoding is determined from the `charset` parameter of `Content-Type` header,
+    /// whatever the media type, such as `application/json; charset=iso-8859-1`,
     /// and defaults to `utf-8` if not presented.
     ///
     /// # Note
@@ -297,6 +521,41 @@ impl Response {
         })
     }
 
+    /// Get the response text, replacing malformed sequences.
+    ///
+    /// This is the same as [`text`](Response::text), named to contrast with
+    /// [`text_strict`](Response::text_strict).
+    pub fn text_lossy(self) -> crate::Result<String> {
+        self.text()
+    }
+
+    /// Get the response text, failing if it isn't valid UTF-8.
+    ///
+    /// Unlike [`text`](Response::text), malformed sequences are an error
+    /// rather than replaced, so corrupted content can be detected. The body
+    /// is always decoded as UTF-8, whatever the `charset` parameter of the
+    /// `Content-Type` header. A UTF-8 BOM is stripped.
+    ///
+    /// # Errors
+    ///
+    /// This method fails if the body isn't valid UTF-8, or couldn't be read.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate reqwest;
+    /// # fn run() -> Result<(), Box<dyn std::error::Error>> {
+    /// let content = reqwest::blocking::get("http://httpbin.org/range/26")?.text_strict()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn text_strict(self) -> crate::Result<String> {
+        wait::timeout(self.inner.text_strict(), self.timeout).map_err(|e| match e {
+            wait::Waited::TimedOut(e) => crate::error::decode(e),
+            wait::Waited::Inner(e) => e,
+        })
+    }
+
     /// Get the response text given a specific encoding.
     ///
     /// This method decodes the response body with BOM sniffing
@@ -305,6 +564,10 @@ impl Response {
     /// `charset` parameter of `Content-Type` header is still prioritized. For more information
     /// about the possible encoding name, please go to [`encoding_rs`] docs.
     ///
+    /// If the body starts with a UTF-8 or UTF-16 byte order mark, the BOM is
+    /// authoritative and overrides both the `charset` parameter and the default
+    /// encoding, matching how browsers behave.
+    ///
     /// [`encoding_rs`]: https://docs.rs/encoding_rs/0.8/encoding_rs/#relationship-with-windows-code-pages
     ///
     /// # Optional
@@ -332,6 +595,33 @@ impl Response {
         })
     }
 
+    /// Get the full response text, falling back to other encodings if the
+    /// declared one doesn't fit.
+    ///
+    /// The body is first decoded with the `charset` parameter of the
+    /// `Content-Type` header, or UTF-8 if there is none. If that produces
+    /// malformed sequences, each encoding in `fallbacks` is tried in order,
+    /// and the first one decoding cleanly is used. Unknown encoding labels
+    /// are skipped.
+    ///
+    /// If none of them decodes cleanly, the result of the declared encoding
+    /// is returned, with malformed sequences replaced with the
+    /// [`char::REPLACEMENT_CHARACTER`].
+    ///
+    /// # Optional
+    ///
+    /// This requires the optional `charset` feature enabled.
+    #[cfg(feature = "charset")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "charset")))]
+    pub fn text_with_fallbacks(self, fallbacks: &[&str]) -> crate::Result<String> {
+        wait::timeout(self.inner.text_with_fallbacks(fallbacks), self.timeout).map_err(
+            |e| match e {
+                wait::Waited::TimedOut(e) => crate::error::decode(e),
+                wait::Waited::Inner(e) => e,
+            },
+        )
+    }
+
     /// Copy the response body into a writer.
     ///
     /// This function internally uses [`std::io::copy`] and hence will continuously read data from
@@ -410,6 +700,25 @@ impl Response {
         self.inner.error_for_status_ref().and_then(|_| Ok(self))
     }
 
+    /// Turn a response into an error if the server returned an error,
+    /// keeping the body of the error response.
+    ///
+    /// This is like [`Response::error_for_status`], but on a 4xx or 5xx
+    /// status the body is read, and is available from
+    /// [`Error::response_body`](crate::Error::response_body). The start of
+    /// the body is also shown when the error is displayed.
+    pub fn error_for_status_with_body(self) -> crate::Result<Self> {
+        let err = match self.inner.error_for_status_ref() {
+            Ok(_) => return Ok(self),
+            Err(err) => err,
+        };
+        // The status is the error that matters, even if the body fails.
+        match self.bytes() {
+            Ok(body) => Err(err.with_response_body(body)),
+            Err(_) => Err(err),
+        }
+    }
+
     // private
 
     fn body_mut(&mut self) -> Pin<&mut dyn futures_util::io::AsyncRead> {
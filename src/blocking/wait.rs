@@ -12,9 +12,11 @@ where
 {
     enter();
 
-    let deadline = timeout.map(|d| {
+    // A timeout too large to be represented as an `Instant` is treated the
+    // same as no timeout at all, instead of overflowing.
+    let deadline = timeout.and_then(|d| {
         log::trace!("wait at most {d:?}");
-        Instant::now() + d
+        Instant::now().checked_add(d)
     });
 
     let thread = ThreadWaker(thread::current());
@@ -0,0 +1,448 @@
+//! HTTP response caching
+//!
+//! A cache installed with [`ClientBuilder::cache`](crate::ClientBuilder::cache)
+//! stores the responses to `GET` requests, and answers later requests for the
+//! same URL from the store while they're fresh, without a network call.
+//! [`MemoryCache`] keeps the responses in memory; other stores can implement
+//! [`HttpCache`].
+//!
+//! A response is fresh for the `max-age` of its `Cache-Control` header. Once
+//! it's stale, it's revalidated with a conditional request built from its
+//! `ETag` and `Last-Modified` headers, and a `304 Not Modified` answer makes
+//! it fresh again.
+//!
+//! Only `200 OK` responses are stored, and not when the request or the
+//! response says `no-store`, the response says `private`, or the response has
+//! a `Vary` header. A response to a request carrying `Authorization` or
+//! `Cookie` is only stored when it's explicitly `public`, so the cache can't
+//! hand one user's response to another. The cache is private to the client,
+//! so `s-maxage` is ignored. Responses with a body over 1 MiB aren't stored
+//! either. Requests with a `Range` or conditional headers of their own bypass
+//! the cache.
+//!
+//! Interceptors see a response answered from the cache like any other. As
+//! nothing is sent, though, it has no `tracing` request span, its
+//! [`elapsed`](crate::Response::elapsed) is zero, and since its body is
+//! already in memory it's never warned about by
+//! [`warn_on_unread_body`](crate::ClientBuilder::warn_on_unread_body).
+//!
+//! # Example
+//!
+//! ```
+//! use std::sync::Arc;
+//! use reqwest::cache::MemoryCache;
+//!
+//! # fn run() -> Result<(), reqwest::Error> {
+//! let client = reqwest::Client::builder()
+//!     .cache(Arc::new(MemoryCache::new()))
+//!     .build()?;
+//! # Ok(())
+//! # }
+//! ```
+
+use std::collections::HashMap;
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
+
+use bytes::{Bytes, BytesMut};
+use http::header::{
+    HeaderMap, AUTHORIZATION, CACHE_CONTROL, COOKIE, ETAG, IF_MATCH, IF_MODIFIED_SINCE,
+    IF_NONE_MATCH, IF_RANGE, IF_UNMODIFIED_SINCE, LAST_MODIFIED, RANGE, VARY,
+};
+use http::{Method, StatusCode, Version};
+use http_body::{Body as HttpBody, Frame};
+use http_body_util::BodyExt;
+use url::Url;
+
+use crate::{Body, Response};
+
+/// The largest body `store` reads into the cache.
+const MAX_BODY_SIZE: usize = 1024 * 1024;
+
+/// A store for cached responses, keyed by URL.
+pub trait HttpCache: Send + Sync {
+    /// Returns the response stored for `url`, if any.
+    fn get(&self, url: &Url) -> Option<CachedResponse>;
+
+    /// Stores `response` for `url`, replacing any previous one.
+    fn put(&self, url: &Url, response: CachedResponse);
+}
+
+/// A response held by an [`HttpCache`].
+#[derive(Clone)]
+pub struct CachedResponse {
+    version: Version,
+    headers: HeaderMap,
+    body: Bytes,
+    stored_at: Instant,
+}
+
+impl CachedResponse {
+    /// The headers of the response.
+    pub fn headers(&self) -> &HeaderMap {
+        &self.headers
+    }
+
+    /// The full body of the response.
+    pub fn body(&self) -> &Bytes {
+        &self.body
+    }
+
+    fn is_fresh(&self) -> bool {
+        let cache_control = CacheControl::parse(&self.headers);
+        match cache_control.max_age {
+            Some(max_age) if !cache_control.no_cache => {
+                self.stored_at.elapsed() < Duration::from_secs(max_age)
+            }
+            _ => false,
+        }
+    }
+
+    fn add_validators(&self, headers: &mut HeaderMap) {
+        if let Some(etag) = self.headers.get(ETAG) {
+            headers.insert(IF_NONE_MATCH, etag.clone());
+        }
+        if let Some(last_modified) = self.headers.get(LAST_MODIFIED) {
+            headers.insert(IF_MODIFIED_SINCE, last_modified.clone());
+        }
+    }
+
+    /// Updates a stale response with the headers of a `304 Not Modified`.
+    fn revalidate(&mut self, headers: &HeaderMap) {
+        for key in headers.keys() {
+            self.headers.remove(key);
+            for value in headers.get_all(key) {
+                self.headers.append(key, value.clone());
+            }
+        }
+        self.stored_at = Instant::now();
+    }
+
+    fn to_response(&self, url: Url) -> Response {
+        let mut res = http::Response::new(self.body.clone());
+        *res.version_mut() = self.version;
+        *res.headers_mut() = self.headers.clone();
+        res.extensions_mut()
+            .insert(crate::response::ResponseUrl(url));
+        Response::from(res)
+    }
+}
+
+impl fmt::Debug for CachedResponse {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("CachedResponse")
+            .field("headers", &self.headers)
+            .field("body_len", &self.body.len())
+            .finish()
+    }
+}
+
+/// An [`HttpCache`] keeping responses in memory.
+///
+/// It holds up to a fixed number of entries, dropping the least recently used
+/// one to make room for a new one.
+pub struct MemoryCache {
+    capacity: usize,
+    entries: Mutex<Entries>,
+}
+
+#[derive(Default)]
+struct Entries {
+    // each response, with the tick it was last used at
+    map: HashMap<Url, (CachedResponse, u64)>,
+    tick: u64,
+}
+
+impl MemoryCache {
+    /// Creates an empty `MemoryCache` holding up to 256 entries.
+    pub fn new() -> MemoryCache {
+        MemoryCache::with_capacity(256)
+    }
+
+    /// Creates an empty `MemoryCache` holding up to `capacity` entries.
+    pub fn with_capacity(capacity: usize) -> MemoryCache {
+        MemoryCache {
+            capacity,
+            entries: Mutex::new(Entries::default()),
+        }
+    }
+}
+
+impl Default for MemoryCache {
+    fn default() -> MemoryCache {
+        MemoryCache::new()
+    }
+}
+
+impl HttpCache for MemoryCache {
+    fn get(&self, url: &Url) -> Option<CachedResponse> {
+        let mut entries = self.entries.lock().unwrap();
+        entries.tick += 1;
+        let tick = entries.tick;
+        entries.map.get_mut(url).map(|(response, used)| {
+            *used = tick;
+            response.clone()
+        })
+    }
+
+    fn put(&self, url: &Url, response: CachedResponse) {
+        if self.capacity == 0 {
+            return;
+        }
+        let mut entries = self.entries.lock().unwrap();
+        if !entries.map.contains_key(url) && entries.map.len() >= self.capacity {
+            let oldest = entries
+                .map
+                .iter()
+                .min_by_key(|(_, (_, used))| *used)
+                .map(|(url, _)| url.clone());
+            if let Some(oldest) = oldest {
+                entries.map.remove(&oldest);
+            }
+        }
+        entries.tick += 1;
+        let tick = entries.tick;
+        entries.map.insert(url.clone(), (response, tick));
+    }
+}
+
+impl fmt::Debug for MemoryCache {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("MemoryCache")
+            .field("capacity", &self.capacity)
+            .field("len", &self.entries.lock().unwrap().map.len())
+            .finish()
+    }
+}
+
+#[derive(Default)]
+struct CacheControl {
+    max_age: Option<u64>,
+    no_cache: bool,
+    no_store: bool,
+    private: bool,
+    public: bool,
+}
+
+impl CacheControl {
+    fn parse(headers: &HeaderMap) -> CacheControl {
+        let mut cache_control = CacheControl::default();
+        let directives = headers
+            .get_all(CACHE_CONTROL)
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .flat_map(|value| value.split(','));
+        for directive in directives {
+            let (name, value) = match directive.split_once('=') {
+                Some((name, value)) => (name.trim(), Some(value.trim().trim_matches('"'))),
+                None => (directive.trim(), None),
+            };
+            if name.eq_ignore_ascii_case("max-age") {
+                cache_control.max_age = value.and_then(|value| value.parse().ok());
+            } else if name.eq_ignore_ascii_case("no-cache") {
+                cache_control.no_cache = true;
+            } else if name.eq_ignore_ascii_case("no-store") {
+                cache_control.no_store = true;
+            } else if name.eq_ignore_ascii_case("private") {
+                cache_control.private = true;
+            } else if name.eq_ignore_ascii_case("public") {
+                cache_control.public = true;
+            }
+        }
+        cache_control
+    }
+}
+
+/// Whether a request may be answered from, and stored in, the cache.
+pub(crate) fn is_cacheable_request(method: &Method, headers: &HeaderMap) -> bool {
+    const BYPASS: [http::HeaderName; 6] = [
+        RANGE,
+        IF_MATCH,
+        IF_NONE_MATCH,
+        IF_MODIFIED_SINCE,
+        IF_UNMODIFIED_SINCE,
+        IF_RANGE,
+    ];
+    method == Method::GET
+        && !BYPASS.iter().any(|name| headers.contains_key(name))
+        && !CacheControl::parse(headers).no_store
+}
+
+/// Whether a request carries credentials, making its response private unless
+/// it says otherwise.
+pub(crate) fn has_credentials(headers: &HeaderMap) -> bool {
+    headers.contains_key(AUTHORIZATION) || headers.contains_key(COOKIE)
+}
+
+fn is_storable(res: &Response, credentialed: bool) -> bool {
+    let headers = res.headers();
+    let cache_control = CacheControl::parse(headers);
+    res.status() == StatusCode::OK
+        && !cache_control.no_store
+        && !cache_control.private
+        && (!credentialed || cache_control.public)
+        && !headers.contains_key(VARY)
+        && res
+            .content_length()
+            .map_or(true, |len| len <= MAX_BODY_SIZE as u64)
+        && (cache_control.max_age.is_some()
+            || headers.contains_key(ETAG)
+            || headers.contains_key(LAST_MODIFIED))
+}
+
+/// What to do with a cacheable request, given what's stored for its URL.
+pub(crate) enum Lookup {
+    Hit(Response),
+    Miss(Option<CachedResponse>),
+}
+
+/// Looks `url` up, adding the validators of a stale response to `headers`.
+pub(crate) fn lookup(cache: &dyn HttpCache, url: &Url, headers: &mut HeaderMap) -> Lookup {
+    match cache.get(url) {
+        Some(cached) if cached.is_fresh() => Lookup::Hit(cached.to_response(url.clone())),
+        Some(cached) => {
+            cached.add_validators(headers);
+            Lookup::Miss(Some(cached))
+        }
+        None => Lookup::Miss(None),
+    }
+}
+
+/// Waits for the response to a cache miss, and stores it if possible.
+///
+/// A storable response is read in full before it's returned, unless its body
+/// turns out to be over `MAX_BODY_SIZE`, in which case it's returned unstored
+/// with the part read so far put back in front of the rest.
+pub(crate) async fn store(
+    cache: Arc<dyn HttpCache>,
+    url: Url,
+    stale: Option<CachedResponse>,
+    credentialed: bool,
+    pending: impl Future<Output = crate::Result<Response>>,
+) -> crate::Result<Response> {
+    let res = pending.await?;
+    // a redirected response belongs to another URL
+    if *res.url() != url {
+        return Ok(res);
+    }
+
+    if let Some(mut cached) = stale {
+        if res.status() == StatusCode::NOT_MODIFIED {
+            cached.revalidate(res.headers());
+            cache.put(&url, cached.clone());
+            return Ok(cached.to_response(url));
+        }
+    }
+
+    if !is_storable(&res, credentialed) {
+        return Ok(res);
+    }
+    let (parts, mut body) = http::Response::<Body>::from(res).into_parts();
+    let mut buf = BytesMut::new();
+    while let Some(frame) = body.frame().await {
+        let frame = frame.map_err(|e| e.if_no_url(|| url.clone()))?;
+        if let Ok(chunk) = frame.into_data() {
+            buf.extend_from_slice(&chunk);
+            if buf.len() > MAX_BODY_SIZE {
+                let body = Body::wrap(Prefixed {
+                    prefix: Some(buf.freeze()),
+                    rest: body,
+                });
+                return Ok(Response::from(http::Response::from_parts(parts, body)));
+            }
+        }
+    }
+    let cached = CachedResponse {
+        version: parts.version,
+        headers: parts.headers,
+        body: buf.freeze(),
+        stored_at: Instant::now(),
+    };
+    cache.put(&url, cached.clone());
+    Ok(cached.to_response(url))
+}
+
+/// A body with its first chunk read already.
+struct Prefixed {
+    prefix: Option<Bytes>,
+    rest: Body,
+}
+
+impl HttpBody for Prefixed {
+    type Data = Bytes;
+    type Error = crate::Error;
+
+    fn poll_frame(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context,
+    ) -> Poll<Option<Result<Frame<Bytes>, crate::Error>>> {
+        if let Some(prefix) = self.prefix.take() {
+            return Poll::Ready(Some(Ok(Frame::data(prefix))));
+        }
+        Pin::new(&mut self.rest).poll_frame(cx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use http::header::HeaderValue;
+
+    #[test]
+    fn parse_cache_control() {
+        let mut headers = HeaderMap::new();
+        headers.append(
+            CACHE_CONTROL,
+            HeaderValue::from_static("public, Max-Age=\"60\""),
+        );
+        headers.append(CACHE_CONTROL, HeaderValue::from_static("no-cache"));
+        let cache_control = CacheControl::parse(&headers);
+        assert_eq!(cache_control.max_age, Some(60));
+        assert!(cache_control.no_cache);
+        assert!(!cache_control.no_store);
+        assert!(cache_control.public);
+        assert!(!cache_control.private);
+    }
+
+    fn cached(body: &'static str) -> CachedResponse {
+        CachedResponse {
+            version: Version::HTTP_11,
+            headers: HeaderMap::new(),
+            body: Bytes::from_static(body.as_bytes()),
+            stored_at: Instant::now(),
+        }
+    }
+
+    #[test]
+    fn s_maxage_is_ignored() {
+        let mut response = cached("a");
+        response
+            .headers
+            .insert(CACHE_CONTROL, HeaderValue::from_static("s-maxage=60"));
+        assert!(!response.is_fresh());
+        response.headers.insert(
+            CACHE_CONTROL,
+            HeaderValue::from_static("max-age=60, s-maxage=0"),
+        );
+        assert!(response.is_fresh());
+    }
+
+    #[test]
+    fn memory_cache_evicts_least_recently_used() {
+        let cache = MemoryCache::with_capacity(2);
+        let a: Url = "http://example.com/a".parse().unwrap();
+        let b: Url = "http://example.com/b".parse().unwrap();
+        let c: Url = "http://example.com/c".parse().unwrap();
+        cache.put(&a, cached("a"));
+        cache.put(&b, cached("b"));
+        assert!(cache.get(&a).is_some());
+        cache.put(&c, cached("c"));
+        assert!(cache.get(&a).is_some());
+        assert!(cache.get(&b).is_none());
+        assert_eq!(cache.get(&c).unwrap().body(), "c");
+    }
+}
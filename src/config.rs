@@ -26,7 +26,7 @@
 
 use std::any::type_name;
 use std::fmt::Debug;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use http::Extensions;
 
@@ -108,3 +108,58 @@ pub(crate) struct TotalTimeout;
 impl RequestConfigValue for TotalTimeout {
     type Value = Duration;
 }
+
+/// An absolute deadline for a request, applying along with its timeout.
+#[derive(Clone, Copy)]
+pub(crate) struct Deadline;
+
+impl RequestConfigValue for Deadline {
+    type Value = Instant;
+}
+
+/// Set when a request's total timeout was disabled, so that the client's
+/// timeout doesn't apply either.
+#[derive(Clone, Copy)]
+pub(crate) struct TotalTimeoutDisabled;
+
+impl RequestConfigValue for TotalTimeoutDisabled {
+    type Value = ();
+}
+
+/// Headers removed from a request, which the client's default headers
+/// mustn't add back.
+#[derive(Clone, Copy)]
+pub(crate) struct RemovedHeaders;
+
+impl RequestConfigValue for RemovedHeaders {
+    type Value = Vec<http::HeaderName>;
+}
+
+/// Set when a request shouldn't get the client's default headers.
+#[derive(Clone, Copy)]
+pub(crate) struct NoDefaultHeaders;
+
+impl RequestConfigValue for NoDefaultHeaders {
+    type Value = ();
+}
+
+/// Set by `RequestBuilder::json`, asking for an `Accept: application/json`
+/// header if the request has no `Accept` header when it's sent.
+#[cfg(feature = "json")]
+#[derive(Clone, Copy)]
+pub(crate) struct JsonAccept;
+
+#[cfg(feature = "json")]
+impl RequestConfigValue for JsonAccept {
+    type Value = ();
+}
+
+/// Set on the requests of a `Client::execute_all` batch, which release their
+/// `max_concurrent` permit once the response headers arrive, since the batch
+/// can't wait on its own unread responses.
+#[derive(Clone, Copy)]
+pub(crate) struct Batched;
+
+impl RequestConfigValue for Batched {
+    type Value = ();
+}
@@ -937,7 +937,11 @@ impl TlsInfoFactory for tokio_native_tls::TlsStream<TokioIo<TokioIo<tokio::net::
             .ok()
             .flatten()
             .and_then(|c| c.to_der().ok());
-        Some(crate::tls::TlsInfo { peer_certificate })
+        Some(crate::tls::TlsInfo {
+            peer_certificate,
+            protocol_version: None,
+            cipher_suite: None,
+        })
     }
 }
 
@@ -954,7 +958,11 @@ impl TlsInfoFactory
             .ok()
             .flatten()
             .and_then(|c| c.to_der().ok());
-        Some(crate::tls::TlsInfo { peer_certificate })
+        Some(crate::tls::TlsInfo {
+            peer_certificate,
+            protocol_version: None,
+            cipher_suite: None,
+        })
     }
 }
 
@@ -971,13 +979,7 @@ impl TlsInfoFactory for hyper_tls::MaybeHttpsStream<TokioIo<tokio::net::TcpStrea
 #[cfg(feature = "__rustls")]
 impl TlsInfoFactory for tokio_rustls::client::TlsStream<TokioIo<TokioIo<tokio::net::TcpStream>>> {
     fn tls_info(&self) -> Option<crate::tls::TlsInfo> {
-        let peer_certificate = self
-            .get_ref()
-            .1
-            .peer_certificates()
-            .and_then(|certs| certs.first())
-            .map(|c| c.to_vec());
-        Some(crate::tls::TlsInfo { peer_certificate })
+        Some(crate::tls::TlsInfo::from_rustls(self.get_ref().1))
     }
 }
 
@@ -988,13 +990,7 @@ impl TlsInfoFactory
     >
 {
     fn tls_info(&self) -> Option<crate::tls::TlsInfo> {
-        let peer_certificate = self
-            .get_ref()
-            .1
-            .peer_certificates()
-            .and_then(|certs| certs.first())
-            .map(|c| c.to_vec());
-        Some(crate::tls::TlsInfo { peer_certificate })
+        Some(crate::tls::TlsInfo::from_rustls(self.get_ref().1))
     }
 }
 
@@ -1028,7 +1024,11 @@ impl TlsInfoFactory for tokio_native_tls::TlsStream<TokioIo<TokioIo<tokio::net::
             .ok()
             .flatten()
             .and_then(|c| c.to_der().ok());
-        Some(crate::tls::TlsInfo { peer_certificate })
+        Some(crate::tls::TlsInfo {
+            peer_certificate,
+            protocol_version: None,
+            cipher_suite: None,
+        })
     }
 }
 
@@ -1046,7 +1046,11 @@ impl TlsInfoFactory
             .ok()
             .flatten()
             .and_then(|c| c.to_der().ok());
-        Some(crate::tls::TlsInfo { peer_certificate })
+        Some(crate::tls::TlsInfo {
+            peer_certificate,
+            protocol_version: None,
+            cipher_suite: None,
+        })
     }
 }
 
@@ -1065,13 +1069,7 @@ impl TlsInfoFactory for hyper_tls::MaybeHttpsStream<TokioIo<tokio::net::UnixStre
 #[cfg(unix)]
 impl TlsInfoFactory for tokio_rustls::client::TlsStream<TokioIo<TokioIo<tokio::net::UnixStream>>> {
     fn tls_info(&self) -> Option<crate::tls::TlsInfo> {
-        let peer_certificate = self
-            .get_ref()
-            .1
-            .peer_certificates()
-            .and_then(|certs| certs.first())
-            .map(|c| c.to_vec());
-        Some(crate::tls::TlsInfo { peer_certificate })
+        Some(crate::tls::TlsInfo::from_rustls(self.get_ref().1))
     }
 }
 
@@ -1083,13 +1081,7 @@ impl TlsInfoFactory
     >
 {
     fn tls_info(&self) -> Option<crate::tls::TlsInfo> {
-        let peer_certificate = self
-            .get_ref()
-            .1
-            .peer_certificates()
-            .and_then(|certs| certs.first())
-            .map(|c| c.to_vec());
-        Some(crate::tls::TlsInfo { peer_certificate })
+        Some(crate::tls::TlsInfo::from_rustls(self.get_ref().1))
     }
 }
 
@@ -1123,6 +1115,26 @@ impl<T: AsyncConn> AsyncConnWithInfo for T {}
 
 type BoxConn = Box<dyn AsyncConnWithInfo>;
 
+/// Counts the responses received over a single connection.
+///
+/// A fresh counter is attached to every new connection, and shared by all
+/// responses received over it, so that each response can tell whether its
+/// connection was reused.
+#[derive(Clone, Default)]
+pub(crate) struct ConnectionUses(Arc<std::sync::atomic::AtomicUsize>);
+
+impl ConnectionUses {
+    /// Records one more response, returning whether the connection had been
+    /// used before.
+    pub(crate) fn record(&self) -> bool {
+        self.0.fetch_add(1, std::sync::atomic::Ordering::Relaxed) > 0
+    }
+}
+
+/// Response extension recording whether the connection was reused.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct ConnectionReused(pub(crate) bool);
+
 pub(crate) mod sealed {
     use super::*;
     #[derive(Debug)]
@@ -1145,7 +1157,11 @@ pub(crate) mod sealed {
 
     impl Connection for Conn {
         fn connected(&self) -> Connected {
-            let connected = self.inner.connected().proxy(self.is_proxy);
+            let connected = self
+                .inner
+                .connected()
+                .proxy(self.is_proxy)
+                .extra(super::ConnectionUses::default());
             #[cfg(feature = "__tls")]
             if self.tls_info {
                 if let Some(tls_info) = self.inner.tls_info() {
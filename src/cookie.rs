@@ -8,6 +8,19 @@ use std::sync::RwLock;
 use std::time::SystemTime;
 
 /// Actions for a persistent cookie store providing session support.
+///
+/// A store is installed with
+/// [`ClientBuilder::cookie_provider`](crate::ClientBuilder::cookie_provider).
+/// The `Client` calls [`cookies`](CookieStore::cookies) before sending each
+/// request that doesn't already have a `Cookie` header, including each
+/// redirect, and
+/// [`set_cookies`](CookieStore::set_cookies) after receiving each response
+/// that carries `Set-Cookie` headers.
+///
+/// Since a `Client` is shared across threads and tasks, a store must be
+/// `Send + Sync`, and may be called concurrently from several requests.
+/// Implementations should use interior mutability, such as a lock, and not
+/// block for long while holding it.
 pub trait CookieStore: Send + Sync {
     /// Store a set of Set-Cookie header values received from `url`
     fn set_cookies(&self, cookie_headers: &mut dyn Iterator<Item = &HeaderValue>, url: &url::Url);
@@ -119,6 +132,44 @@ pub(crate) fn extract_response_cookies<'a>(
         .map(|value| Cookie::parse(value))
 }
 
+/// Whether a single request uses the client's cookie store.
+///
+/// These are the values of the Fetch standard's [credentials mode] request
+/// option, applied to the cookie store. Set one with
+/// [`RequestBuilder::fetch_credentials_mode`](crate::RequestBuilder::fetch_credentials_mode).
+///
+/// [credentials mode]: https://fetch.spec.whatwg.org/#concept-request-credentials-mode
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Credentials {
+    /// Don't send cookies from the store nor store the cookies responses set,
+    /// like [`RequestBuilder::no_cookies`](crate::RequestBuilder::no_cookies).
+    Omit,
+    /// Only use the cookie store for the request and its redirects to the
+    /// same origin, skipping it for redirects to other origins.
+    SameOrigin,
+    /// Use the cookie store for the request and all its redirects. This is
+    /// the default.
+    Include,
+}
+
+/// Request extension marking a request that must not send or store cookies.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct NoCookies;
+
+pub(crate) fn set_credentials(extensions: &mut http::Extensions, credentials: Credentials) {
+    extensions.remove::<NoCookies>();
+    extensions.remove::<Credentials>();
+    match credentials {
+        Credentials::Omit => {
+            extensions.insert(NoCookies);
+        }
+        Credentials::SameOrigin => {
+            extensions.insert(credentials);
+        }
+        Credentials::Include => (),
+    }
+}
+
 /// Error representing a parse failure of a 'Set-Cookie' header.
 pub(crate) struct CookieParseError(cookie_crate::ParseError);
 
@@ -238,14 +289,18 @@ pub(crate) mod service {
             let clone = self.inner.clone();
             let mut inner = std::mem::replace(&mut self.inner, clone);
             let url = Url::parse(req.uri().to_string().as_str()).expect("invalid URL");
-            if let Some(cookie_store) = self.cookie_store.as_ref() {
+            let cookie_store = if req.extensions().get::<cookie::NoCookies>().is_some() {
+                None
+            } else {
+                self.cookie_store.clone()
+            };
+            if let Some(cookie_store) = cookie_store.as_ref() {
                 if req.headers().get(crate::header::COOKIE).is_none() {
                     let headers = req.headers_mut();
                     crate::util::add_cookie_header(headers, &**cookie_store, &url);
                 }
             }
 
-            let cookie_store = self.cookie_store.clone();
             ResponseFuture {
                 future: inner.call(req),
                 cookie_store,
@@ -1,8 +1,10 @@
 #![cfg_attr(target_arch = "wasm32", allow(unused))]
 use std::error::Error as StdError;
-use std::fmt;
+use std::fmt::{self, Write};
 use std::io;
 
+use bytes::Bytes;
+
 use crate::util::Escape;
 use crate::{StatusCode, Url};
 
@@ -20,10 +22,14 @@ pub struct Error {
 
 pub(crate) type BoxError = Box<dyn StdError + Send + Sync>;
 
+/// The most bytes of a captured response body shown when displaying an error.
+const BODY_SNIPPET_LEN: usize = 256;
+
 struct Inner {
     kind: Kind,
     source: Option<BoxError>,
     url: Option<Url>,
+    response_body: Option<Bytes>,
 }
 
 impl Error {
@@ -36,6 +42,7 @@ impl Error {
                 kind,
                 source: source.map(Into::into),
                 url: None,
+                response_body: None,
             }),
         }
     }
@@ -90,6 +97,24 @@ impl Error {
         self
     }
 
+    /// Returns the body of the response that caused this error, if it was
+    /// captured.
+    ///
+    /// Status errors returned by
+    /// [`Response::error_for_status_with_body`](crate::Response::error_for_status_with_body)
+    /// and
+    /// [`RequestBuilder::send_streaming`](crate::RequestBuilder::send_streaming)
+    /// carry the body of the error response, which often explains what went
+    /// wrong. The start of it is also shown when the error is displayed.
+    pub fn response_body(&self) -> Option<&[u8]> {
+        self.inner.response_body.as_deref()
+    }
+
+    pub(crate) fn with_response_body(mut self, body: Bytes) -> Self {
+        self.inner.response_body = Some(body);
+        self
+    }
+
     /// Returns true if the error is from a type Builder.
     pub fn is_builder(&self) -> bool {
         matches!(self.inner.kind, Kind::Builder)
@@ -160,6 +185,45 @@ impl Error {
         false
     }
 
+    /// Returns true if the error is related to TLS, such as a failed
+    /// handshake or an invalid certificate.
+    ///
+    /// # Optional
+    ///
+    /// This requires the optional `default-tls`, `native-tls`, or `rustls-tls(-...)`
+    /// feature to be enabled.
+    #[cfg(feature = "__tls")]
+    #[cfg_attr(
+        docsrs,
+        doc(cfg(any(
+            feature = "default-tls",
+            feature = "native-tls",
+            feature = "rustls-tls"
+        )))
+    )]
+    pub fn is_tls(&self) -> bool {
+        let mut source = self.source();
+
+        while let Some(err) = source {
+            #[cfg(feature = "default-tls")]
+            if err.is::<native_tls_crate::Error>() {
+                return true;
+            }
+            #[cfg(feature = "__rustls")]
+            if err.is::<rustls::Error>() {
+                return true;
+            }
+            // TLS errors are often wrapped in an `io::Error`, whose `source`
+            // skips over the wrapped error itself.
+            source = match err.downcast_ref::<io::Error>() {
+                Some(io) => io.get_ref().map(|inner| inner as _),
+                None => err.source(),
+            };
+        }
+
+        false
+    }
+
     /// Returns true if the error is related to the request or response body
     pub fn is_body(&self) -> bool {
         matches!(self.inner.kind, Kind::Body)
@@ -181,6 +245,50 @@ impl Error {
         }
     }
 
+    /// Returns the first error of type `T` in the source chain, if any.
+    ///
+    /// This recovers the concrete error behind this one, looking through
+    /// `io::Error`s wrapping other errors. The errors to expect depend on the
+    /// kind:
+    ///
+    /// - [`is_decode`](Error::is_decode): a `serde_json::Error` from
+    ///   [`Response::json`](crate::Response::json), or an `io::Error` from
+    ///   decompressing the body.
+    /// - [`is_connect`](Error::is_connect) and [`is_request`](Error::is_request):
+    ///   a `hyper::Error`, and often an `io::Error` from the socket.
+    /// - [`is_body`](Error::is_body): a `hyper::Error`, or the error of a
+    ///   streamed request body.
+    /// - [`is_builder`](Error::is_builder): the error of the invalid input,
+    ///   such as an `http::header::InvalidHeaderValue`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # async fn run() {
+    /// let res = reqwest::get("http://[::1]:1/").await;
+    /// if let Err(err) = res {
+    ///     if let Some(io) = err.downcast_ref::<std::io::Error>() {
+    ///         println!("io error: {:?}", io.kind());
+    ///     }
+    /// }
+    /// # }
+    /// ```
+    pub fn downcast_ref<T: StdError + 'static>(&self) -> Option<&T> {
+        let mut source = self.source();
+
+        while let Some(err) = source {
+            if let Some(found) = err.downcast_ref::<T>() {
+                return Some(found);
+            }
+            source = match err.downcast_ref::<io::Error>() {
+                Some(io) => io.get_ref().map(|inner| inner as _),
+                None => err.source(),
+            };
+        }
+
+        None
+    }
+
     // private
 
     #[allow(unused)]
@@ -263,10 +371,37 @@ impl fmt::Display for Error {
             write!(f, " for url ({url})")?;
         }
 
+        if let Some(body) = &self.inner.response_body {
+            write_body_snippet(f, body)?;
+        }
+
         Ok(())
     }
 }
 
+fn write_body_snippet(f: &mut fmt::Formatter, body: &[u8]) -> fmt::Result {
+    if body.is_empty() {
+        return Ok(());
+    }
+
+    let mut snippet = &body[..body.len().min(BODY_SNIPPET_LEN)];
+    // don't cut a character in half
+    if let Err(e) = std::str::from_utf8(snippet) {
+        if e.error_len().is_none() {
+            snippet = &snippet[..e.valid_up_to()];
+        }
+    }
+
+    f.write_str(": ")?;
+    for c in String::from_utf8_lossy(snippet).chars() {
+        f.write_char(if c.is_control() { ' ' } else { c })?;
+    }
+    if snippet.len() < body.len() {
+        f.write_str("...")?;
+    }
+    Ok(())
+}
+
 impl StdError for Error {
     fn source(&self) -> Option<&(dyn StdError + 'static)> {
         self.inner.source.as_ref().map(|e| &**e as _)
@@ -406,6 +541,21 @@ impl fmt::Display for BadScheme {
 
 impl StdError for BadScheme {}
 
+#[derive(Debug)]
+pub(crate) struct BodyLimitExceeded(pub(crate) usize);
+
+impl fmt::Display for BodyLimitExceeded {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "response body is larger than the limit of {} bytes",
+            self.0
+        )
+    }
+}
+
+impl StdError for BodyLimitExceeded {}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -424,6 +574,24 @@ mod tests {
         assert_sync::<Error>();
     }
 
+    #[test]
+    fn status_error_body_snippet() {
+        let url = Url::parse("http://example.com/").unwrap();
+        let err = status_code(url.clone(), StatusCode::BAD_REQUEST, None)
+            .with_response_body(Bytes::from_static(b"{\"error\":\n\"bad\"}"));
+        assert_eq!(
+            err.to_string(),
+            "HTTP status client error (400 Bad Request) for url (http://example.com/): {\"error\": \"bad\"}"
+        );
+
+        // long bodies are cut at a character boundary
+        let body = format!("{}\u{e9}tc", "a".repeat(BODY_SNIPPET_LEN - 1));
+        let err = status_code(url, StatusCode::BAD_GATEWAY, None).with_response_body(body.into());
+        let msg = err.to_string();
+        assert!(msg.ends_with(&format!(": {}...", "a".repeat(BODY_SNIPPET_LEN - 1))));
+        assert_eq!(err.response_body().unwrap().len(), BODY_SNIPPET_LEN + 3);
+    }
+
     #[test]
     fn mem_size_of() {
         use std::mem::size_of;
@@ -465,4 +633,65 @@ mod tests {
         let nested = super::request(io);
         assert!(nested.is_timeout());
     }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn downcast_ref() {
+        let json = serde_json::from_str::<u8>("nope").unwrap_err();
+        let err = super::decode(json);
+        assert!(err.downcast_ref::<serde_json::Error>().unwrap().is_syntax());
+        assert!(err.downcast_ref::<io::Error>().is_none());
+
+        // through an `io::Error` wrapping it
+        let json = serde_json::from_str::<u8>("nope").unwrap_err();
+        let err = super::decode_io(io::Error::new(io::ErrorKind::Other, json));
+        assert!(err.downcast_ref::<io::Error>().is_some());
+        assert!(err.downcast_ref::<serde_json::Error>().is_some());
+    }
+
+    #[test]
+    fn one_kind_per_constructor() {
+        type Predicate = fn(&Error) -> bool;
+
+        fn kinds(err: &Error) -> Vec<&'static str> {
+            let predicates: [(&str, Predicate); 6] = [
+                ("builder", Error::is_builder),
+                ("request", Error::is_request),
+                ("redirect", Error::is_redirect),
+                ("status", Error::is_status),
+                ("body", Error::is_body),
+                ("decode", Error::is_decode),
+            ];
+            predicates
+                .iter()
+                .filter(|(_, is)| is(err))
+                .map(|(name, _)| *name)
+                .collect()
+        }
+
+        let url = Url::parse("http://example.com").unwrap();
+        assert_eq!(kinds(&super::builder("e")), ["builder"]);
+        assert_eq!(kinds(&super::request("e")), ["request"]);
+        assert_eq!(kinds(&super::redirect("e", url.clone())), ["redirect"]);
+        assert_eq!(kinds(&super::body("e")), ["body"]);
+        assert_eq!(kinds(&super::decode("e")), ["decode"]);
+        assert_eq!(kinds(&super::upgrade("e")), Vec::<&str>::new());
+
+        let status = super::status_code(
+            url,
+            StatusCode::NOT_FOUND,
+            #[cfg(not(target_arch = "wasm32"))]
+            None,
+        );
+        assert_eq!(kinds(&status), ["status"]);
+    }
+
+    #[test]
+    fn status_only_for_status_errors() {
+        let err = super::request("orig");
+        assert_eq!(err.status(), None);
+
+        let err = super::decode("orig");
+        assert_eq!(err.status(), None);
+    }
 }
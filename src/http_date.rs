@@ -0,0 +1,49 @@
+use std::time::SystemTime;
+
+use http::HeaderValue;
+
+/// A trait to try to convert some type into an HTTP date, as used by headers
+/// like `If-Modified-Since`.
+///
+/// It's implemented for `SystemTime`, and for strings in the HTTP date
+/// format, like `"Sun, 06 Nov 1994 08:49:37 GMT"`.
+///
+/// This trait is "sealed", such that only types within reqwest can
+/// implement it.
+pub trait IntoHttpDate: IntoHttpDateSealed {}
+
+impl IntoHttpDate for SystemTime {}
+impl IntoHttpDate for String {}
+impl IntoHttpDate for &str {}
+impl IntoHttpDate for &String {}
+
+pub trait IntoHttpDateSealed {
+    fn into_header_value(self) -> crate::Result<HeaderValue>;
+}
+
+impl IntoHttpDateSealed for SystemTime {
+    fn into_header_value(self) -> crate::Result<HeaderValue> {
+        HeaderValue::from_str(&httpdate::fmt_http_date(self)).map_err(crate::error::builder)
+    }
+}
+
+impl IntoHttpDateSealed for &str {
+    fn into_header_value(self) -> crate::Result<HeaderValue> {
+        // also accepts the obsolete formats, which are sent in the preferred one
+        httpdate::parse_http_date(self)
+            .map_err(crate::error::builder)?
+            .into_header_value()
+    }
+}
+
+impl IntoHttpDateSealed for String {
+    fn into_header_value(self) -> crate::Result<HeaderValue> {
+        (&*self).into_header_value()
+    }
+}
+
+impl IntoHttpDateSealed for &String {
+    fn into_header_value(self) -> crate::Result<HeaderValue> {
+        (&**self).into_header_value()
+    }
+}
@@ -0,0 +1,58 @@
+//! Request and response interceptors
+//!
+//! An [`Interceptor`] is installed with
+//! [`ClientBuilder::with_interceptor`](crate::ClientBuilder::with_interceptor),
+//! and sees every request the `Client` executes and every response it
+//! receives. This is useful for logging, tracing, or injecting headers.
+//!
+//! # Example
+//!
+//! ```
+//! use std::sync::Arc;
+//! use reqwest::interceptor::Interceptor;
+//!
+//! struct RequestId;
+//!
+//! impl Interceptor for RequestId {
+//!     fn on_request(&self, req: &mut reqwest::Request) {
+//!         req.headers_mut()
+//!             .insert("x-request-id", reqwest::header::HeaderValue::from_static("42"));
+//!     }
+//!
+//!     fn on_response(&self, res: &reqwest::Response) {
+//!         println!("{} {}", res.url(), res.status());
+//!     }
+//! }
+//!
+//! # fn run() -> Result<(), reqwest::Error> {
+//! let client = reqwest::Client::builder()
+//!     .with_interceptor(Arc::new(RequestId))
+//!     .build()?;
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::{Request, Response};
+
+/// Hooks called by a `Client` around each request.
+///
+/// When several interceptors are installed, `on_request` is called in the
+/// order they were added, and `on_response` in the reverse order.
+pub trait Interceptor: Send + Sync {
+    /// Called before a request is sent.
+    ///
+    /// This happens before the client's default headers are added, so a
+    /// header set here takes precedence over a default one. Redirects and
+    /// retries of the request are not intercepted again.
+    fn on_request(&self, req: &mut Request) {
+        let _ = req;
+    }
+
+    /// Called when the final response has been received, before its body is
+    /// read.
+    ///
+    /// This includes responses answered from a `ClientBuilder::cache`.
+    fn on_response(&self, res: &Response) {
+        let _ = res;
+    }
+}
@@ -1,3 +1,5 @@
+use std::borrow::Cow;
+
 use url::Url;
 
 /// A trait to try to convert some type into a `Url`.
@@ -10,6 +12,8 @@ impl IntoUrl for Url {}
 impl IntoUrl for String {}
 impl<'a> IntoUrl for &'a str {}
 impl<'a> IntoUrl for &'a String {}
+impl IntoUrl for &Url {}
+impl IntoUrl for Cow<'_, str> {}
 
 pub trait IntoUrlSealed {
     // Besides parsing as a valid `Url`, the `Url` must be a valid
@@ -43,6 +47,16 @@ impl IntoUrlSealed for Url {
     }
 }
 
+impl IntoUrlSealed for &Url {
+    fn into_url(self) -> crate::Result<Url> {
+        self.clone().into_url()
+    }
+
+    fn as_str(&self) -> &str {
+        self.as_ref()
+    }
+}
+
 impl<'a> IntoUrlSealed for &'a str {
     fn into_url(self) -> crate::Result<Url> {
         Url::parse(self).map_err(crate::error::builder)?.into_url()
@@ -73,12 +87,34 @@ impl IntoUrlSealed for String {
     }
 }
 
+impl IntoUrlSealed for Cow<'_, str> {
+    fn into_url(self) -> crate::Result<Url> {
+        (&*self).into_url()
+    }
+
+    fn as_str(&self) -> &str {
+        self.as_ref()
+    }
+}
+
 if_hyper! {
     pub(crate) fn try_uri(url: &Url) -> crate::Result<http::Uri> {
         url.as_str()
             .parse()
             .map_err(|_| crate::error::url_invalid_uri(url.clone()))
     }
+
+    /// Converts `url` into a `Url`, resolving it against `base` if there is
+    /// one. An absolute `url` replaces the base entirely.
+    pub(crate) fn resolve<U: IntoUrl>(base: Option<&Url>, url: U) -> crate::Result<Url> {
+        match base {
+            Some(base) => base
+                .join(url.as_str())
+                .map_err(crate::error::builder)?
+                .into_url(),
+            None => url.into_url(),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -208,7 +208,14 @@
 //! - **json**: Provides serialization and deserialization for JSON bodies.
 //! - **multipart**: Provides functionality for multipart forms.
 //! - **stream**: Adds support for `futures::Stream`.
+//! - **cache**: Provides an in-memory [HTTP response cache](cache).
+//! - **sse**: Provides parsing of [Server-Sent Events](sse) responses.
+//! - **websocket**: Provides a minimal [WebSocket](websocket) client.
+//! - **test-util**: Provides a [mock transport][test_util] for testing
+//!   without network access.
 //! - **socks**: Provides SOCKS5 proxy support.
+//! - **tracing**: Emits [`tracing`](https://docs.rs/tracing) spans for
+//!   requests, and events for their redirects and retries.
 //! - **hickory-dns**: Enables a hickory-dns async resolver instead of default
 //!   threadpool using `getaddrinfo`.
 //! - **system-proxy** *(enabled by default)*: Use Windows and macOS system
@@ -237,6 +244,7 @@
 //!
 //! [hyper]: https://hyper.rs
 //! [blocking]: ./blocking/index.html
+//! [test_util]: ./test_util/index.html
 //! [client]: ./struct.Client.html
 //! [response]: ./struct.Response.html
 //! [get]: ./fn.get.html
@@ -324,6 +332,108 @@ pub async fn get<T: IntoUrl>(url: T) -> crate::Result<Response> {
     Client::builder().build()?.get(url).send().await
 }
 
+/// Shortcut method to start building a `POST` request.
+///
+/// Unlike [`get`], this returns a [`RequestBuilder`](./struct.RequestBuilder.html)
+/// so that a body or headers can be added before sending.
+///
+/// **NOTE**: This function creates a new internal `Client` on each call,
+/// and so should not be used if making many requests. Create a
+/// [`Client`](./struct.Client.html) instead.
+///
+/// # Examples
+///
+/// ```rust
+/// # async fn run() -> Result<(), reqwest::Error> {
+/// let res = reqwest::post("http://httpbin.org/post")?
+///     .body("the exact body that is sent")
+///     .send()
+///     .await?;
+/// # Ok(())
+/// # }
+/// ```
+///
+/// # Errors
+///
+/// This function fails if the native TLS backend cannot be initialized.
+/// Errors from parsing the `Url` or sending the request are returned when
+/// the request is sent.
+pub fn post<T: IntoUrl>(url: T) -> crate::Result<RequestBuilder> {
+    Ok(Client::builder().build()?.post(url))
+}
+
+/// Shortcut method to start building a `PUT` request.
+///
+/// Unlike [`get`], this returns a [`RequestBuilder`](./struct.RequestBuilder.html)
+/// so that a body or headers can be added before sending.
+///
+/// **NOTE**: This function creates a new internal `Client` on each call,
+/// and so should not be used if making many requests. Create a
+/// [`Client`](./struct.Client.html) instead.
+///
+/// # Errors
+///
+/// This function fails if the native TLS backend cannot be initialized.
+/// Errors from parsing the `Url` or sending the request are returned when
+/// the request is sent.
+pub fn put<T: IntoUrl>(url: T) -> crate::Result<RequestBuilder> {
+    Ok(Client::builder().build()?.put(url))
+}
+
+/// Shortcut method to start building a `PATCH` request.
+///
+/// Unlike [`get`], this returns a [`RequestBuilder`](./struct.RequestBuilder.html)
+/// so that a body or headers can be added before sending.
+///
+/// **NOTE**: This function creates a new internal `Client` on each call,
+/// and so should not be used if making many requests. Create a
+/// [`Client`](./struct.Client.html) instead.
+///
+/// # Errors
+///
+/// This function fails if the native TLS backend cannot be initialized.
+/// Errors from parsing the `Url` or sending the request are returned when
+/// the request is sent.
+pub fn patch<T: IntoUrl>(url: T) -> crate::Result<RequestBuilder> {
+    Ok(Client::builder().build()?.patch(url))
+}
+
+/// Shortcut method to start building a `DELETE` request.
+///
+/// Unlike [`get`], this returns a [`RequestBuilder`](./struct.RequestBuilder.html)
+/// so that a body or headers can be added before sending.
+///
+/// **NOTE**: This function creates a new internal `Client` on each call,
+/// and so should not be used if making many requests. Create a
+/// [`Client`](./struct.Client.html) instead.
+///
+/// # Errors
+///
+/// This function fails if the native TLS backend cannot be initialized.
+/// Errors from parsing the `Url` or sending the request are returned when
+/// the request is sent.
+pub fn delete<T: IntoUrl>(url: T) -> crate::Result<RequestBuilder> {
+    Ok(Client::builder().build()?.delete(url))
+}
+
+/// Shortcut method to start building a `HEAD` request.
+///
+/// Unlike [`get`], this returns a [`RequestBuilder`](./struct.RequestBuilder.html)
+/// so that a body or headers can be added before sending.
+///
+/// **NOTE**: This function creates a new internal `Client` on each call,
+/// and so should not be used if making many requests. Create a
+/// [`Client`](./struct.Client.html) instead.
+///
+/// # Errors
+///
+/// This function fails if the native TLS backend cannot be initialized.
+/// Errors from parsing the `Url` or sending the request are returned when
+/// the request is sent.
+pub fn head<T: IntoUrl>(url: T) -> crate::Result<RequestBuilder> {
+    Ok(Client::builder().build()?.head(url))
+}
+
 fn _assert_impls() {
     fn assert_send<T: Send>() {}
     fn assert_sync<T: Sync>() {}
@@ -357,29 +467,47 @@ if_hyper! {
     doctest!("../README.md");
 
     pub use self::async_impl::{
-        Body, Client, ClientBuilder, Request, RequestBuilder, Response, Upgraded,
+        AcceptRanges, Body, Client, ClientBuilder, Link, Request, RequestBuilder, Response,
+        Upgraded,
     };
+    #[cfg(feature = "stream")]
+    pub use self::async_impl::BodySender;
+    #[cfg(any(feature = "gzip", feature = "brotli"))]
+    pub use self::async_impl::Encoding;
     pub use self::proxy::{Proxy,NoProxy};
+    pub use self::http_date::IntoHttpDate;
     #[cfg(feature = "__tls")]
     // Re-exports, to be removed in a future release
     pub use tls::{Certificate, Identity};
     #[cfg(feature = "multipart")]
     pub use self::async_impl::multipart;
+    pub use self::async_impl::api;
 
 
     mod async_impl;
     #[cfg(feature = "blocking")]
     pub mod blocking;
+    #[cfg(feature = "cache")]
+    pub mod cache;
     mod connect;
     #[cfg(feature = "cookies")]
     pub mod cookie;
     pub mod dns;
+    mod http_date;
+    pub mod interceptor;
     mod proxy;
     pub mod redirect;
     pub mod retry;
+    #[cfg(feature = "sse")]
+    pub mod sse;
+    #[cfg(feature = "test-util")]
+    pub mod test_util;
     #[cfg(feature = "__tls")]
     pub mod tls;
+    pub mod transport;
     mod util;
+    #[cfg(feature = "websocket")]
+    pub mod websocket;
 
     #[cfg(docsrs)]
     pub use connect::uds::UnixSocketProvider;
@@ -51,6 +51,9 @@ use crate::Url;
 /// # Ok(())
 /// # }
 /// ```
+///
+/// Without that feature a socks proxy URL is an error, as is a URL with any
+/// scheme other than `http` or `https`.
 #[derive(Clone)]
 pub struct Proxy {
     extra: Extra,
@@ -72,6 +75,7 @@ struct Extra {
 
 // ===== Internal =====
 
+#[derive(Clone)]
 pub(crate) struct Matcher {
     inner: Matcher_,
     extra: Extra,
@@ -79,8 +83,9 @@ pub(crate) struct Matcher {
     maybe_has_http_custom_headers: bool,
 }
 
+#[derive(Clone)]
 enum Matcher_ {
-    Util(matcher::Matcher),
+    Util(Arc<matcher::Matcher>),
     Custom(Custom),
 }
 
@@ -123,15 +128,24 @@ pub trait IntoProxy {
 impl<S: IntoUrl> IntoProxy for S {
     fn into_proxy(self) -> crate::Result<Url> {
         match self.as_str().into_url() {
-            Ok(mut url) => {
-                // If the scheme is a SOCKS protocol and no port is specified, set the default
-                if url.port().is_none()
-                    && matches!(url.scheme(), "socks4" | "socks4a" | "socks5" | "socks5h")
-                {
-                    let _ = url.set_port(Some(1080));
+            Ok(url) => match url.scheme() {
+                "http" | "https" => Ok(url),
+                #[cfg(feature = "socks")]
+                "socks4" | "socks4a" | "socks5" | "socks5h" => {
+                    // If no port is specified, set the default
+                    let mut url = url;
+                    if url.port().is_none() {
+                        let _ = url.set_port(Some(1080));
+                    }
+                    Ok(url)
                 }
-                Ok(url)
-            }
+                // Rather than connecting to these as if they were HTTP proxies.
+                #[cfg(not(feature = "socks"))]
+                "socks4" | "socks4a" | "socks5" | "socks5h" => Err(crate::error::builder(
+                    "socks proxies require the `socks` feature",
+                )),
+                _ => Err(crate::error::builder("unknown proxy scheme")),
+            },
             Err(e) => {
                 let mut presumed_to_have_scheme = true;
                 let mut source = e.source();
@@ -377,34 +391,34 @@ impl Proxy {
                 maybe_has_http_auth = cache_maybe_has_http_auth(&url, &extra.auth);
                 maybe_has_http_custom_headers =
                     cache_maybe_has_http_custom_headers(&url, &extra.misc);
-                Matcher_::Util(
+                Matcher_::Util(Arc::new(
                     matcher::Matcher::builder()
                         .all(String::from(url))
                         .no(no_proxy.as_ref().map(|n| n.inner.as_ref()).unwrap_or(""))
                         .build(),
-                )
+                ))
             }
             Intercept::Http(url) => {
                 maybe_has_http_auth = cache_maybe_has_http_auth(&url, &extra.auth);
                 maybe_has_http_custom_headers =
                     cache_maybe_has_http_custom_headers(&url, &extra.misc);
-                Matcher_::Util(
+                Matcher_::Util(Arc::new(
                     matcher::Matcher::builder()
                         .http(String::from(url))
                         .no(no_proxy.as_ref().map(|n| n.inner.as_ref()).unwrap_or(""))
                         .build(),
-                )
+                ))
             }
             Intercept::Https(url) => {
                 maybe_has_http_auth = cache_maybe_has_http_auth(&url, &extra.auth);
                 maybe_has_http_custom_headers =
                     cache_maybe_has_http_custom_headers(&url, &extra.misc);
-                Matcher_::Util(
+                Matcher_::Util(Arc::new(
                     matcher::Matcher::builder()
                         .https(String::from(url))
                         .no(no_proxy.as_ref().map(|n| n.inner.as_ref()).unwrap_or(""))
                         .build(),
-                )
+                ))
             }
             Intercept::Custom(mut custom) => {
                 maybe_has_http_auth = true; // never know
@@ -490,9 +504,10 @@ impl NoProxy {
     /// * Entries are expected to be comma-separated (whitespace between entries is ignored)
     /// * IP addresses (both IPv4 and IPv6) are allowed, as are optional subnet masks (by adding /size,
     ///   for example "`192.168.1.0/24`").
-    /// * An entry "`*`" matches all hostnames (this is the only wildcard allowed)
-    /// * Any other entry is considered a domain name (and may contain a leading dot, for example `google.com`
-    ///   and `.google.com` are equivalent) and would match both that domain AND all subdomains.
+    /// * An entry "`*`" matches all hostnames
+    /// * Any other entry is considered a domain name (and may contain a leading dot or `*.`, for example
+    ///   `google.com`, `.google.com` and `*.google.com` are equivalent) and would match both that domain AND
+    ///   all subdomains.
     ///
     /// For example, if `"NO_PROXY=google.com, 192.168.1.0/24"` was set, all the following would match
     /// (and therefore would bypass the proxy):
@@ -504,15 +519,63 @@ impl NoProxy {
     pub fn from_string(no_proxy_list: &str) -> Option<Self> {
         // lazy parsed, to not make the type public in hyper-util
         Some(NoProxy {
-            inner: no_proxy_list.into(),
+            inner: normalize_no_proxy(no_proxy_list),
         })
     }
 }
 
+// hyper-util matches a leading dot as a suffix, but takes `*.` literally.
+fn normalize_no_proxy(no_proxy_list: &str) -> String {
+    no_proxy_list
+        .split(',')
+        .map(|entry| {
+            let entry = entry.trim();
+            if entry.starts_with("*.") {
+                &entry[1..]
+            } else {
+                entry
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
 impl Matcher {
     pub(crate) fn system() -> Self {
         Self {
-            inner: Matcher_::Util(matcher::Matcher::from_system()),
+            inner: Matcher_::Util(Arc::new(matcher::Matcher::from_system())),
+            extra: Extra {
+                auth: None,
+                misc: None,
+            },
+            // maybe env vars have auth!
+            maybe_has_http_auth: true,
+            maybe_has_http_custom_headers: true,
+        }
+    }
+
+    /// Proxies configured by the `ALL_PROXY`, `HTTPS_PROXY`, `HTTP_PROXY`
+    /// and `NO_PROXY` environment variables, or their lowercase versions.
+    pub(crate) fn env() -> Self {
+        fn var(names: &[&str]) -> String {
+            names
+                .iter()
+                .find_map(|name| std::env::var(name).ok())
+                .unwrap_or_default()
+        }
+
+        let mut builder = matcher::Matcher::builder()
+            .all(var(&["ALL_PROXY", "all_proxy"]))
+            .https(var(&["HTTPS_PROXY", "https_proxy"]))
+            .no(normalize_no_proxy(&var(&["NO_PROXY", "no_proxy"])));
+        // A CGI request can set `HTTP_PROXY` with a `Proxy` header, see
+        // https://httpoxy.org
+        if std::env::var_os("REQUEST_METHOD").is_none() {
+            builder = builder.http(var(&["HTTP_PROXY", "http_proxy"]));
+        }
+
+        Self {
+            inner: Matcher_::Util(Arc::new(builder.build())),
             extra: Extra {
                 auth: None,
                 misc: None,
@@ -919,6 +982,34 @@ mod tests {
         assert!(m.maybe_has_http_auth(), "http forwards");
     }
 
+    #[test]
+    fn test_no_proxy_wildcard() {
+        let no_proxy = NoProxy::from_string("*, *.example.com ,.foo.bar, 10.0.0.0/8").unwrap();
+        assert_eq!(no_proxy.inner, "*,.example.com,.foo.bar,10.0.0.0/8");
+
+        let m = Proxy::all("http://proxy.local")
+            .unwrap()
+            .no_proxy(NoProxy::from_string("*.example.com"))
+            .into_matcher();
+        assert!(m.intercept(&url("http://example.com")).is_none());
+        assert!(m.intercept(&url("http://api.example.com")).is_none());
+        assert!(m.intercept(&url("http://notexample.com")).is_some());
+    }
+
+    #[test]
+    fn test_unsupported_proxy_scheme() {
+        let err = Proxy::all("ftp://example.com").unwrap_err();
+        assert!(err.is_builder());
+        assert_eq!(err.source().unwrap().to_string(), "unknown proxy scheme");
+
+        #[cfg(not(feature = "socks"))]
+        {
+            let err = Proxy::http("socks5://example.com").unwrap_err();
+            assert!(err.is_builder());
+        }
+    }
+
+    #[cfg(feature = "socks")]
     #[test]
     fn test_socks_proxy_default_port() {
         {
@@ -1,8 +1,14 @@
+use std::time::Duration;
+
 use url::Url;
 
 #[derive(Debug, Clone, PartialEq)]
 pub(crate) struct ResponseUrl(pub Url);
 
+/// The `elapsed` of a `Response` converted into an `http::Response`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct ResponseElapsed(pub Duration);
+
 /// Extension trait for http::response::Builder objects
 ///
 /// Allows the user to add a `Url` to the http::Response
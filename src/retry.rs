@@ -37,19 +37,20 @@
 //! idempotent, or if the response status code indicates a transient error.
 
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use tower::retry::budget::{Budget as _, TpsBudget as Budget};
 
 /// Builder to configure retries
 ///
 /// Construct with [`for_host()`].
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct Builder {
-    //backoff: Backoff,
+    backoff: Option<Backoff>,
     budget: Option<f32>,
     classifier: classify::Classifier,
     max_retries_per_request: u32,
+    max_retry_after: Duration,
     scope: scope::Scoped,
 }
 
@@ -57,15 +58,23 @@ pub struct Builder {
 /// tower::retry::Policy privately.
 #[derive(Clone, Debug)]
 pub(crate) struct Policy {
+    backoff: Option<Backoff>,
     budget: Option<Arc<Budget>>,
     classifier: classify::Classifier,
     max_retries_per_request: u32,
+    max_retry_after: Duration,
     retry_cnt: u32,
     scope: scope::Scoped,
+    started: Option<Instant>,
 }
 
-//#[derive(Debug)]
-//struct Backoff;
+const DEFAULT_MAX_RETRY_AFTER: Duration = Duration::from_secs(60);
+
+#[derive(Clone, Copy, Debug)]
+struct Backoff {
+    base: Duration,
+    max: Duration,
+}
 
 /// Create a retry builder with a request scope.
 ///
@@ -101,9 +110,11 @@ impl Builder {
     /// For a more convenient constructor, see [`for_host()`].
     pub fn scoped(scope: impl scope::Scope) -> Self {
         Self {
+            backoff: None,
             budget: Some(0.2),
             classifier: classify::Classifier::Never,
             max_retries_per_request: 2, // on top of the original
+            max_retry_after: DEFAULT_MAX_RETRY_AFTER,
             scope: scope::Scoped::Dyn(Arc::new(scope)),
         }
     }
@@ -183,6 +194,114 @@ impl Builder {
         self.classify(classify::ClassifyFn(func))
     }
 
+    /// Wait with an exponential backoff before each retry.
+    ///
+    /// The first retry waits around `base`, and each one after that around
+    /// twice as long as the one before, up to `max`. Each wait is randomly
+    /// shortened by up to half, so that clients failing together don't
+    /// retry in lockstep.
+    ///
+    /// By default, retries are sent right away.
+    pub fn backoff(mut self, base: Duration, max: Duration) -> Self {
+        self.backoff = Some(Backoff { base, max });
+        self
+    }
+
+    /// Set the longest time a `Retry-After` header can make a retry wait.
+    ///
+    /// When a `429 Too Many Requests` or `503 Service Unavailable` response
+    /// that is retried has a `Retry-After` header, the retry waits at least
+    /// as long as the header asks, instead of just the backoff. This limit
+    /// keeps a misbehaving server from stalling the client for too long.
+    ///
+    /// Default is 60 seconds.
+    pub fn max_retry_after(mut self, max: Duration) -> Self {
+        self.max_retry_after = max;
+        self
+    }
+
+    /// Retry requests that receive a response with one of these status codes.
+    ///
+    /// Only requests with an idempotent method, such as `GET` or `PUT`, are
+    /// retried, since the server may already have acted on the original.
+    /// Protocol NACKs, which are always safe to retry, are retried as well.
+    ///
+    /// This replaces any classifier set before.
+    pub fn retry_on_status<I>(self, statuses: I) -> Self
+    where
+        I: IntoIterator<Item = http::StatusCode>,
+    {
+        self.retry_on_status_inner(statuses, false)
+    }
+
+    /// Retry requests that receive a `429 Too Many Requests`,
+    /// `502 Bad Gateway`, `503 Service Unavailable` or `504 Gateway Timeout`
+    /// response.
+    ///
+    /// See [`retry_on_status()`](Builder::retry_on_status) for details.
+    pub fn retry_on_transient_status(self) -> Self {
+        self.retry_on_status([
+            http::StatusCode::TOO_MANY_REQUESTS,
+            http::StatusCode::BAD_GATEWAY,
+            http::StatusCode::SERVICE_UNAVAILABLE,
+            http::StatusCode::GATEWAY_TIMEOUT,
+        ])
+    }
+
+    /// Like [`retry_on_status()`](Builder::retry_on_status), but also retry
+    /// requests with a non-idempotent method, such as `POST`.
+    ///
+    /// Only use this if the server is known to handle duplicates safely.
+    pub fn retry_on_status_any_method<I>(self, statuses: I) -> Self
+    where
+        I: IntoIterator<Item = http::StatusCode>,
+    {
+        self.retry_on_status_inner(statuses, true)
+    }
+
+    fn retry_on_status_inner<I>(mut self, statuses: I, any_method: bool) -> Self
+    where
+        I: IntoIterator<Item = http::StatusCode>,
+    {
+        self.classifier = classify::Classifier::Statuses {
+            statuses: statuses.into_iter().collect(),
+            any_method,
+        };
+        self
+    }
+
+    /// Retry a request whenever the predicate returns `true`.
+    ///
+    /// This is a shorthand for a classifier that only decides between
+    /// retrying or not. The `ReqRep` passed in exposes
+    /// the attempt number, the time elapsed since the first attempt, and
+    /// either the response status and headers or the error.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # fn with_builder(builder: reqwest::retry::Builder) -> reqwest::retry::Builder {
+    /// builder.retry_if(|req_rep| {
+    ///     req_rep.attempt() < 3
+    ///         && req_rep
+    ///             .headers()
+    ///             .map_or(false, |headers| headers.contains_key("x-retry"))
+    /// })
+    /// # }
+    /// ```
+    pub fn retry_if<F>(self, predicate: F) -> Self
+    where
+        F: Fn(&classify::ReqRep<'_>) -> bool + Send + Sync + 'static,
+    {
+        self.classify_fn(move |req_rep| {
+            if predicate(&req_rep) {
+                req_rep.retryable()
+            } else {
+                req_rep.success()
+            }
+        })
+    }
+
     /// Provide a classifier to determine if a request should be retried.
     pub fn classify(mut self, classifier: impl classify::Classify) -> Self {
         self.classifier = classify::Classifier::Dyn(Arc::new(classifier));
@@ -191,10 +310,12 @@ impl Builder {
 
     pub(crate) fn default() -> Builder {
         Self {
+            backoff: None,
             // unscoped protocols nacks doesn't need a budget
             budget: None,
             classifier: classify::Classifier::ProtocolNacks,
             max_retries_per_request: 2, // on top of the original
+            max_retry_after: DEFAULT_MAX_RETRY_AFTER,
             scope: scope::Scoped::Unscoped,
         }
     }
@@ -204,11 +325,14 @@ impl Builder {
             .budget
             .map(|p| Arc::new(Budget::new(Duration::from_secs(10), 10, p)));
         Policy {
+            backoff: self.backoff,
             budget,
             classifier: self.classifier,
             max_retries_per_request: self.max_retries_per_request,
+            max_retry_after: self.max_retry_after,
             retry_cnt: 0,
             scope: self.scope,
+            started: None,
         }
     }
 }
@@ -218,15 +342,16 @@ impl Builder {
 type Req = http::Request<crate::async_impl::body::Body>;
 
 impl<B> tower::retry::Policy<Req, http::Response<B>, crate::Error> for Policy {
-    // TODO? backoff futures...
-    type Future = std::future::Ready<()>;
+    type Future = BackoffFuture;
 
     fn retry(
         &mut self,
         req: &mut Req,
         result: &mut crate::Result<http::Response<B>>,
     ) -> Option<Self::Future> {
-        match self.classifier.classify(req, result) {
+        let attempt = self.retry_cnt + 1;
+        let elapsed = self.started.map(|t| t.elapsed()).unwrap_or_default();
+        match self.classifier.classify(req, result, attempt, elapsed) {
             classify::Action::Success => {
                 log::trace!("shouldn't retry!");
                 if let Some(ref budget) = self.budget {
@@ -237,8 +362,21 @@ impl<B> tower::retry::Policy<Req, http::Response<B>, crate::Error> for Policy {
             classify::Action::Retryable => {
                 log::trace!("could retry!");
                 if self.budget.as_ref().map(|b| b.withdraw()).unwrap_or(true) {
+                    let mut delay = self.backoff.map(|b| b.delay(self.retry_cnt));
+                    if let Some(retry_after) = result.as_ref().ok().and_then(retry_after) {
+                        let retry_after = retry_after.min(self.max_retry_after);
+                        delay = Some(delay.map_or(retry_after, |d| d.max(retry_after)));
+                    }
                     self.retry_cnt += 1;
-                    Some(std::future::ready(()))
+                    #[cfg(feature = "tracing")]
+                    tracing::debug!(
+                        attempt = self.retry_cnt,
+                        delay_ms = delay.map_or(0, |d| d.as_millis() as u64),
+                        "retrying request"
+                    );
+                    Some(BackoffFuture(
+                        delay.map(|d| Box::pin(tokio::time::sleep(d))),
+                    ))
                 } else {
                     log::debug!("retryable but could not withdraw from budget");
                     None
@@ -248,6 +386,11 @@ impl<B> tower::retry::Policy<Req, http::Response<B>, crate::Error> for Policy {
     }
 
     fn clone_request(&mut self, req: &Req) -> Option<Req> {
+        // Called on the shared policy right before each original request is
+        // sent, and then cloned along with it.
+        if self.retry_cnt == 0 {
+            self.started = Some(Instant::now());
+        }
         if self.retry_cnt > 0 && !self.scope.applies_to(req) {
             return None;
         }
@@ -267,6 +410,47 @@ impl<B> tower::retry::Policy<Req, http::Response<B>, crate::Error> for Policy {
     }
 }
 
+/// How long a response asks to wait before retrying, if it's one where
+/// `Retry-After` means that.
+fn retry_after<B>(res: &http::Response<B>) -> Option<Duration> {
+    match res.status() {
+        http::StatusCode::TOO_MANY_REQUESTS | http::StatusCode::SERVICE_UNAVAILABLE => {
+            crate::util::retry_after(res.headers(), std::time::SystemTime::now())
+        }
+        _ => None,
+    }
+}
+
+impl Backoff {
+    fn delay(&self, retry_cnt: u32) -> Duration {
+        let exp = self
+            .base
+            .checked_mul(1 << retry_cnt.min(31))
+            .map_or(self.max, |d| d.min(self.max));
+        // "equal jitter": keep at least half, randomize the rest
+        let half = exp / 2;
+        let jitter = (crate::util::fast_random() % 1_000) as f64 / 1_000.0;
+        half + half.mul_f64(jitter)
+    }
+}
+
+/// Waits out the backoff, if any, before a retry is sent.
+pub(crate) struct BackoffFuture(Option<std::pin::Pin<Box<tokio::time::Sleep>>>);
+
+impl std::future::Future for BackoffFuture {
+    type Output = ();
+
+    fn poll(
+        mut self: std::pin::Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<()> {
+        match self.0 {
+            Some(ref mut sleep) => sleep.as_mut().poll(cx),
+            None => std::task::Poll::Ready(()),
+        }
+    }
+}
+
 fn is_retryable_error(err: &crate::Error) -> bool {
     use std::error::Error as _;
 
@@ -394,23 +578,43 @@ mod classify {
     }
 
     #[derive(Debug)]
-    pub struct ReqRep<'a>(&'a super::Req, Result<http::StatusCode, &'a crate::Error>);
+    pub struct ReqRep<'a> {
+        req: &'a super::Req,
+        res: Result<(http::StatusCode, &'a http::HeaderMap), &'a crate::Error>,
+        attempt: u32,
+        elapsed: std::time::Duration,
+    }
 
     impl ReqRep<'_> {
         pub fn method(&self) -> &http::Method {
-            self.0.method()
+            self.req.method()
         }
 
         pub fn uri(&self) -> &http::Uri {
-            self.0.uri()
+            self.req.uri()
         }
 
         pub fn status(&self) -> Option<http::StatusCode> {
-            self.1.ok()
+            self.res.as_ref().ok().map(|&(status, _)| status)
+        }
+
+        /// The headers of the response, if one was received.
+        pub fn headers(&self) -> Option<&http::HeaderMap> {
+            self.res.as_ref().ok().map(|&(_, headers)| headers)
         }
 
         pub fn error(&self) -> Option<&(dyn std::error::Error + 'static)> {
-            self.1.as_ref().err().map(|e| &**e as _)
+            self.res.as_ref().err().map(|e| &**e as _)
+        }
+
+        /// Which attempt this is, starting at `1` for the original request.
+        pub fn attempt(&self) -> u32 {
+            self.attempt
+        }
+
+        /// The time elapsed since the original request was sent.
+        pub fn elapsed(&self) -> std::time::Duration {
+            self.elapsed
         }
 
         pub fn retryable(self) -> Action {
@@ -422,7 +626,7 @@ mod classify {
         }
 
         fn is_protocol_nack(&self) -> bool {
-            self.1
+            self.res
                 .as_ref()
                 .err()
                 .map(|&e| super::is_retryable_error(e))
@@ -441,6 +645,10 @@ mod classify {
     pub(super) enum Classifier {
         Never,
         ProtocolNacks,
+        Statuses {
+            statuses: std::sync::Arc<[http::StatusCode]>,
+            any_method: bool,
+        },
         Dyn(std::sync::Arc<dyn Classify>),
     }
 
@@ -449,8 +657,15 @@ mod classify {
             &self,
             req: &super::Req,
             res: &Result<http::Response<B>, crate::Error>,
+            attempt: u32,
+            elapsed: std::time::Duration,
         ) -> Action {
-            let req_rep = ReqRep(req, res.as_ref().map(|r| r.status()));
+            let req_rep = ReqRep {
+                req,
+                res: res.as_ref().map(|r| (r.status(), r.headers())),
+                attempt,
+                elapsed,
+            };
             match self {
                 Self::Never => Action::Success,
                 Self::ProtocolNacks => {
@@ -460,6 +675,20 @@ mod classify {
                         Action::Success
                     }
                 }
+                Self::Statuses {
+                    statuses,
+                    any_method,
+                } => {
+                    let status_retryable = req_rep
+                        .status()
+                        .map_or(false, |status| statuses.contains(&status))
+                        && (*any_method || req_rep.method().is_idempotent());
+                    if status_retryable || req_rep.is_protocol_nack() {
+                        Action::Retryable
+                    } else {
+                        Action::Success
+                    }
+                }
                 Self::Dyn(c) => c.classify(req_rep),
             }
         }
@@ -470,6 +699,9 @@ mod classify {
             match self {
                 Self::Never => f.write_str("Never"),
                 Self::ProtocolNacks => f.write_str("ProtocolNacks"),
+                Self::Statuses { statuses, .. } => {
+                    f.debug_tuple("Statuses").field(statuses).finish()
+                }
                 Self::Dyn(_) => f.write_str("Classifier"),
             }
         }
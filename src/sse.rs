@@ -0,0 +1,232 @@
+//! Server-Sent Events
+//!
+//! A response in the [`text/event-stream`] format can be read as a stream of
+//! [`Event`]s with [`Response::sse`](crate::Response::sse).
+//!
+//! [`text/event-stream`]: https://html.spec.whatwg.org/multipage/server-sent-events.html#event-stream-interpretation
+
+use std::fmt;
+use std::time::Duration;
+
+use bytes::{Buf, BytesMut};
+
+/// An event received from a `text/event-stream` response.
+#[derive(Clone, PartialEq, Eq)]
+pub struct Event {
+    id: Option<String>,
+    event: String,
+    data: String,
+    retry: Option<Duration>,
+}
+
+impl Event {
+    /// The last event ID set by the stream, if any.
+    ///
+    /// As in browsers, an ID applies to every event after the one setting it,
+    /// until another one is set.
+    pub fn id(&self) -> Option<&str> {
+        self.id.as_deref()
+    }
+
+    /// The type of the event, `"message"` unless the stream set another.
+    pub fn event(&self) -> &str {
+        &self.event
+    }
+
+    /// The data of the event, with the lines of multi-line data joined by
+    /// `\n`.
+    pub fn data(&self) -> &str {
+        &self.data
+    }
+
+    /// The reconnection time the stream asked for with this event, if any.
+    pub fn retry(&self) -> Option<Duration> {
+        self.retry
+    }
+}
+
+impl fmt::Debug for Event {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Event")
+            .field("id", &self.id)
+            .field("event", &self.event)
+            .field("data", &self.data)
+            .field("retry", &self.retry)
+            .finish()
+    }
+}
+
+/// Incremental parser for the `text/event-stream` format.
+#[derive(Default)]
+pub(crate) struct Parser {
+    buf: BytesMut,
+    started: bool,
+    last_id: Option<String>,
+    event: Option<String>,
+    data: String,
+    has_data: bool,
+    retry: Option<Duration>,
+}
+
+impl Parser {
+    pub(crate) fn feed(&mut self, chunk: &[u8]) {
+        self.buf.extend_from_slice(chunk);
+    }
+
+    /// Returns the next complete event in what was fed so far.
+    ///
+    /// With `eof`, a last line without a line ending is processed too. An
+    /// event that isn't terminated by a blank line is discarded, as the spec
+    /// requires.
+    pub(crate) fn next_event(&mut self, eof: bool) -> Option<Event> {
+        const BOM: &[u8] = b"\xEF\xBB\xBF";
+        if !self.started {
+            if !eof && self.buf.len() < BOM.len() && BOM.starts_with(&self.buf) {
+                return None;
+            }
+            self.started = true;
+            if self.buf.starts_with(BOM) {
+                self.buf.advance(BOM.len());
+            }
+        }
+
+        while let Some(line) = self.next_line(eof) {
+            if line.is_empty() {
+                if let Some(event) = self.dispatch() {
+                    return Some(event);
+                }
+                continue;
+            }
+            let line = String::from_utf8_lossy(&line);
+            let (field, value) = match line.find(':') {
+                Some(0) => continue,
+                Some(i) => {
+                    let value = &line[i + 1..];
+                    (&line[..i], value.strip_prefix(' ').unwrap_or(value))
+                }
+                None => (&line[..], ""),
+            };
+            self.process_field(field, value);
+        }
+        None
+    }
+
+    fn next_line(&mut self, eof: bool) -> Option<BytesMut> {
+        let end = self.buf.iter().position(|&b| b == b'\n' || b == b'\r');
+        match end {
+            // a `\r` at the end of what was fed may be the start of `\r\n`
+            Some(i) if self.buf[i] == b'\r' && i + 1 == self.buf.len() && !eof => None,
+            Some(i) => {
+                let line = self.buf.split_to(i);
+                let newline = if self.buf.starts_with(b"\r\n") { 2 } else { 1 };
+                self.buf.advance(newline);
+                Some(line)
+            }
+            None if eof && !self.buf.is_empty() => Some(self.buf.split()),
+            None => None,
+        }
+    }
+
+    fn process_field(&mut self, field: &str, value: &str) {
+        match field {
+            "event" => self.event = Some(value.to_owned()),
+            "data" => {
+                if self.has_data {
+                    self.data.push('\n');
+                }
+                self.data.push_str(value);
+                self.has_data = true;
+            }
+            "id" if !value.contains('\0') => {
+                self.last_id = Some(value.to_owned()).filter(|id| !id.is_empty());
+            }
+            "retry" if !value.is_empty() && value.bytes().all(|b| b.is_ascii_digit()) => {
+                if let Ok(millis) = value.parse() {
+                    self.retry = Some(Duration::from_millis(millis));
+                }
+            }
+            _ => (),
+        }
+    }
+
+    fn dispatch(&mut self) -> Option<Event> {
+        let event = self.event.take();
+        let retry = self.retry.take();
+        if !std::mem::take(&mut self.has_data) {
+            return None;
+        }
+        Some(Event {
+            id: self.last_id.clone(),
+            event: event
+                .filter(|event| !event.is_empty())
+                .unwrap_or_else(|| "message".to_owned()),
+            data: std::mem::take(&mut self.data),
+            retry,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_chunks(chunks: &[&str]) -> Vec<Event> {
+        let mut parser = Parser::default();
+        let mut events = Vec::new();
+        for chunk in chunks {
+            parser.feed(chunk.as_bytes());
+            events.extend(std::iter::from_fn(|| parser.next_event(false)));
+        }
+        events.extend(std::iter::from_fn(|| parser.next_event(true)));
+        events
+    }
+
+    #[test]
+    fn parses_events_across_chunks() {
+        let stream = "\u{feff}: a comment\r\n\
+            data: first\r\n\
+            data:  line\r\n\
+            \r\n\
+            event: update\n\
+            id: 7\n\
+            retry: 1500\n\
+            data: {\"x\":1}\n\
+            \n\
+            data: third\r\r\
+            id\n\
+            data\n\
+            \n\
+            data: cut off at the end";
+
+        let whole = parse_chunks(&[stream]);
+        assert_eq!(whole.len(), 4);
+
+        assert_eq!(whole[0].event(), "message");
+        assert_eq!(whole[0].data(), "first\n line");
+        assert_eq!(whole[0].id(), None);
+
+        assert_eq!(whole[1].event(), "update");
+        assert_eq!(whole[1].data(), "{\"x\":1}");
+        assert_eq!(whole[1].id(), Some("7"));
+        assert_eq!(whole[1].retry(), Some(Duration::from_millis(1500)));
+
+        assert_eq!(whole[2].data(), "third");
+        assert_eq!(whole[2].id(), Some("7"));
+        assert_eq!(whole[2].retry(), None);
+
+        assert_eq!(whole[3].data(), "");
+        assert_eq!(whole[3].id(), None);
+
+        // every split gives the same events, including between `\r` and `\n`
+        for i in 1..stream.len() {
+            if !stream.is_char_boundary(i) {
+                continue;
+            }
+            let (a, b) = stream.split_at(i);
+            assert_eq!(parse_chunks(&[a, b]), whole, "split at {i}");
+        }
+        let bytes = stream.chars().map(String::from).collect::<Vec<_>>();
+        let bytes = bytes.iter().map(String::as_str).collect::<Vec<_>>();
+        assert_eq!(parse_chunks(&bytes), whole);
+    }
+}
@@ -0,0 +1,188 @@
+//! Utilities for testing code using a `Client`
+//!
+//! # Optional
+//!
+//! This requires the optional `test-util` feature to be enabled.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Mutex;
+
+use bytes::Bytes;
+use http::{HeaderMap, Method, StatusCode};
+
+use crate::response::ResponseUrl;
+use crate::transport::Transport;
+use crate::{IntoUrl, Request, Response, Url};
+
+/// A [`Transport`] answering requests with canned responses.
+///
+/// Responses are registered per URL with a [`MockTransportBuilder`], and
+/// requests to any other URL are answered with `404 Not Found`. The
+/// transport also records the requests it's sent, for checking afterwards.
+///
+/// # Example
+///
+/// ```
+/// use std::sync::Arc;
+/// use reqwest::test_util::MockTransport;
+///
+/// # async fn run() -> Result<(), reqwest::Error> {
+/// let mock = Arc::new(
+///     MockTransport::builder()
+///         .redirect("http://example.com/old", "/new")
+///         .respond("http://example.com/new", 200, "hello")
+///         .build(),
+/// );
+/// let client = reqwest::Client::builder()
+///     .transport(mock.clone())
+///     .build()?;
+///
+/// let res = client.get("http://example.com/old").send().await?;
+/// assert_eq!(res.url().as_str(), "http://example.com/new");
+/// assert_eq!(res.text().await?, "hello");
+/// assert_eq!(mock.requests().len(), 2);
+/// # Ok(())
+/// # }
+/// ```
+pub struct MockTransport {
+    routes: HashMap<Url, MockResponse>,
+    requests: Mutex<Vec<(Method, Url)>>,
+}
+
+/// A builder for a [`MockTransport`].
+#[must_use]
+pub struct MockTransportBuilder {
+    routes: HashMap<Url, MockResponse>,
+}
+
+#[derive(Clone)]
+struct MockResponse {
+    status: StatusCode,
+    headers: HeaderMap,
+    body: Bytes,
+}
+
+impl MockTransport {
+    /// Constructs a new `MockTransportBuilder`.
+    pub fn builder() -> MockTransportBuilder {
+        MockTransportBuilder {
+            routes: HashMap::new(),
+        }
+    }
+
+    /// The method and URL of every request sent so far, in order.
+    pub fn requests(&self) -> Vec<(Method, Url)> {
+        self.requests.lock().unwrap().clone()
+    }
+}
+
+impl Transport for MockTransport {
+    fn send(
+        &self,
+        req: Request,
+    ) -> Pin<Box<dyn Future<Output = crate::Result<Response>> + Send + '_>> {
+        self.requests
+            .lock()
+            .unwrap()
+            .push((req.method().clone(), req.url().clone()));
+
+        let mock = self.routes.get(req.url()).cloned().unwrap_or(MockResponse {
+            status: StatusCode::NOT_FOUND,
+            headers: HeaderMap::new(),
+            body: Bytes::new(),
+        });
+        let mut res = http::Response::new(mock.body);
+        *res.status_mut() = mock.status;
+        *res.headers_mut() = mock.headers;
+        res.extensions_mut().insert(ResponseUrl(req.url().clone()));
+        Box::pin(async move { Ok(Response::from(res)) })
+    }
+}
+
+impl fmt::Debug for MockTransport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("MockTransport")
+            .field("routes", &self.routes.keys().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+impl MockTransportBuilder {
+    /// Answers requests for `url` with the given status and body.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `url` or `status` is invalid.
+    pub fn respond<U, B>(self, url: U, status: u16, body: B) -> MockTransportBuilder
+    where
+        U: IntoUrl,
+        B: Into<Bytes>,
+    {
+        self.respond_with(url, status, HeaderMap::new(), body)
+    }
+
+    /// Answers requests for `url` with the given status, headers, and body.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `url` or `status` is invalid.
+    pub fn respond_with<U, B>(
+        mut self,
+        url: U,
+        status: u16,
+        headers: HeaderMap,
+        body: B,
+    ) -> MockTransportBuilder
+    where
+        U: IntoUrl,
+        B: Into<Bytes>,
+    {
+        let url = url.into_url().expect("invalid mock URL");
+        let status = StatusCode::from_u16(status).expect("invalid mock status");
+        self.routes.insert(
+            url,
+            MockResponse {
+                status,
+                headers,
+                body: body.into(),
+            },
+        );
+        self
+    }
+
+    /// Answers requests for `url` with a `302 Found` to `location`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `url` or `location` is invalid.
+    pub fn redirect<U>(self, url: U, location: &str) -> MockTransportBuilder
+    where
+        U: IntoUrl,
+    {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            http::header::LOCATION,
+            location.parse().expect("invalid mock location"),
+        );
+        self.respond_with(url, 302, headers, Bytes::new())
+    }
+
+    /// Returns a `MockTransport` with the registered responses.
+    pub fn build(self) -> MockTransport {
+        MockTransport {
+            routes: self.routes,
+            requests: Mutex::new(Vec::new()),
+        }
+    }
+}
+
+impl fmt::Debug for MockTransportBuilder {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("MockTransportBuilder")
+            .field("routes", &self.routes.keys().collect::<Vec<_>>())
+            .finish()
+    }
+}
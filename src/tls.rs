@@ -61,6 +61,7 @@ use std::{
 
 /// Represents a X509 certificate revocation list.
 #[cfg(feature = "__rustls")]
+#[derive(Clone)]
 pub struct CertificateRevocationList {
     #[cfg(feature = "__rustls")]
     inner: rustls_pki_types::CertificateRevocationListDer<'static>,
@@ -553,6 +554,7 @@ impl Version {
     }
 }
 
+#[derive(Clone)]
 pub(crate) enum TlsBackend {
     // This is the default and HTTP/3 feature does not use it so suppress it.
     #[allow(dead_code)]
@@ -727,18 +729,51 @@ impl ServerCertVerifier for IgnoreHostname {
 #[derive(Clone)]
 pub struct TlsInfo {
     pub(crate) peer_certificate: Option<Vec<u8>>,
+    pub(crate) protocol_version: Option<&'static str>,
+    pub(crate) cipher_suite: Option<&'static str>,
 }
 
 impl TlsInfo {
+    #[cfg(feature = "__rustls")]
+    pub(crate) fn from_rustls(conn: &rustls::ClientConnection) -> TlsInfo {
+        TlsInfo {
+            peer_certificate: conn
+                .peer_certificates()
+                .and_then(|certs| certs.first())
+                .map(|c| c.to_vec()),
+            protocol_version: conn.protocol_version().and_then(|v| v.as_str()),
+            cipher_suite: conn
+                .negotiated_cipher_suite()
+                .and_then(|suite| suite.suite().as_str()),
+        }
+    }
+
     /// Get the DER encoded leaf certificate of the peer.
     pub fn peer_certificate(&self) -> Option<&[u8]> {
         self.peer_certificate.as_ref().map(|der| &der[..])
     }
+
+    /// Get the negotiated protocol version, such as `"TLSv1_3"`.
+    ///
+    /// This is only known when using a `rustls` backend.
+    pub fn protocol_version(&self) -> Option<&str> {
+        self.protocol_version
+    }
+
+    /// Get the negotiated cipher suite, such as `"TLS13_AES_128_GCM_SHA256"`.
+    ///
+    /// This is only known when using a `rustls` backend.
+    pub fn cipher_suite(&self) -> Option<&str> {
+        self.cipher_suite
+    }
 }
 
 impl std::fmt::Debug for TlsInfo {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        f.debug_struct("TlsInfo").finish()
+        f.debug_struct("TlsInfo")
+            .field("protocol_version", &self.protocol_version)
+            .field("cipher_suite", &self.cipher_suite)
+            .finish()
     }
 }
 
@@ -0,0 +1,62 @@
+//! Pluggable transports
+//!
+//! A [`Transport`] installed with
+//! [`ClientBuilder::transport`](crate::ClientBuilder::transport) replaces the
+//! network I/O of a `Client`. It's given every request the client sends,
+//! after the default headers and cookies are added, and returns the response.
+//! Everything around it still applies: redirects are followed by sending
+//! another request through the transport, retries by sending it again, and
+//! timeouts and decompression work as usual.
+//!
+//! This makes a `Client` testable without network access. See
+//! [`MockTransport`](../test_util/struct.MockTransport.html) for a ready-made
+//! one.
+//!
+//! # Example
+//!
+//! ```
+//! use std::future::Future;
+//! use std::pin::Pin;
+//! use std::sync::Arc;
+//! use reqwest::transport::Transport;
+//!
+//! struct Teapot;
+//!
+//! impl Transport for Teapot {
+//!     fn send(
+//!         &self,
+//!         req: reqwest::Request,
+//!     ) -> Pin<Box<dyn Future<Output = reqwest::Result<reqwest::Response>> + Send + '_>> {
+//!         let res = http::Response::builder()
+//!             .status(418)
+//!             .body(format!("no coffee at {}", req.url()))
+//!             .unwrap();
+//!         Box::pin(async move { Ok(res.into()) })
+//!     }
+//! }
+//!
+//! # fn run() -> Result<(), reqwest::Error> {
+//! let client = reqwest::Client::builder()
+//!     .transport(Arc::new(Teapot))
+//!     .build()?;
+//! # Ok(())
+//! # }
+//! ```
+
+use std::future::Future;
+use std::pin::Pin;
+
+use crate::{Request, Response};
+
+/// Sends requests in place of the network.
+pub trait Transport: Send + Sync {
+    /// Sends a request, returning its response.
+    ///
+    /// The request's body, if any, is the one given to the client, and the
+    /// response's body is decoded according to its `Content-Encoding`, as
+    /// one from the network would be.
+    fn send(
+        &self,
+        req: Request,
+    ) -> Pin<Box<dyn Future<Output = crate::Result<Response>> + Send + '_>>;
+}
@@ -88,6 +88,173 @@ pub(crate) fn add_cookie_header(
     }
 }
 
+/// Parses a `Retry-After` header into how long to wait from `now`.
+///
+/// Both the delta-seconds and the HTTP-date forms are supported. An
+/// HTTP-date is measured against the response's `Date` header if present,
+/// so that clock skew between client and server doesn't matter, and
+/// otherwise against `now`. Dates in the past produce a zero duration.
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) fn retry_after(
+    headers: &HeaderMap,
+    now: std::time::SystemTime,
+) -> Option<std::time::Duration> {
+    use std::time::Duration;
+
+    let value = headers
+        .get(crate::header::RETRY_AFTER)?
+        .to_str()
+        .ok()?
+        .trim();
+
+    if !value.is_empty() && value.bytes().all(|b| b.is_ascii_digit()) {
+        return value.parse().ok().map(Duration::from_secs);
+    }
+
+    let retry_at = httpdate::parse_http_date(value).ok()?;
+    let since = headers
+        .get(crate::header::DATE)
+        .and_then(|date| date.to_str().ok())
+        .and_then(|date| httpdate::parse_http_date(date).ok())
+        .unwrap_or(now);
+
+    Some(retry_at.duration_since(since).unwrap_or(Duration::ZERO))
+}
+
+/// Expands `{name}` placeholders in a URL template with the matching
+/// parameters, percent-encoding each value like an RFC 6570 simple string
+/// expansion, so that only unreserved characters are left as is.
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) fn expand_url_template(
+    template: &str,
+    params: &std::collections::HashMap<&str, &str>,
+) -> crate::Result<String> {
+    use percent_encoding::{utf8_percent_encode, AsciiSet, NON_ALPHANUMERIC};
+
+    const UNRESERVED: &AsciiSet = &NON_ALPHANUMERIC
+        .remove(b'-')
+        .remove(b'.')
+        .remove(b'_')
+        .remove(b'~');
+
+    let mut expanded = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        expanded.push_str(&rest[..start]);
+        let len = rest[start..]
+            .find('}')
+            .ok_or_else(|| crate::error::builder("unclosed `{` in URL template"))?;
+        let name = &rest[start + 1..start + len];
+        let value = params.get(name).ok_or_else(|| {
+            crate::error::builder(format!("missing URL template variable `{name}`"))
+        })?;
+        expanded.extend(utf8_percent_encode(value, UNRESERVED));
+        rest = &rest[start + len + 1..];
+    }
+    expanded.push_str(rest);
+    Ok(expanded)
+}
+
+/// Appends the default query parameters whose names aren't already in the
+/// URL's query.
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) fn merge_default_query(url: &mut url::Url, defaults: &[(String, String)]) {
+    let missing = defaults
+        .iter()
+        .filter(|(key, _)| !url.query_pairs().any(|(k, _)| k == key.as_str()))
+        .collect::<Vec<_>>();
+    if !missing.is_empty() {
+        url.query_pairs_mut().extend_pairs(missing);
+    }
+}
+
+/// Returns true if the URL may not be requested because it isn't `https`,
+/// while only `https` is allowed except to the `http_allowed_hosts`.
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) fn is_insecure_disallowed(
+    url: &url::Url,
+    https_only: bool,
+    http_allowed_hosts: &[String],
+) -> bool {
+    if !https_only || url.scheme() == "https" {
+        return false;
+    }
+    let host = match url.host_str() {
+        Some(host) => host.trim_start_matches('[').trim_end_matches(']'),
+        None => return true,
+    };
+    !http_allowed_hosts.iter().any(|allowed| {
+        allowed
+            .trim_start_matches('[')
+            .trim_end_matches(']')
+            .eq_ignore_ascii_case(host)
+    })
+}
+
+/// Headers whose values are left out of `Debug` output, in addition to values
+/// marked as sensitive.
+#[cfg(not(target_arch = "wasm32"))]
+const SENSITIVE_HEADERS: &[crate::header::HeaderName] = &[
+    crate::header::AUTHORIZATION,
+    crate::header::PROXY_AUTHORIZATION,
+    crate::header::COOKIE,
+    crate::header::SET_COOKIE,
+];
+
+/// Marks the values of the `names` headers as sensitive.
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) fn mark_sensitive(headers: &mut HeaderMap, names: &[crate::header::HeaderName]) {
+    for name in names {
+        if let crate::header::Entry::Occupied(mut entry) = headers.entry(name) {
+            for value in entry.iter_mut() {
+                value.set_sensitive(true);
+            }
+        }
+    }
+}
+
+/// Formats a `HeaderMap` like its `Debug` impl, but with the values of
+/// sensitive headers replaced by `Sensitive`.
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) struct RedactedHeaders<'a>(pub(crate) &'a HeaderMap);
+
+#[cfg(not(target_arch = "wasm32"))]
+impl fmt::Debug for RedactedHeaders<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        struct Sensitive;
+
+        impl fmt::Debug for Sensitive {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("Sensitive")
+            }
+        }
+
+        f.debug_map()
+            .entries(self.0.iter().map(|(name, value)| {
+                let value: &dyn fmt::Debug =
+                    if value.is_sensitive() || SENSITIVE_HEADERS.contains(name) {
+                        &Sensitive
+                    } else {
+                        value
+                    };
+                (name, value)
+            }))
+            .finish()
+    }
+}
+
+/// Returns true if there are several `Content-Length` headers that don't
+/// agree. Such a response could be framed in more than one way, so none of
+/// them can be trusted.
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) fn has_conflicting_content_length(headers: &HeaderMap) -> bool {
+    let mut values = headers.get_all(crate::header::CONTENT_LENGTH).iter();
+    match values.next() {
+        Some(first) => values.any(|value| value != first),
+        None => false,
+    }
+}
+
 pub(crate) struct Escape<'a>(&'a [u8]);
 
 #[cfg(not(target_arch = "wasm32"))]
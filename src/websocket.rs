@@ -0,0 +1,384 @@
+//! WebSocket
+//!
+//! A request built with [`RequestBuilder::upgrade`](crate::RequestBuilder::upgrade)
+//! asks the server to switch to the [WebSocket] protocol, and
+//! [`Response::into_websocket`](crate::Response::into_websocket) checks the
+//! server agreed and returns a [`WebSocket`] to exchange messages over.
+//!
+//! ```no_run
+//! # async fn run() -> Result<(), reqwest::Error> {
+//! use reqwest::websocket::Message;
+//!
+//! let mut ws = reqwest::Client::new()
+//!     .get("http://echo.example/ws")
+//!     .upgrade()
+//!     .send()
+//!     .await?
+//!     .into_websocket()
+//!     .await?;
+//!
+//! ws.send(Message::Text("hello".into())).await?;
+//! while let Some(message) = ws.recv().await {
+//!     println!("{:?}", message?);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [WebSocket]: https://datatracker.ietf.org/doc/html/rfc6455
+
+use std::fmt;
+
+use base64::prelude::BASE64_STANDARD;
+use base64::Engine;
+use bytes::Bytes;
+use http::header::{CONNECTION, SEC_WEBSOCKET_ACCEPT, SEC_WEBSOCKET_KEY, SEC_WEBSOCKET_VERSION, UPGRADE};
+use http::{HeaderMap, HeaderValue, StatusCode};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+use crate::async_impl::Upgraded;
+use crate::Response;
+
+const GUID: &str = "258EAFA5-E914-47DA-95CA-C5AB0DC85B11";
+const MAX_MESSAGE_SIZE: usize = 64 << 20;
+
+const OP_CONTINUATION: u8 = 0x0;
+const OP_TEXT: u8 = 0x1;
+const OP_BINARY: u8 = 0x2;
+const OP_CLOSE: u8 = 0x8;
+const OP_PING: u8 = 0x9;
+const OP_PONG: u8 = 0xA;
+
+/// A message sent or received over a [`WebSocket`].
+#[derive(Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Message {
+    /// A UTF-8 text message.
+    Text(String),
+    /// A binary message.
+    Binary(Bytes),
+}
+
+impl fmt::Debug for Message {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Message::Text(text) => f.debug_tuple("Text").field(text).finish(),
+            Message::Binary(data) => f.debug_tuple("Binary").field(data).finish(),
+        }
+    }
+}
+
+/// A WebSocket connection, from the client's end.
+///
+/// Pings from the server are answered automatically, and a close from the
+/// server is echoed and ends [`recv`](WebSocket::recv).
+pub struct WebSocket {
+    io: Upgraded,
+    closed: bool,
+    // whether a Close frame was sent, which may happen only once
+    close_sent: bool,
+}
+
+impl WebSocket {
+    /// Sends a message.
+    pub async fn send(&mut self, message: Message) -> crate::Result<()> {
+        if self.closed {
+            return Err(crate::error::body("websocket is closed"));
+        }
+        match message {
+            Message::Text(text) => self.write_frame(OP_TEXT, text.as_bytes()).await,
+            Message::Binary(data) => self.write_frame(OP_BINARY, &data).await,
+        }
+    }
+
+    /// Receives the next message.
+    ///
+    /// Returns `None` once the connection is closed.
+    pub async fn recv(&mut self) -> Option<crate::Result<Message>> {
+        if self.closed {
+            return None;
+        }
+        match self.read_message().await {
+            Ok(Some(message)) => Some(Ok(message)),
+            Ok(None) => {
+                self.closed = true;
+                None
+            }
+            Err(e) => {
+                self.closed = true;
+                Some(Err(e))
+            }
+        }
+    }
+
+    /// Closes the connection, waiting for the server to acknowledge it.
+    ///
+    /// Messages the server sent in the meantime are discarded.
+    pub async fn close(mut self) -> crate::Result<()> {
+        if !self.closed {
+            // status code 1000, a normal closure
+            self.write_frame(OP_CLOSE, &1000u16.to_be_bytes()).await?;
+            self.close_sent = true;
+            while let Some(message) = self.recv().await {
+                message?;
+            }
+        }
+        self.io.shutdown().await.map_err(crate::error::body)
+    }
+
+    async fn read_message(&mut self) -> crate::Result<Option<Message>> {
+        let mut message: Option<(u8, Vec<u8>)> = None;
+        loop {
+            let frame = match self.read_frame().await? {
+                Some(frame) => frame,
+                None if message.is_none() => return Ok(None),
+                None => return Err(protocol("connection closed in the middle of a message")),
+            };
+            match frame.opcode {
+                OP_CLOSE => {
+                    // if the server started the close, echo the status code
+                    // back, if there was one
+                    if !self.close_sent {
+                        let code = frame.payload.get(..2).unwrap_or_default();
+                        self.write_frame(OP_CLOSE, code).await?;
+                        self.close_sent = true;
+                    }
+                    return Ok(None);
+                }
+                OP_PING => {
+                    self.write_frame(OP_PONG, &frame.payload).await?;
+                    continue;
+                }
+                OP_PONG => continue,
+                OP_TEXT | OP_BINARY if message.is_none() => {
+                    message = Some((frame.opcode, Vec::new()));
+                }
+                OP_CONTINUATION if message.is_some() => (),
+                OP_TEXT | OP_BINARY | OP_CONTINUATION => {
+                    return Err(protocol("unexpected frame in a fragmented message"));
+                }
+                _ => return Err(protocol("unknown opcode")),
+            }
+
+            let (opcode, data) = message.as_mut().expect("a data frame started a message");
+            if data.len() + frame.payload.len() > MAX_MESSAGE_SIZE {
+                return Err(protocol("message too large"));
+            }
+            data.extend_from_slice(&frame.payload);
+            if !frame.fin {
+                continue;
+            }
+
+            let (opcode, data) = (*opcode, std::mem::take(data));
+            return if opcode == OP_TEXT {
+                String::from_utf8(data)
+                    .map(|text| Some(Message::Text(text)))
+                    .map_err(crate::error::decode)
+            } else {
+                Ok(Some(Message::Binary(data.into())))
+            };
+        }
+    }
+
+    async fn read_frame(&mut self) -> crate::Result<Option<Frame>> {
+        let mut head = [0; 2];
+        match self.io.read(&mut head[..1]).await.map_err(crate::error::body)? {
+            0 => return Ok(None),
+            _ => self.read_exact(&mut head[1..]).await?,
+        }
+
+        if head[0] & 0x70 != 0 {
+            return Err(protocol("reserved bits set without an extension"));
+        }
+        if head[1] & 0x80 != 0 {
+            return Err(protocol("masked frame from the server"));
+        }
+        let fin = head[0] & 0x80 != 0;
+        let opcode = head[0] & 0x0F;
+
+        let len = match head[1] & 0x7F {
+            126 => {
+                let mut len = [0; 2];
+                self.read_exact(&mut len).await?;
+                u64::from(u16::from_be_bytes(len))
+            }
+            127 => {
+                let mut len = [0; 8];
+                self.read_exact(&mut len).await?;
+                u64::from_be_bytes(len)
+            }
+            len => u64::from(len),
+        };
+        if opcode & 0x8 != 0 && (!fin || len > 125) {
+            return Err(protocol("invalid control frame"));
+        }
+        if len > MAX_MESSAGE_SIZE as u64 {
+            return Err(protocol("message too large"));
+        }
+
+        let mut payload = vec![0; len as usize];
+        self.read_exact(&mut payload).await?;
+        Ok(Some(Frame {
+            fin,
+            opcode,
+            payload,
+        }))
+    }
+
+    async fn read_exact(&mut self, buf: &mut [u8]) -> crate::Result<()> {
+        self.io
+            .read_exact(buf)
+            .await
+            .map(drop)
+            .map_err(crate::error::body)
+    }
+
+    async fn write_frame(&mut self, opcode: u8, payload: &[u8]) -> crate::Result<()> {
+        let mut frame = Vec::with_capacity(payload.len() + 14);
+        frame.push(0x80 | opcode);
+        // frames from a client are always masked
+        match payload.len() {
+            len @ 0..=125 => frame.push(0x80 | len as u8),
+            len @ 126..=0xFFFF => {
+                frame.push(0x80 | 126);
+                frame.extend_from_slice(&(len as u16).to_be_bytes());
+            }
+            len => {
+                frame.push(0x80 | 127);
+                frame.extend_from_slice(&(len as u64).to_be_bytes());
+            }
+        }
+        // RFC 6455 asks for masks a proxy can't predict
+        let mut mask = [0; 4];
+        getrandom::fill(&mut mask).map_err(crate::error::body)?;
+        frame.extend_from_slice(&mask);
+        frame.extend(payload.iter().zip(mask.iter().cycle()).map(|(b, m)| b ^ m));
+
+        self.io.write_all(&frame).await.map_err(crate::error::body)?;
+        self.io.flush().await.map_err(crate::error::body)
+    }
+}
+
+impl fmt::Debug for WebSocket {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("WebSocket")
+            .field("closed", &self.closed)
+            .finish()
+    }
+}
+
+struct Frame {
+    fin: bool,
+    opcode: u8,
+    payload: Vec<u8>,
+}
+
+fn protocol(msg: &'static str) -> crate::Error {
+    crate::error::decode(msg)
+}
+
+impl Response {
+    /// Checks the server accepted a WebSocket upgrade, and returns the
+    /// [`WebSocket`] connection.
+    ///
+    /// The request must have been built with
+    /// [`RequestBuilder::upgrade`](crate::RequestBuilder::upgrade).
+    ///
+    /// # Errors
+    ///
+    /// Fails if the server didn't switch protocols, or its
+    /// `Sec-WebSocket-Accept` header doesn't match the request's key.
+    ///
+    /// # Optional
+    ///
+    /// This requires the optional `websocket` feature to be enabled.
+    #[cfg_attr(docsrs, doc(cfg(feature = "websocket")))]
+    pub async fn into_websocket(self) -> crate::Result<WebSocket> {
+        if let Err(msg) = check_handshake(&self) {
+            return Err(crate::error::upgrade(msg).with_url(self.url().clone()));
+        }
+        let io = self.upgrade().await?;
+        Ok(WebSocket {
+            io,
+            closed: false,
+            close_sent: false,
+        })
+    }
+}
+
+fn check_handshake(res: &Response) -> Result<(), &'static str> {
+    let key = match res.extensions().get::<Key>() {
+        Some(Key(key)) => key,
+        None => return Err("request wasn't a websocket upgrade"),
+    };
+    if res.status() != StatusCode::SWITCHING_PROTOCOLS {
+        return Err("server didn't switch protocols");
+    }
+    let headers = res.headers();
+    let has_token = |name, token: &str| {
+        headers.get_all(name).iter().any(|value| {
+            value
+                .to_str()
+                .map(|value| value.split(',').any(|t| t.trim().eq_ignore_ascii_case(token)))
+                .unwrap_or(false)
+        })
+    };
+    if !has_token(UPGRADE, "websocket") || !has_token(CONNECTION, "upgrade") {
+        return Err("server didn't upgrade to websocket");
+    }
+    match headers.get(SEC_WEBSOCKET_ACCEPT) {
+        Some(accept) if accept.as_bytes() == accept_key(key.as_bytes()).as_bytes() => Ok(()),
+        _ => Err("invalid Sec-WebSocket-Accept header"),
+    }
+}
+
+/// The `Sec-WebSocket-Key` a request was sent with, kept in the extensions of
+/// its response.
+#[derive(Clone)]
+pub(crate) struct Key(HeaderValue);
+
+/// Remembers the key of a WebSocket upgrade request in its response.
+pub(crate) fn remember_key<B>(
+    mut res: http::Response<B>,
+    req_headers: &HeaderMap,
+) -> http::Response<B> {
+    if let Some(key) = req_headers.get(SEC_WEBSOCKET_KEY) {
+        res.extensions_mut().insert(Key(key.clone()));
+    }
+    res
+}
+
+/// Sets the headers asking for a WebSocket upgrade, with a fresh key.
+pub(crate) fn handshake_headers(headers: &mut HeaderMap) -> crate::Result<()> {
+    let mut key = [0; 16];
+    getrandom::fill(&mut key).map_err(crate::error::builder)?;
+    let key = BASE64_STANDARD.encode(key);
+
+    headers.insert(CONNECTION, HeaderValue::from_static("upgrade"));
+    headers.insert(UPGRADE, HeaderValue::from_static("websocket"));
+    headers.insert(SEC_WEBSOCKET_VERSION, HeaderValue::from_static("13"));
+    headers.insert(
+        SEC_WEBSOCKET_KEY,
+        HeaderValue::from_str(&key).expect("base64 is always valid HeaderValue"),
+    );
+    Ok(())
+}
+
+fn accept_key(key: &[u8]) -> String {
+    let mut input = key.to_vec();
+    input.extend_from_slice(GUID.as_bytes());
+    BASE64_STANDARD.encode(sha1_smol::Sha1::from(input).digest().bytes())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn accept_key_matches_rfc_example() {
+        assert_eq!(
+            accept_key(b"dGhlIHNhbXBsZSBub25jZQ=="),
+            "s3pPLMBiTxaQ9kYGzzhZRbK+xOo="
+        );
+    }
+}
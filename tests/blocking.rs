@@ -411,3 +411,18 @@ fn test_response_no_tls_info_for_http() {
     let body = res.text().unwrap();
     assert_eq!(b"Hello", body.as_bytes());
 }
+
+#[test]
+fn base_url_joins_relative_urls() {
+    let server = server::http(move |req| async move {
+        assert_eq!(req.uri(), "/v1/users");
+        http::Response::default()
+    });
+
+    let client = reqwest::blocking::Client::builder()
+        .base_url(format!("http://{}", server.addr()))
+        .build()
+        .unwrap();
+    let res = client.get("/v1/users").send().unwrap();
+    assert_eq!(res.status(), 200);
+}
@@ -355,3 +355,29 @@ async fn test_chunked_fragmented_response_with_extra_bytes() {
     assert!(err.is_decode());
     assert!(start.elapsed() >= DELAY_BETWEEN_RESPONSE_PARTS - DELAY_MARGIN);
 }
+
+#[tokio::test]
+async fn brotli_request_body() {
+    use http_body_util::BodyExt;
+
+    let server = server::http(move |req| async move {
+        assert_eq!(req.headers()["content-encoding"], "br");
+        let body = req.into_body().collect().await.unwrap().to_bytes();
+        let mut decoded = String::new();
+        brotli_crate::Decompressor::new(&body[..], 4096)
+            .read_to_string(&mut decoded)
+            .unwrap();
+        http::Response::new(decoded.into())
+    });
+
+    let content = "compressible ".repeat(1_000);
+    let res = reqwest::Client::new()
+        .post(format!("http://{}/upload", server.addr()))
+        .body(content.clone())
+        .compress(reqwest::Encoding::Brotli)
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(res.text().await.unwrap(), content);
+}
@@ -0,0 +1,206 @@
+#![cfg(not(target_arch = "wasm32"))]
+mod support;
+use support::server;
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use reqwest::cache::{HttpCache, MemoryCache};
+
+#[tokio::test]
+async fn cache_miss_hit_and_revalidation() {
+    let requests = Arc::new(AtomicUsize::new(0));
+    let counter = requests.clone();
+    let server = server::http(move |req| {
+        let n = counter.fetch_add(1, Ordering::SeqCst);
+        async move {
+            let (max_age, revalidated) = match req.uri().path() {
+                "/fresh" => (60, false),
+                _ => (0, req.headers().get("if-none-match").is_some()),
+            };
+            if revalidated {
+                assert_eq!(req.headers()["if-none-match"], "\"v1\"");
+                return http::Response::builder()
+                    .status(304)
+                    .header("cache-control", "max-age=60")
+                    .body(Default::default())
+                    .unwrap();
+            }
+            http::Response::builder()
+                .header("cache-control", format!("max-age={max_age}"))
+                .header("etag", "\"v1\"")
+                .body(format!("response {n}").into())
+                .unwrap()
+        }
+    });
+
+    let cache = Arc::new(MemoryCache::new());
+    let client = reqwest::Client::builder()
+        .cache(cache.clone())
+        .build()
+        .unwrap();
+
+    // a cold miss goes to the server, and is stored
+    let url = format!("http://{}/fresh", server.addr());
+    let res = client.get(&url).send().await.unwrap();
+    assert_eq!(res.text().await.unwrap(), "response 0");
+    assert!(cache.get(&url.parse().unwrap()).is_some());
+
+    // a warm hit doesn't
+    let res = client.get(&url).send().await.unwrap();
+    assert_eq!(res.status(), reqwest::StatusCode::OK);
+    assert_eq!(res.url().as_str(), url);
+    assert_eq!(res.text().await.unwrap(), "response 0");
+    assert_eq!(requests.load(Ordering::SeqCst), 1);
+
+    // a stale entry is revalidated, and a 304 answered with the stored body
+    let url = format!("http://{}/stale", server.addr());
+    let res = client.get(&url).send().await.unwrap();
+    assert_eq!(res.text().await.unwrap(), "response 1");
+    let res = client.get(&url).send().await.unwrap();
+    assert_eq!(res.status(), reqwest::StatusCode::OK);
+    assert_eq!(res.headers()["cache-control"], "max-age=60");
+    assert_eq!(res.text().await.unwrap(), "response 1");
+    assert_eq!(requests.load(Ordering::SeqCst), 3);
+
+    // which made it fresh again
+    let res = client.get(&url).send().await.unwrap();
+    assert_eq!(res.text().await.unwrap(), "response 1");
+    assert_eq!(requests.load(Ordering::SeqCst), 3);
+}
+
+#[tokio::test]
+async fn cache_bypassed_by_no_store() {
+    let requests = Arc::new(AtomicUsize::new(0));
+    let counter = requests.clone();
+    let server = server::http(move |_req| {
+        counter.fetch_add(1, Ordering::SeqCst);
+        async move {
+            http::Response::builder()
+                .header("cache-control", "max-age=60")
+                .body(Default::default())
+                .unwrap()
+        }
+    });
+
+    let client = reqwest::Client::builder()
+        .cache(Arc::new(MemoryCache::new()))
+        .build()
+        .unwrap();
+
+    let url = format!("http://{}/", server.addr());
+    for _ in 0..2 {
+        client
+            .get(&url)
+            .header("cache-control", "no-store")
+            .send()
+            .await
+            .unwrap();
+    }
+    assert_eq!(requests.load(Ordering::SeqCst), 2);
+}
+
+#[tokio::test]
+async fn cache_keeps_private_responses_out() {
+    let server = server::http(move |req| async move {
+        let cache_control = match req.uri().path() {
+            "/private" => "private, max-age=60",
+            "/public" => "public, max-age=60",
+            "/shared" => "s-maxage=60",
+            _ => "max-age=60",
+        };
+        http::Response::builder()
+            .header("cache-control", cache_control)
+            .body(Default::default())
+            .unwrap()
+    });
+
+    let cache = Arc::new(MemoryCache::new());
+    let client = reqwest::Client::builder()
+        .cache(cache.clone())
+        .build()
+        .unwrap();
+
+    let stored = |path: &str| {
+        let url = format!("http://{}{}", server.addr(), path);
+        cache.get(&url.parse().unwrap()).is_some()
+    };
+
+    let url = format!("http://{}/private", server.addr());
+    client.get(&url).send().await.unwrap();
+    assert!(!stored("/private"));
+
+    // a credentialed request's response is only stored if it's public
+    let url = format!("http://{}/authorized", server.addr());
+    client.get(&url).bearer_auth("t").send().await.unwrap();
+    assert!(!stored("/authorized"));
+
+    // s-maxage is for shared caches, so it doesn't make one public
+    let url = format!("http://{}/shared", server.addr());
+    client.get(&url).bearer_auth("t").send().await.unwrap();
+    assert!(!stored("/shared"));
+
+    let url = format!("http://{}/public", server.addr());
+    client.get(&url).bearer_auth("t").send().await.unwrap();
+    assert!(stored("/public"));
+}
+
+#[cfg(feature = "stream")]
+#[tokio::test]
+async fn cache_skips_large_bodies() {
+    let server = server::http(move |_req| async move {
+        let chunks = futures_util::stream::iter(
+            (0..3).map(|_| Ok::<_, std::convert::Infallible>(vec![b'x'; 512 * 1024])),
+        );
+        http::Response::builder()
+            .header("cache-control", "max-age=60")
+            .body(reqwest::Body::wrap_stream(chunks))
+            .unwrap()
+    });
+
+    let cache = Arc::new(MemoryCache::new());
+    let client = reqwest::Client::builder()
+        .cache(cache.clone())
+        .build()
+        .unwrap();
+
+    let url = format!("http://{}/", server.addr());
+    let res = client.get(&url).send().await.unwrap();
+    assert_eq!(res.bytes().await.unwrap().len(), 3 * 512 * 1024);
+    assert!(cache.get(&url.parse().unwrap()).is_none());
+}
+
+#[tokio::test]
+async fn cache_hits_run_interceptors() {
+    use reqwest::interceptor::Interceptor;
+    use std::sync::Mutex;
+
+    struct Log(Arc<Mutex<Vec<String>>>);
+
+    impl Interceptor for Log {
+        fn on_response(&self, res: &reqwest::Response) {
+            self.0.lock().unwrap().push(res.status().to_string());
+        }
+    }
+
+    let server = server::http(move |_req| async move {
+        http::Response::builder()
+            .header("cache-control", "max-age=60")
+            .body("cached".into())
+            .unwrap()
+    });
+
+    let log = Arc::new(Mutex::new(Vec::new()));
+    let client = reqwest::Client::builder()
+        .cache(Arc::new(MemoryCache::new()))
+        .with_interceptor(Arc::new(Log(log.clone())))
+        .build()
+        .unwrap();
+
+    let url = format!("http://{}/", server.addr());
+    for _ in 0..2 {
+        let res = client.get(&url).send().await.unwrap();
+        assert_eq!(res.text().await.unwrap(), "cached");
+    }
+    assert_eq!(*log.lock().unwrap(), ["200 OK", "200 OK"]);
+}
@@ -5,7 +5,6 @@ mod support;
 use support::server;
 
 use http::header::{CONTENT_LENGTH, CONTENT_TYPE, TRANSFER_ENCODING};
-#[cfg(feature = "json")]
 use std::collections::HashMap;
 
 use reqwest::Client;
@@ -85,6 +84,27 @@ async fn donot_set_content_length_0_if_have_no_body() {
     assert_eq!(res.status(), reqwest::StatusCode::OK);
 }
 
+#[tokio::test]
+async fn head_response_has_no_body() {
+    let server = server::http(move |req| async move {
+        assert_eq!(req.method(), "HEAD");
+        http::Response::builder()
+            .header(CONTENT_LENGTH, "1000")
+            .body("x".repeat(1000).into())
+            .unwrap()
+    });
+
+    let res = Client::new()
+        .head(format!("http://{}/", server.addr()))
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(res.content_length(), Some(1000));
+    assert_eq!(res.headers()[CONTENT_LENGTH], "1000");
+    assert!(res.bytes().await.unwrap().is_empty());
+}
+
 #[tokio::test]
 async fn user_agent() {
     let server = server::http(move |req| async move {
@@ -159,6 +179,118 @@ async fn response_json() {
     assert_eq!("Hello", text);
 }
 
+#[tokio::test]
+#[cfg(feature = "json")]
+async fn response_json_or_text() {
+    let server = server::http(move |req| async move {
+        match req.uri().path() {
+            "/json" => http::Response::new("\"Hello\"".into()),
+            _ => http::Response::builder()
+                .status(502)
+                .header("content-type", "text/html")
+                .body("<html><body>Bad Gateway</body></html>".into())
+                .unwrap(),
+        }
+    });
+
+    let client = Client::new();
+
+    let res = client
+        .get(format!("http://{}/json", server.addr()))
+        .send()
+        .await
+        .expect("Failed to get");
+    let json = res.json_or_text::<String>().await.unwrap();
+    assert_eq!(json, Ok("Hello".to_owned()));
+
+    let res = client
+        .get(format!("http://{}/error", server.addr()))
+        .send()
+        .await
+        .expect("Failed to get");
+    let text = res.json_or_text::<String>().await.unwrap();
+    assert_eq!(
+        text,
+        Err("<html><body>Bad Gateway</body></html>".to_owned())
+    );
+}
+
+#[tokio::test]
+async fn default_user_agent_if_unset() {
+    let server = server::http(move |req| async move {
+        let ua = req
+            .headers()
+            .get("user-agent")
+            .map(|ua| ua.to_str().unwrap().to_owned())
+            .unwrap_or_default();
+        http::Response::new(ua.into())
+    });
+    let url = format!("http://{}/ua", server.addr());
+
+    let user_agent = |builder: reqwest::ClientBuilder| {
+        let url = url.clone();
+        async move {
+            let client = builder.build().unwrap();
+            let res = client.get(url).send().await.unwrap();
+            res.text().await.unwrap()
+        }
+    };
+
+    assert_eq!(user_agent(Client::builder()).await, "");
+    assert_eq!(
+        user_agent(Client::builder().default_user_agent_if_unset(true)).await,
+        concat!("reqwest/", env!("CARGO_PKG_VERSION"))
+    );
+    assert_eq!(
+        user_agent(
+            Client::builder()
+                .user_agent("custom-agent")
+                .default_user_agent_if_unset(true)
+        )
+        .await,
+        "custom-agent"
+    );
+    assert_eq!(
+        user_agent(
+            Client::builder()
+                .default_user_agent_if_unset(true)
+                .user_agent("custom-agent")
+        )
+        .await,
+        "custom-agent"
+    );
+}
+
+#[cfg(feature = "stream")]
+#[tokio::test]
+async fn body_channel_streams_chunks_in_order() {
+    use http_body_util::BodyExt;
+
+    let server = server::http(move |req| async move {
+        assert_eq!(req.headers()["transfer-encoding"], "chunked");
+        let full = req.into_body().collect().await.unwrap().to_bytes();
+        http::Response::new(full.into())
+    });
+
+    let (mut tx, body) = reqwest::Body::channel();
+    let producer = tokio::spawn(async move {
+        for i in 0..100 {
+            tx.send(format!("{i},")).await.unwrap();
+        }
+    });
+
+    let res = Client::new()
+        .post(format!("http://{}/upload", server.addr()))
+        .body(body)
+        .send()
+        .await
+        .unwrap();
+    producer.await.unwrap();
+
+    let expected = (0..100).map(|i| format!("{i},")).collect::<String>();
+    assert_eq!(res.text().await.unwrap(), expected);
+}
+
 #[tokio::test]
 async fn body_pipe_response() {
     use http_body_util::BodyExt;
@@ -413,6 +545,88 @@ async fn test_allowed_methods() {
     assert!(resp.is_err());
 }
 
+#[tokio::test]
+async fn https_only_http_allowed_hosts() {
+    let server = server::http(move |req| async move {
+        if req.uri() == "/redirect" {
+            let port = req.headers()["host"]
+                .to_str()
+                .unwrap()
+                .rsplit(':')
+                .next()
+                .unwrap()
+                .to_owned();
+            http::Response::builder()
+                .status(302)
+                .header("location", format!("http://localhost:{port}/ok"))
+                .body(Default::default())
+                .unwrap()
+        } else {
+            http::Response::new("ok".into())
+        }
+    });
+
+    let client = reqwest::Client::builder()
+        .https_only(true)
+        .http_allowed_hosts(vec!["127.0.0.1".into()])
+        .build()
+        .unwrap();
+
+    let res = client
+        .get(format!("http://{}/ok", server.addr()))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(res.text().await.unwrap(), "ok");
+
+    // the redirect target isn't allowed
+    let err = client
+        .get(format!("http://{}/redirect", server.addr()))
+        .send()
+        .await
+        .unwrap_err();
+    assert!(err.is_redirect());
+
+    let client = reqwest::Client::builder()
+        .https_only(true)
+        .http_allowed_hosts(vec!["127.0.0.1".into(), "LOCALHOST".into()])
+        .build()
+        .unwrap();
+
+    let res = client
+        .get(format!("http://{}/redirect", server.addr()))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(res.url().host_str(), Some("localhost"));
+    assert_eq!(res.text().await.unwrap(), "ok");
+}
+
+#[tokio::test]
+async fn https_only_rejects_http_before_connecting() {
+    let mut server = server::http(move |_req| async move {
+        panic!("https_only must not send plaintext requests");
+    });
+
+    let client = reqwest::Client::builder().https_only(true).build().unwrap();
+
+    let err = client
+        .get(format!("http://{}/", server.addr()))
+        .send()
+        .await
+        .unwrap_err();
+    assert!(err.is_builder());
+    assert!(server.events().is_empty());
+
+    // https is let through, and so only fails since the server speaks plain HTTP
+    let err = client
+        .get(format!("https://{}/", server.addr()))
+        .send()
+        .await
+        .unwrap_err();
+    assert!(!err.is_builder());
+}
+
 #[test]
 #[cfg(feature = "json")]
 fn add_json_default_content_type_if_not_set_manually() {
@@ -443,6 +657,50 @@ fn update_json_content_type_if_set_manually() {
     assert_eq!("application/json", req.headers().get(CONTENT_TYPE).unwrap());
 }
 
+#[tokio::test]
+#[cfg(feature = "json")]
+async fn json_sets_default_accept() {
+    let server = server::http(move |req| async move {
+        let accept = req
+            .headers()
+            .get_all("accept")
+            .iter()
+            .map(|value| value.to_str().unwrap())
+            .collect::<Vec<_>>();
+        http::Response::new(accept.join(", ").into())
+    });
+    let url = format!("http://{}/", server.addr());
+
+    let client = Client::new();
+    let res = client.post(&url).json(&"body").send().await.unwrap();
+    assert_eq!(res.text().await.unwrap(), "application/json");
+
+    let res = client
+        .post(&url)
+        .header("accept", "application/vnd.api+json")
+        .json(&"body")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(res.text().await.unwrap(), "application/vnd.api+json");
+
+    // an `Accept` set after the body replaces the default, rather than being
+    // sent alongside it
+    let res = client
+        .post(&url)
+        .json(&"body")
+        .header("accept", "application/vnd.api+json")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(res.text().await.unwrap(), "application/vnd.api+json");
+
+    // opted out, the client's default is sent
+    let client = Client::builder().json_accept(false).build().unwrap();
+    let res = client.post(&url).json(&"body").send().await.unwrap();
+    assert_eq!(res.text().await.unwrap(), "*/*");
+}
+
 #[cfg(all(feature = "__tls", not(feature = "rustls-tls-manual-roots")))]
 #[tokio::test]
 async fn test_tls_info() {
@@ -473,6 +731,60 @@ async fn test_tls_info() {
     assert!(tls_info.is_none());
 }
 
+#[tokio::test]
+async fn with_headers_shares_connection() {
+    let mut server = server::http(move |req| async move {
+        let tenant = req
+            .headers()
+            .get("x-tenant")
+            .map(|v| v.to_str().unwrap().to_owned())
+            .unwrap_or_default();
+        http::Response::new(tenant.into())
+    });
+
+    let mut headers = reqwest::header::HeaderMap::new();
+    headers.insert("x-tenant", "base".parse().unwrap());
+    let client = reqwest::Client::builder()
+        .default_headers(headers.clone())
+        .build()
+        .unwrap();
+
+    headers.insert("x-tenant", "acme".parse().unwrap());
+    let derived = client.with_headers(headers);
+
+    let url = format!("http://{}", server.addr());
+    let res = client.get(&url).send().await.unwrap();
+    assert_eq!(res.text().await.unwrap(), "base");
+    let res = derived.get(&url).send().await.unwrap();
+    assert_eq!(res.text().await.unwrap(), "acme");
+
+    // Both clients used a single pooled connection, which closes once the
+    // last of them is dropped.
+    drop(client);
+    assert!(server.events().is_empty());
+    drop(derived);
+    tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+    assert_eq!(server.events().len(), 1);
+}
+
+#[tokio::test]
+async fn connection_reused() {
+    let server = server::http(move |_| async move { http::Response::default() });
+
+    let client = Client::new();
+    let url = format!("http://{}", server.addr());
+
+    let res = client.get(&url).send().await.unwrap();
+    assert_eq!(res.connection_reused(), Some(false));
+    res.bytes().await.unwrap();
+
+    let res = client.get(&url).send().await.unwrap();
+    assert_eq!(res.connection_reused(), Some(true));
+
+    let res = reqwest::Response::from(http::Response::new("converted"));
+    assert_eq!(res.connection_reused(), None);
+}
+
 #[tokio::test]
 async fn close_connection_after_idle_timeout() {
     let mut server = server::http(move |_| async move { http::Response::default() });
@@ -522,9 +834,1675 @@ async fn http1_reason_phrase() {
     );
 }
 
+#[tokio::test]
+async fn status_error_has_url() {
+    let server = server::http(move |_req| async {
+        http::Response::builder()
+            .status(404)
+            .body(Default::default())
+            .unwrap()
+    });
+
+    let url = format!("http://{}/missing", server.addr());
+    let err = reqwest::get(&url)
+        .await
+        .unwrap()
+        .error_for_status()
+        .unwrap_err();
+
+    assert!(err.is_status());
+    assert_eq!(err.url().map(|u| u.as_str()), Some(url.as_str()));
+}
+
+#[tokio::test]
+async fn connect_error_predicates() {
+    // Bind and drop a listener so that nothing is listening on the port.
+    let addr = std::net::TcpListener::bind("127.0.0.1:0")
+        .unwrap()
+        .local_addr()
+        .unwrap();
+
+    let err = reqwest::get(format!("http://{addr}")).await.unwrap_err();
+
+    assert!(err.is_connect(), "{err:?}");
+    assert!(err.is_request());
+    assert!(!err.is_timeout());
+    #[cfg(feature = "__tls")]
+    assert!(!err.is_tls());
+    assert!(!err.is_body() && !err.is_decode() && !err.is_status() && !err.is_redirect());
+}
+
+#[cfg(any(feature = "default-tls", feature = "__rustls"))]
+#[tokio::test]
+async fn tls_error_predicate() {
+    let server = server::http(move |_| async move { http::Response::default() });
+
+    // The server only speaks plain HTTP, so the handshake fails.
+    let err = reqwest::get(format!("https://{}", server.addr()))
+        .await
+        .unwrap_err();
+
+    assert!(err.is_tls(), "{err:?}");
+    assert!(err.is_connect());
+}
+
 #[tokio::test]
 async fn error_has_url() {
     let u = "http://does.not.exist.local/ever";
     let err = reqwest::get(u).await.unwrap_err();
     assert_eq!(err.url().map(AsRef::as_ref), Some(u), "{err:?}");
 }
+
+#[tokio::test]
+async fn shortcut_methods() {
+    let server = server::http(move |req| async move {
+        let body = req.method().as_str().to_owned();
+        http::Response::new(body.into())
+    });
+
+    let url = format!("http://{}/", server.addr());
+    let builders = [
+        ("POST", reqwest::post(&url).unwrap()),
+        ("PUT", reqwest::put(&url).unwrap()),
+        ("PATCH", reqwest::patch(&url).unwrap()),
+        ("DELETE", reqwest::delete(&url).unwrap()),
+    ];
+    for (method, builder) in builders {
+        let res = builder.send().await.unwrap();
+        assert_eq!(res.text().await.unwrap(), method);
+    }
+
+    let res = reqwest::head(&url).unwrap().send().await.unwrap();
+    assert_eq!(res.status(), reqwest::StatusCode::OK);
+}
+
+#[tokio::test]
+async fn if_range_partial_or_full() {
+    let server = server::http(move |req| async move {
+        assert_eq!(req.headers()["range"], "bytes=5-");
+        if req.headers()["if-range"] == "\"v2\"" {
+            http::Response::builder()
+                .status(206)
+                .header("content-range", "bytes 5-10/11")
+                .body(" world".into())
+                .unwrap()
+        } else {
+            http::Response::new("hello world".into())
+        }
+    });
+
+    let client = Client::new();
+    let url = format!("http://{}/file", server.addr());
+
+    let res = client
+        .get(&url)
+        .header(reqwest::header::RANGE, "bytes=5-")
+        .if_range("\"v2\"")
+        .send()
+        .await
+        .unwrap();
+    assert!(res.is_partial());
+    assert_eq!(res.text().await.unwrap(), " world");
+
+    let res = client
+        .get(&url)
+        .header(reqwest::header::RANGE, "bytes=5-")
+        .if_range("Wed, 21 Oct 2015 07:28:00 GMT")
+        .send()
+        .await
+        .unwrap();
+    assert!(!res.is_partial());
+    assert_eq!(res.text().await.unwrap(), "hello world");
+}
+
+#[tokio::test]
+async fn get_template_encodes_params() {
+    let server = server::http(move |req| async move {
+        assert_eq!(req.method(), "GET");
+        assert_eq!(req.uri(), "/users/a%20b%2Fc%3F/posts?tag=caf%C3%A9%26more");
+        http::Response::default()
+    });
+
+    let template = format!("http://{}/users/{{id}}/posts?tag={{tag}}", server.addr());
+    let params = HashMap::from([("id", "a b/c?"), ("tag", "café&more")]);
+
+    let res = Client::new()
+        .get_template(&template, &params)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(res.status(), reqwest::StatusCode::OK);
+
+    let params = HashMap::from([("id", "1")]);
+    let err = Client::new()
+        .get_template(&template, &params)
+        .send()
+        .await
+        .unwrap_err();
+    assert!(err.is_builder());
+    assert!(format!("{err:?}").contains("`tag`"), "{err:?}");
+
+    // a relative template is resolved against the base URL, like `get`
+    let params = HashMap::from([("id", "a b/c?"), ("tag", "café&more")]);
+    let res = Client::builder()
+        .base_url(format!("http://{}/", server.addr()))
+        .build()
+        .unwrap()
+        .get_template("/users/{id}/posts?tag={tag}", &params)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(res.status(), reqwest::StatusCode::OK);
+}
+
+#[tokio::test]
+async fn error_for_status_with_body_keeps_json_details() {
+    let server = server::http(move |req| async move {
+        if req.uri() == "/bad" {
+            http::Response::builder()
+                .status(400)
+                .header("content-type", "application/json")
+                .body(r#"{"error":"invalid_token"}"#.into())
+                .unwrap()
+        } else {
+            http::Response::new("fine".into())
+        }
+    });
+
+    let client = Client::new();
+
+    let err = client
+        .get(format!("http://{}/bad", server.addr()))
+        .send()
+        .await
+        .unwrap()
+        .error_for_status_with_body()
+        .await
+        .unwrap_err();
+    assert_eq!(err.status(), Some(reqwest::StatusCode::BAD_REQUEST));
+    assert_eq!(
+        err.response_body(),
+        Some(&br#"{"error":"invalid_token"}"#[..])
+    );
+    assert!(err
+        .to_string()
+        .ends_with(r#"/bad): {"error":"invalid_token"}"#));
+
+    let res = client
+        .get(format!("http://{}/ok", server.addr()))
+        .send()
+        .await
+        .unwrap()
+        .error_for_status_with_body()
+        .await
+        .unwrap();
+    assert_eq!(res.text().await.unwrap(), "fine");
+}
+
+#[tokio::test]
+async fn send_streaming_fails_fast_on_error_status() {
+    let server = server::http(move |req| async move {
+        if req.uri() == "/error" {
+            http::Response::builder()
+                .status(500)
+                .body("oops".into())
+                .unwrap()
+        } else {
+            http::Response::new("onetwo".into())
+        }
+    });
+
+    let client = Client::new();
+
+    let err = client
+        .get(format!("http://{}/error", server.addr()))
+        .send_streaming()
+        .await
+        .unwrap_err();
+    assert_eq!(
+        err.status(),
+        Some(reqwest::StatusCode::INTERNAL_SERVER_ERROR)
+    );
+    assert_eq!(err.response_body(), Some(&b"oops"[..]));
+
+    let mut res = client
+        .get(format!("http://{}/stream", server.addr()))
+        .send_streaming()
+        .await
+        .unwrap();
+    let mut body = Vec::new();
+    while let Some(chunk) = res.chunk().await.unwrap() {
+        body.extend_from_slice(&chunk);
+    }
+    assert_eq!(body, b"onetwo");
+}
+
+#[tokio::test]
+async fn conflicting_content_length_rejected() {
+    use reqwest::transport::Transport;
+    use std::future::Future;
+    use std::pin::Pin;
+
+    // hyper's parser rejects such a response before the client sees it, so
+    // it's handed over by a transport instead.
+    struct Conflicting;
+
+    impl Transport for Conflicting {
+        fn send(
+            &self,
+            _req: reqwest::Request,
+        ) -> Pin<Box<dyn Future<Output = reqwest::Result<reqwest::Response>> + Send + '_>> {
+            let res = http::Response::builder()
+                .header(CONTENT_LENGTH, "5")
+                .header(CONTENT_LENGTH, "11")
+                .body("hello world")
+                .unwrap();
+            Box::pin(async move { Ok(res.into()) })
+        }
+    }
+
+    let err = Client::builder()
+        .transport(std::sync::Arc::new(Conflicting))
+        .build()
+        .unwrap()
+        .get("http://example.com/")
+        .send()
+        .await
+        .unwrap_err();
+    assert!(err.is_request());
+    assert_eq!(err.url().unwrap().as_str(), "http://example.com/");
+}
+
+#[tokio::test]
+async fn early_hints_preload_links() {
+    let server = server::low_level_with_response(|_raw_request, client_socket| {
+        Box::new(async move {
+            client_socket
+                .write_all(
+                    b"HTTP/1.1 103 Early Hints\r\n\
+                      Link: </style.css>; rel=preload; as=style\r\n\
+                      Link: </app.js>; rel=preload; as=script, <https://cdn.example>; rel=preconnect\r\n\
+                      \r\n\
+                      HTTP/1.1 200 OK\r\nContent-Length: 5\r\n\r\nhello",
+                )
+                .await
+                .expect("response write_all failed");
+        })
+    });
+
+    let hints = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+    let seen = hints.clone();
+    let client = Client::builder()
+        .on_early_hints(move |links| {
+            seen.lock().unwrap().push(
+                links
+                    .iter()
+                    .map(|link| {
+                        (
+                            link.url().path().to_owned(),
+                            link.as_destination().map(str::to_owned),
+                        )
+                    })
+                    .collect::<Vec<_>>(),
+            );
+        })
+        .build()
+        .unwrap();
+
+    let res = client
+        .get(format!("http://{}/page", server.addr()))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(res.status(), 200);
+    assert_eq!(res.text().await.unwrap(), "hello");
+
+    let hints = hints.lock().unwrap();
+    assert_eq!(
+        *hints,
+        [vec![
+            ("/style.css".to_owned(), Some("style".to_owned())),
+            ("/app.js".to_owned(), Some("script".to_owned())),
+        ]]
+    );
+}
+
+#[tokio::test]
+async fn early_hints_after_redirect() {
+    let target = server::low_level_with_response(|_raw_request, client_socket| {
+        Box::new(async move {
+            client_socket
+                .write_all(
+                    b"HTTP/1.1 103 Early Hints\r\n\
+                      Link: <style.css>; rel=preload; as=style\r\n\
+                      \r\n\
+                      HTTP/1.1 200 OK\r\nContent-Length: 5\r\n\r\nhello",
+                )
+                .await
+                .expect("response write_all failed");
+        })
+    });
+    let location = format!("http://{}/assets/page", target.addr());
+    let redirect = server::http(move |_req| {
+        let location = location.clone();
+        async move {
+            http::Response::builder()
+                .status(302)
+                .header("location", location)
+                .body(Default::default())
+                .unwrap()
+        }
+    });
+
+    let hints = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+    let seen = hints.clone();
+    let client = Client::builder()
+        .on_early_hints(move |links| {
+            let mut seen = seen.lock().unwrap();
+            seen.extend(links.iter().map(|link| link.url().to_string()));
+        })
+        .build()
+        .unwrap();
+
+    let res = client
+        .get(format!("http://{}/start/page", redirect.addr()))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(res.text().await.unwrap(), "hello");
+
+    // resolved against the hop that sent the hints, not the first URL
+    assert_eq!(
+        *hints.lock().unwrap(),
+        [format!("http://{}/assets/style.css", target.addr())]
+    );
+}
+
+#[tokio::test]
+async fn base_url_joins_relative_urls() {
+    let client = Client::builder()
+        .base_url("https://api.example.com")
+        .build()
+        .unwrap();
+
+    let req = client.get("/v1/users").build().unwrap();
+    assert_eq!(req.url().as_str(), "https://api.example.com/v1/users");
+
+    let req = client
+        .post("https://other.example.com/v2/items?page=2")
+        .build()
+        .unwrap();
+    assert_eq!(
+        req.url().as_str(),
+        "https://other.example.com/v2/items?page=2"
+    );
+
+    let server = server::http(move |req| async move {
+        assert_eq!(req.uri(), "/v1/users?active=true");
+        http::Response::default()
+    });
+    let client = Client::builder()
+        .base_url(format!("http://{}/v1/", server.addr()))
+        .build()
+        .unwrap();
+    let res = client.get("users?active=true").send().await.unwrap();
+    assert_eq!(res.status(), 200);
+
+    let err = Client::builder()
+        .base_url("data:text/plain,hello")
+        .build()
+        .unwrap_err();
+    assert!(err.is_builder());
+}
+
+#[tokio::test]
+async fn request_url_pre_parsed() {
+    let server = server::http(move |req| async move {
+        assert_eq!(req.method(), "PUT");
+        assert_eq!(req.uri(), "/items/1");
+        http::Response::default()
+    });
+
+    let url = reqwest::Url::parse(&format!("http://{}/items/1", server.addr())).unwrap();
+    let client = Client::new();
+    let req = client
+        .request_url(reqwest::Method::PUT, url.clone())
+        .build()
+        .unwrap();
+    assert_eq!(req.url(), &url);
+
+    let res = client.execute(req).await.unwrap();
+    assert_eq!(res.status(), 200);
+
+    // unsupported URLs are caught when sending instead
+    let url = reqwest::Url::parse("data:text/plain,hello").unwrap();
+    let err = client
+        .request_url(reqwest::Method::GET, url)
+        .send()
+        .await
+        .unwrap_err();
+    assert!(err.is_builder());
+}
+
+#[tokio::test]
+async fn client_as_tower_service() {
+    use tower::ServiceExt;
+
+    let server = server::http(move |req| async move {
+        assert_eq!(req.uri(), "/svc");
+        http::Response::new("tower".into())
+    });
+
+    let client = Client::new();
+    let req = client
+        .get(format!("http://{}/svc", server.addr()))
+        .build()
+        .unwrap();
+
+    let svc = tower::ServiceBuilder::new()
+        .concurrency_limit(1)
+        .service(client);
+    let res = svc.oneshot(req).await.unwrap();
+    assert_eq!(res.text().await.unwrap(), "tower");
+}
+
+#[tokio::test]
+async fn interceptors_run_in_order() {
+    use reqwest::interceptor::Interceptor;
+    use std::sync::{Arc, Mutex};
+
+    struct Tag {
+        name: &'static str,
+        log: Arc<Mutex<Vec<String>>>,
+    }
+
+    impl Interceptor for Tag {
+        fn on_request(&self, req: &mut reqwest::Request) {
+            req.headers_mut()
+                .append("x-tag", self.name.parse().unwrap());
+            self.log.lock().unwrap().push(format!("req {}", self.name));
+        }
+
+        fn on_response(&self, res: &reqwest::Response) {
+            self.log
+                .lock()
+                .unwrap()
+                .push(format!("res {} {}", self.name, res.status()));
+        }
+    }
+
+    let server = server::http(move |req| async move {
+        let tags = req
+            .headers()
+            .get_all("x-tag")
+            .iter()
+            .map(|v| v.to_str().unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(tags, ["a", "b"]);
+        http::Response::default()
+    });
+
+    let log = Arc::new(Mutex::new(Vec::new()));
+    let client = Client::builder()
+        .with_interceptor(Arc::new(Tag {
+            name: "a",
+            log: log.clone(),
+        }))
+        .with_interceptor(Arc::new(Tag {
+            name: "b",
+            log: log.clone(),
+        }))
+        .build()
+        .unwrap();
+
+    client
+        .get(format!("http://{}/", server.addr()))
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(
+        *log.lock().unwrap(),
+        ["req a", "req b", "res b 200 OK", "res a 200 OK"]
+    );
+}
+
+#[tokio::test]
+async fn request_version_not_downgraded() {
+    let server = server::http(move |_req| async move { http::Response::default() });
+
+    let client = Client::new();
+    let req = client
+        .get(format!("http://{}/", server.addr()))
+        .version(reqwest::Version::HTTP_2)
+        .build()
+        .unwrap();
+    assert_eq!(req.version(), reqwest::Version::HTTP_2);
+
+    // a plain http connection without prior knowledge only speaks HTTP/1
+    let err = client.execute(req).await.unwrap_err();
+    assert!(err.is_request());
+}
+
+#[tokio::test]
+async fn into_url_borrowed_types() {
+    use std::borrow::Cow;
+
+    let server = server::http(move |req| async move {
+        assert_eq!(req.uri(), "/borrowed");
+        http::Response::default()
+    });
+
+    let client = Client::new();
+    let string = format!("http://{}/borrowed", server.addr());
+    let url = reqwest::Url::parse(&string).unwrap();
+
+    let res = client.get(&string).send().await.unwrap();
+    assert_eq!(res.status(), 200);
+    let res = client.get(&url).send().await.unwrap();
+    assert_eq!(res.url(), &url);
+    let res = client.get(Cow::Borrowed(&*string)).send().await.unwrap();
+    assert_eq!(res.status(), 200);
+    let res = client
+        .get(Cow::<str>::Owned(string.clone()))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(res.status(), 200);
+
+    let err = client.get(Cow::Borrowed("not a url")).build().unwrap_err();
+    assert!(err.is_builder());
+}
+
+#[tokio::test]
+async fn response_status_reason() {
+    let server = server::low_level_with_response(|raw_request, client_socket| {
+        Box::new(async move {
+            let reason = if raw_request.starts_with(b"GET /custom") {
+                "Totally Fine"
+            } else {
+                "OK"
+            };
+            let response = format!("HTTP/1.1 200 {reason}\r\nContent-Length: 0\r\n\r\n");
+            client_socket.write_all(response.as_bytes()).await.unwrap();
+        })
+    });
+
+    let client = Client::new();
+    let res = client
+        .get(format!("http://{}/custom", server.addr()))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(res.status_reason(), Some("Totally Fine"));
+    assert!(format!("{res:?}").contains("reason: \"Totally Fine\""));
+
+    let res = client
+        .get(format!("http://{}/", server.addr()))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(res.status_reason(), Some("OK"));
+}
+
+#[tokio::test]
+async fn default_bearer_auth_overridden_per_request() {
+    let server = server::http(move |req| async move {
+        let auth = req.headers()["authorization"].clone();
+        assert!(auth.to_str().unwrap().starts_with("Bearer "));
+        http::Response::new(auth.as_bytes().to_vec().into())
+    });
+
+    let client = Client::builder()
+        .bearer_auth("client-token")
+        .build()
+        .unwrap();
+    let url = format!("http://{}/", server.addr());
+
+    let auth = client.get(&url).send().await.unwrap().text().await.unwrap();
+    assert_eq!(auth, "Bearer client-token");
+
+    let auth = client
+        .get(&url)
+        .bearer_auth("request-token")
+        .send()
+        .await
+        .unwrap()
+        .text()
+        .await
+        .unwrap();
+    assert_eq!(auth, "Bearer request-token");
+
+    let err = Client::builder()
+        .bearer_auth("bad\ntoken")
+        .build()
+        .unwrap_err();
+    assert!(err.is_builder());
+}
+
+#[tokio::test]
+async fn builder_from_inherits_config() {
+    let server = server::http(move |req| async move {
+        if req.uri() == "/slow" {
+            tokio::time::sleep(std::time::Duration::from_millis(500)).await;
+        }
+        assert_eq!(req.headers()["x-team"], "core");
+        http::Response::new(req.headers()["user-agent"].as_bytes().to_vec().into())
+    });
+
+    let mut headers = reqwest::header::HeaderMap::new();
+    headers.insert("x-team", "core".parse().unwrap());
+    let client = Client::builder()
+        .default_headers(headers)
+        .timeout(std::time::Duration::from_millis(100))
+        .user_agent("base/1")
+        .build()
+        .unwrap();
+    let derived = client
+        .builder_from()
+        .user_agent("derived/1")
+        .build()
+        .unwrap();
+
+    let url = format!("http://{}/", server.addr());
+    let ua = client.get(&url).send().await.unwrap().text().await.unwrap();
+    assert_eq!(ua, "base/1");
+    let ua = derived
+        .get(&url)
+        .send()
+        .await
+        .unwrap()
+        .text()
+        .await
+        .unwrap();
+    assert_eq!(ua, "derived/1");
+
+    let err = derived
+        .get(format!("http://{}/slow", server.addr()))
+        .send()
+        .await
+        .unwrap_err();
+    assert!(err.is_timeout());
+}
+
+#[tokio::test]
+async fn builder_from_keeps_with_headers() {
+    let server = server::http(move |req| async move {
+        http::Response::new(req.headers()["x-team"].as_bytes().to_vec().into())
+    });
+
+    let mut headers = reqwest::header::HeaderMap::new();
+    headers.insert("x-team", "core".parse().unwrap());
+    let client = Client::builder().default_headers(headers).build().unwrap();
+
+    let mut headers = reqwest::header::HeaderMap::new();
+    headers.insert("x-team", "tenant".parse().unwrap());
+    let derived = client.with_headers(headers).builder_from().build().unwrap();
+
+    let url = format!("http://{}/", server.addr());
+    let team = derived
+        .get(&url)
+        .send()
+        .await
+        .unwrap()
+        .text()
+        .await
+        .unwrap();
+    assert_eq!(team, "tenant");
+}
+
+#[tokio::test]
+async fn builder_from_gets_own_max_concurrent() {
+    let server = server::http(move |_req| async { http::Response::new("hello".into()) });
+
+    let client = Client::builder().max_concurrent(1).build().unwrap();
+    let derived = client.builder_from().build().unwrap();
+
+    let url = format!("http://{}/", server.addr());
+    // an unread response keeps the parent's only slot taken
+    let _held = client.get(&url).send().await.unwrap();
+    let res = tokio::time::timeout(std::time::Duration::from_secs(5), derived.get(&url).send())
+        .await
+        .expect("derived client waited for the parent's slot");
+    assert_eq!(res.unwrap().status(), 200);
+}
+
+#[tokio::test]
+async fn response_bytes_with_progress() {
+    let body = "x".repeat(256 * 1024);
+    let server = server::http(move |_req| {
+        let body = body.clone();
+        async move { http::Response::new(body.into()) }
+    });
+
+    let mut reported = Vec::new();
+    let bytes = reqwest::get(format!("http://{}/", server.addr()))
+        .await
+        .unwrap()
+        .bytes_with_progress(|received| reported.push(received))
+        .await
+        .unwrap();
+
+    assert_eq!(bytes.len(), 256 * 1024);
+    assert_eq!(reported.last(), Some(&(256 * 1024)));
+    assert!(reported.windows(2).all(|w| w[0] < w[1]), "{reported:?}");
+}
+
+#[tokio::test]
+async fn response_bytes_limited() {
+    let server = server::http(move |_req| async move { http::Response::new("0123456789".into()) });
+    let url = format!("http://{}/", server.addr());
+    let client = Client::new();
+
+    let body = client
+        .get(&url)
+        .send()
+        .await
+        .unwrap()
+        .bytes_limited(10)
+        .await
+        .unwrap();
+    assert_eq!(body, "0123456789");
+
+    let err = client
+        .get(&url)
+        .send()
+        .await
+        .unwrap()
+        .bytes_limited(9)
+        .await
+        .unwrap_err();
+    assert!(err.is_body());
+    let source = std::error::Error::source(&err).unwrap();
+    assert_eq!(
+        source.to_string(),
+        "response body is larger than the limit of 9 bytes"
+    );
+
+    // without a Content-Length, the body is cut off while reading
+    let server = server::low_level_with_response(|_raw_request, client_socket| {
+        Box::new(async move {
+            client_socket
+                .write_all(b"HTTP/1.1 200 OK\r\nTransfer-Encoding: chunked\r\n\r\n5\r\nhello\r\n6\r\n world\r\n0\r\n\r\n")
+                .await
+                .expect("response write_all failed");
+        })
+    });
+    let err = client
+        .get(format!("http://{}/", server.addr()))
+        .send()
+        .await
+        .unwrap()
+        .bytes_limited(8)
+        .await
+        .unwrap_err();
+    assert!(err.is_body());
+}
+
+#[tokio::test]
+async fn default_query_merged() {
+    let server = server::http(move |req| async move {
+        http::Response::new(req.uri().query().unwrap_or("").to_owned().into())
+    });
+
+    let client = Client::builder()
+        .default_query(&[("api_key", "secret"), ("format", "json")])
+        .build()
+        .unwrap();
+    let url = format!("http://{}/search", server.addr());
+
+    let query = client
+        .get(&url)
+        .query(&[("q", "rust lang"), ("format", "xml")])
+        .send()
+        .await
+        .unwrap()
+        .text()
+        .await
+        .unwrap();
+    assert_eq!(query, "q=rust+lang&format=xml&api_key=secret");
+
+    let query = client.get(&url).send().await.unwrap().text().await.unwrap();
+    assert_eq!(query, "api_key=secret&format=json");
+}
+
+#[test]
+fn effective_url_includes_default_query() {
+    let client = Client::builder()
+        .base_url("http://example.com/api/")
+        .default_query(&[("api_key", "secret"), ("format", "json")])
+        .build()
+        .unwrap();
+
+    let req = client
+        .get("search")
+        .query(&[("format", "xml")])
+        .build()
+        .unwrap();
+    assert_eq!(
+        req.url().as_str(),
+        "http://example.com/api/search?format=xml&api_key=secret"
+    );
+}
+
+#[test]
+fn query_pairs_appends_encoded_pairs() {
+    let client = Client::new();
+
+    let tags = ["rust".to_string(), "http & co".to_string()];
+    let req = client
+        .get("http://example.com/search?page=2")
+        .query_pairs(tags.iter().map(|tag| ("tag", tag)))
+        .query_pairs([("q=1", "ünï/côde")])
+        .build()
+        .unwrap();
+    assert_eq!(
+        req.url().query(),
+        Some("page=2&tag=rust&tag=http+%26+co&q%3D1=%C3%BCn%C3%AF%2Fc%C3%B4de")
+    );
+
+    let req = client
+        .get("http://example.com/")
+        .query_pairs(std::iter::empty::<(&str, &str)>())
+        .build()
+        .unwrap();
+    assert_eq!(req.url().as_str(), "http://example.com/");
+}
+
+#[tokio::test]
+async fn api_client_relative_paths() {
+    let server = server::http(move |req| async move {
+        assert_eq!(req.uri(), "/api/v1/users/42?fields=name");
+        assert_eq!(req.headers()["authorization"], "Bearer secret");
+        assert_eq!(req.headers()["x-api-version"], "2");
+        http::Response::default()
+    });
+
+    let mut headers = reqwest::header::HeaderMap::new();
+    headers.insert("x-api-version", "2".parse().unwrap());
+    let api = reqwest::api::ApiClient::builder(format!("http://{}/api/v1", server.addr()))
+        .bearer_auth("secret")
+        .default_headers(headers)
+        .build()
+        .unwrap();
+    assert_eq!(
+        api.base_url().as_str(),
+        format!("http://{}/api/v1/", server.addr())
+    );
+
+    for path in ["users/42?fields=name", "/users/42?fields=name"] {
+        let res = api.get(path).send().await.unwrap();
+        assert_eq!(
+            res.url().as_str(),
+            format!("http://{}/api/v1/users/42?fields=name", server.addr())
+        );
+    }
+
+    // nothing may lead the client's authorization to another host, or out
+    // of the base path
+    for path in [
+        "https://evil.example/x",
+        "//evil.example/x",
+        "/\\evil.example/x",
+        "\\\\evil.example/x",
+        "../x",
+        "users/../../x",
+        "%2e%2e/x",
+        "mailto:someone@example.com",
+    ] {
+        let err = api.get(path).build().unwrap_err();
+        assert!(err.is_builder(), "{path}");
+    }
+
+    let err = reqwest::api::ApiClient::builder("data:text/plain,hi")
+        .build()
+        .unwrap_err();
+    assert!(err.is_builder());
+}
+
+#[cfg(feature = "stream")]
+#[tokio::test]
+async fn response_bytes_stream_size_hint() {
+    use futures_util::{Stream, StreamExt};
+
+    let server = server::http(move |_req| async { http::Response::new("hello".into()) });
+
+    let res = reqwest::get(format!("http://{}/", server.addr()))
+        .await
+        .unwrap();
+    assert_eq!(res.content_length(), Some(5));
+
+    let mut stream = res.bytes_stream();
+    assert_eq!(stream.size_hint(), (1, Some(5)));
+
+    let mut body = Vec::new();
+    while let Some(chunk) = stream.next().await {
+        body.extend_from_slice(&chunk.unwrap());
+    }
+    assert_eq!(body, b"hello");
+    assert_eq!(stream.size_hint(), (0, Some(0)));
+}
+
+#[cfg(feature = "stream")]
+#[tokio::test]
+async fn response_bytes_stream_yields_body_error() {
+    use futures_util::StreamExt;
+
+    let server = server::http(move |_req| async {
+        // a first chunk, then the body fails and the connection is aborted
+        let chunks = futures_util::stream::once(async { Ok("hello") }).chain(
+            futures_util::stream::once(async {
+                tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+                Err(std::io::Error::new(std::io::ErrorKind::Other, "oh no"))
+            }),
+        );
+        http::Response::new(reqwest::Body::wrap_stream(chunks))
+    });
+
+    let url = format!("http://{}/", server.addr());
+    let mut stream = reqwest::get(&url).await.unwrap().bytes_stream();
+
+    assert_eq!(stream.next().await.unwrap().unwrap(), "hello");
+    let err = stream.next().await.unwrap().unwrap_err();
+    assert!(err.is_decode(), "{err:?}");
+    assert_eq!(err.url().map(|u| u.as_str()), Some(url.as_str()));
+
+    // the other streams over the body name the URL too
+    #[cfg(feature = "json")]
+    {
+        let mut stream = reqwest::get(&url)
+            .await
+            .unwrap()
+            .json_stream::<serde_json::Value>();
+        let err = stream.next().await.unwrap().unwrap_err();
+        assert!(err.is_decode(), "{err:?}");
+        assert_eq!(err.url().map(|u| u.as_str()), Some(url.as_str()));
+    }
+}
+
+#[tokio::test]
+async fn response_elapsed() {
+    let server = server::http(move |_req| async {
+        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+        http::Response::default()
+    });
+
+    let res = reqwest::get(format!("http://{}/slow", server.addr()))
+        .await
+        .unwrap();
+
+    let elapsed = res.elapsed();
+    assert!(
+        elapsed >= std::time::Duration::from_millis(100),
+        "{elapsed:?}"
+    );
+    assert!(elapsed < std::time::Duration::from_secs(10), "{elapsed:?}");
+}
+
+#[tokio::test]
+async fn debug_redacts_sensitive_headers() {
+    let server = server::http(move |_req| async {
+        http::Response::builder()
+            .header("set-cookie", "session=secret-cookie")
+            .header("x-visible", "shown")
+            .body(Default::default())
+            .unwrap()
+    });
+
+    let client = Client::new();
+    let req = client
+        .get(format!("http://{}/", server.addr()))
+        .header("authorization", "Bearer secret-token")
+        .header("cookie", "session=secret-cookie")
+        .header("x-visible", "shown")
+        .build()
+        .unwrap();
+
+    let debug = format!("{req:?}");
+    assert!(!debug.contains("secret"), "{debug}");
+    assert!(debug.contains(r#""authorization": Sensitive"#), "{debug}");
+    assert!(debug.contains(r#""x-visible": "shown""#), "{debug}");
+
+    let res = client.execute(req).await.unwrap();
+    let debug = format!("{res:?}");
+    assert!(!debug.contains("secret"), "{debug}");
+    assert!(debug.contains(r#""set-cookie": Sensitive"#), "{debug}");
+    assert!(debug.contains(r#""x-visible": "shown""#), "{debug}");
+}
+
+#[tokio::test]
+async fn debug_redacts_configured_sensitive_headers() {
+    let server = server::http(move |req| async move {
+        assert_eq!(req.headers()["x-api-key"], "secret-key");
+        http::Response::builder()
+            .header("x-api-key", "secret-echo")
+            .header("x-visible", "shown")
+            .body(Default::default())
+            .unwrap()
+    });
+
+    let mut headers = reqwest::header::HeaderMap::new();
+    headers.insert("x-api-key", "secret-key".parse().unwrap());
+    let client = Client::builder()
+        .default_headers(headers)
+        .sensitive_headers([reqwest::header::HeaderName::from_static("x-api-key")])
+        .build()
+        .unwrap();
+
+    let debug = format!("{client:?}");
+    assert!(!debug.contains("secret"), "{debug}");
+    assert!(debug.contains(r#""x-api-key": Sensitive"#), "{debug}");
+
+    let res = client
+        .get(format!("http://{}/", server.addr()))
+        .send()
+        .await
+        .unwrap();
+    let debug = format!("{res:?}");
+    assert!(!debug.contains("secret"), "{debug}");
+    assert!(debug.contains(r#""x-api-key": Sensitive"#), "{debug}");
+    assert!(debug.contains(r#""x-visible": "shown""#), "{debug}");
+}
+
+#[cfg(all(feature = "stream", feature = "json"))]
+#[tokio::test]
+async fn response_json_stream_ndjson() {
+    use futures_util::StreamExt;
+
+    let server = server::http(move |_req| async {
+        let chunks = vec![
+            "{\"id\":1,\"na",
+            "me\":\"a\"}\n{\"id\":2,",
+            "\"name\":\"b\"}\r\n\n{\"id\"",
+            ":3,\"name\":\"c\"}",
+        ];
+        let stream = futures_util::stream::iter(chunks).then(|chunk| async move {
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+            Ok::<_, std::convert::Infallible>(chunk)
+        });
+        http::Response::new(reqwest::Body::wrap_stream(stream))
+    });
+
+    #[derive(serde::Deserialize, Debug, PartialEq)]
+    struct Item {
+        id: u32,
+        name: String,
+    }
+
+    let res = reqwest::get(format!("http://{}/", server.addr()))
+        .await
+        .unwrap();
+    let items = res
+        .json_stream::<Item>()
+        .map(Result::unwrap)
+        .collect::<Vec<_>>()
+        .await;
+
+    let names = items
+        .iter()
+        .map(|item| item.name.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(names, ["a", "b", "c"]);
+    assert_eq!(items[2].id, 3);
+
+    // a value cut off at the end is an error
+    let server =
+        server::http(move |_req| async { http::Response::new("{\"id\":1}\n{\"id\"".into()) });
+    let res = reqwest::get(format!("http://{}/", server.addr()))
+        .await
+        .unwrap();
+    let results = res
+        .json_stream::<serde_json::Value>()
+        .collect::<Vec<_>>()
+        .await;
+    assert_eq!(results.len(), 2);
+    assert!(results[0].is_ok());
+    assert!(results[1].as_ref().unwrap_err().is_decode());
+
+    // a line that never ends stops the stream instead of buffering forever
+    let server = server::http(move |_req| async {
+        let chunk = vec![b'x'; 64 * 1024];
+        let stream = futures_util::stream::repeat(chunk)
+            .take(128)
+            .map(Ok::<_, std::convert::Infallible>);
+        http::Response::new(reqwest::Body::wrap_stream(stream))
+    });
+    let res = reqwest::get(format!("http://{}/", server.addr()))
+        .await
+        .unwrap();
+    let results = res
+        .json_stream::<serde_json::Value>()
+        .collect::<Vec<_>>()
+        .await;
+    assert_eq!(results.len(), 1);
+    assert!(results[0].as_ref().unwrap_err().is_decode());
+}
+
+#[cfg(feature = "sse")]
+#[tokio::test]
+async fn response_sse_events_split_across_chunks() {
+    use futures_util::StreamExt;
+
+    let server = server::http(move |_req| async {
+        let chunks = vec![
+            ": keep-alive\n\nevent: tok",
+            "en\nid: 1\ndata: hel",
+            "lo\ndata: world\n",
+            "\ndata: [DONE]\n\n",
+        ];
+        let stream = futures_util::stream::iter(chunks).then(|chunk| async move {
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+            Ok::<_, std::convert::Infallible>(chunk)
+        });
+        http::Response::builder()
+            .header("content-type", "text/event-stream")
+            .body(reqwest::Body::wrap_stream(stream))
+            .unwrap()
+    });
+
+    let res = reqwest::get(format!("http://{}/", server.addr()))
+        .await
+        .unwrap();
+    let events = res.sse().map(Result::unwrap).collect::<Vec<_>>().await;
+
+    assert_eq!(events.len(), 2);
+    assert_eq!(events[0].event(), "token");
+    assert_eq!(events[0].id(), Some("1"));
+    assert_eq!(events[0].data(), "hello\nworld");
+    assert_eq!(events[1].event(), "message");
+    assert_eq!(events[1].id(), Some("1"));
+    assert_eq!(events[1].data(), "[DONE]");
+}
+
+#[tokio::test]
+async fn remove_header_suppresses_client_default() {
+    let server = server::http(move |req| async move {
+        assert_eq!(req.headers().get("user-agent"), None);
+        assert_eq!(req.headers().get("accept"), None);
+        assert_eq!(req.headers()["x-custom"], "request");
+        http::Response::default()
+    });
+
+    let mut headers = http::HeaderMap::new();
+    headers.insert("x-custom", "default".parse().unwrap());
+    let client = Client::builder()
+        .user_agent("reqwest-test")
+        .default_headers(headers)
+        .build()
+        .unwrap();
+
+    let res = client
+        .get(format!("http://{}/", server.addr()))
+        .header("x-custom", "ignored")
+        .remove_header("user-agent")
+        .remove_header(http::header::ACCEPT)
+        .remove_header("x-custom")
+        .header("x-custom", "request")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(res.status(), reqwest::StatusCode::OK);
+}
+
+#[tokio::test]
+async fn default_header_append_sends_every_value() {
+    let server = server::http(move |req| async move {
+        let accept = req.headers().get_all("accept").iter().collect::<Vec<_>>();
+        let body = if req.uri().path() == "/override" {
+            assert_eq!(accept, ["text/html"]);
+            "override"
+        } else {
+            assert_eq!(accept, ["application/json", "text/plain"]);
+            "appended"
+        };
+        http::Response::new(body.into())
+    });
+
+    let mut headers = http::HeaderMap::new();
+    headers.insert(http::header::ACCEPT, "application/json".parse().unwrap());
+    let client = Client::builder()
+        .default_headers(headers)
+        .default_header_append(http::header::ACCEPT, "text/plain")
+        .build()
+        .unwrap();
+
+    let res = client
+        .get(format!("http://{}/", server.addr()))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(res.text().await.unwrap(), "appended");
+
+    let res = client
+        .get(format!("http://{}/override", server.addr()))
+        .header(http::header::ACCEPT, "text/html")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(res.text().await.unwrap(), "override");
+}
+
+#[tokio::test]
+async fn no_default_headers_skips_client_defaults() {
+    let server = server::http(move |req| async move {
+        assert_eq!(req.headers().get("user-agent"), None);
+        assert_eq!(req.headers().get("accept"), None);
+        assert_eq!(req.headers().get("accept-encoding"), None);
+        assert_eq!(req.headers().get("x-default"), None);
+        assert_eq!(req.headers()["x-custom"], "request");
+        http::Response::default()
+    });
+
+    let mut headers = http::HeaderMap::new();
+    headers.insert("x-default", "default".parse().unwrap());
+    let client = Client::builder()
+        .user_agent("reqwest-test")
+        .default_headers(headers)
+        .build()
+        .unwrap();
+
+    let res = client
+        .get(format!("http://{}/", server.addr()))
+        .header("x-custom", "request")
+        .no_default_headers()
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(res.status(), reqwest::StatusCode::OK);
+}
+
+#[cfg(feature = "stream")]
+#[tokio::test]
+async fn body_from_reader_uploads() {
+    use http_body_util::BodyExt;
+
+    let server = server::http(move |req| async move {
+        let headers = req.headers();
+        if req.uri() == "/sized" {
+            assert_eq!(headers[CONTENT_LENGTH], "11");
+            assert!(headers.get(TRANSFER_ENCODING).is_none());
+        } else {
+            assert!(headers.get(CONTENT_LENGTH).is_none());
+            assert_eq!(headers[TRANSFER_ENCODING], "chunked");
+        }
+        let full = req.into_body().collect().await.unwrap().to_bytes();
+        http::Response::new(full.into())
+    });
+
+    let client = Client::new();
+
+    let reader = std::io::Cursor::new(b"sized body!".to_vec());
+    let res = client
+        .post(format!("http://{}/sized", server.addr()))
+        .body(reqwest::Body::from_reader(reader, Some(11)))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(res.text().await.unwrap(), "sized body!");
+
+    let reader = std::io::Cursor::new(b"streamed body".to_vec());
+    let res = client
+        .post(format!("http://{}/streamed", server.addr()))
+        .body(reqwest::Body::from_reader(reader, None))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(res.text().await.unwrap(), "streamed body");
+}
+
+#[tokio::test]
+async fn conditional_request_not_modified() {
+    let server = server::http(move |req| async move {
+        assert_eq!(req.headers()["if-none-match"], "\"xyzzy\"");
+        assert_eq!(
+            req.headers()["if-modified-since"],
+            "Sun, 06 Nov 1994 08:49:37 GMT"
+        );
+        http::Response::builder()
+            .status(http::StatusCode::NOT_MODIFIED)
+            .body(Default::default())
+            .unwrap()
+    });
+    let url = format!("http://{}/", server.addr());
+    let client = Client::new();
+
+    let last_modified =
+        std::time::SystemTime::UNIX_EPOCH + std::time::Duration::from_secs(784_111_777);
+    let res = client
+        .get(&url)
+        .if_none_match("\"xyzzy\"")
+        .if_modified_since(last_modified)
+        .send()
+        .await
+        .unwrap();
+    assert!(res.is_not_modified());
+
+    // the obsolete RFC 850 format is sent in the preferred one
+    let res = client
+        .get(&url)
+        .if_none_match("\"xyzzy\"")
+        .if_modified_since("Sunday, 06-Nov-94 08:49:37 GMT")
+        .send()
+        .await
+        .unwrap();
+    assert!(res.is_not_modified());
+
+    let err = client
+        .get(&url)
+        .if_modified_since("yesterday")
+        .send()
+        .await
+        .unwrap_err();
+    assert!(err.is_builder());
+}
+
+#[tokio::test]
+async fn rate_limit_throttles_requests() {
+    let server = server::http(move |_req| async { http::Response::default() });
+    let url = format!("http://{}/", server.addr());
+
+    let client = Client::builder()
+        .rate_limit(3, std::time::Duration::from_millis(600))
+        .build()
+        .unwrap();
+
+    // three go out right away, and the fourth waits for a token
+    let start = std::time::Instant::now();
+    for _ in 0..4 {
+        client.get(&url).send().await.unwrap();
+    }
+    assert!(start.elapsed() >= std::time::Duration::from_millis(200));
+
+    // a request whose timeout is before its token fails without waiting
+    let err = client
+        .get(&url)
+        .timeout(std::time::Duration::from_millis(50))
+        .send()
+        .await
+        .unwrap_err();
+    assert!(err.is_timeout());
+}
+
+#[tokio::test]
+async fn rate_limit_token_taken_when_polled() {
+    let server = server::http(move |_req| async { http::Response::default() });
+    let url = format!("http://{}/", server.addr());
+
+    let client = Client::builder()
+        .rate_limit(1, std::time::Duration::from_secs(60))
+        .build()
+        .unwrap();
+
+    // a request that's never polled leaves the only token in the bucket
+    drop(client.get(&url).send());
+    client
+        .get(&url)
+        .timeout(std::time::Duration::from_secs(5))
+        .send()
+        .await
+        .unwrap();
+}
+
+#[test]
+fn send_future_is_send_sync() {
+    fn assert_send_sync<T: Send + Sync>(_: &T) {}
+
+    let client = Client::builder()
+        .max_concurrent(1)
+        .rate_limit(10, std::time::Duration::from_secs(1))
+        .build()
+        .unwrap();
+    let res_fut = client.get("http://127.0.0.1/").send();
+    assert_send_sync(&res_fut);
+}
+
+#[tokio::test]
+async fn max_concurrent_caps_in_flight_requests() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    let in_flight = Arc::new(AtomicUsize::new(0));
+    let most = Arc::new(AtomicUsize::new(0));
+    let (counter, max) = (in_flight.clone(), most.clone());
+    let server = server::http(move |_req| {
+        let (counter, max) = (counter.clone(), max.clone());
+        async move {
+            let now = counter.fetch_add(1, Ordering::SeqCst) + 1;
+            max.fetch_max(now, Ordering::SeqCst);
+            tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+            counter.fetch_sub(1, Ordering::SeqCst);
+            http::Response::new("done".into())
+        }
+    });
+    let url = format!("http://{}/", server.addr());
+
+    let client = Client::builder().max_concurrent(2).build().unwrap();
+    let tasks = (0..6)
+        .map(|_| {
+            let (client, url) = (client.clone(), url.clone());
+            tokio::spawn(async move { client.get(url).send().await?.text().await })
+        })
+        .collect::<Vec<_>>();
+    for task in tasks {
+        assert_eq!(task.await.unwrap().unwrap(), "done");
+    }
+    assert_eq!(most.load(Ordering::SeqCst), 2);
+}
+
+#[cfg(feature = "charset")]
+#[tokio::test]
+async fn response_text_json_charset() {
+    let server = server::http(move |req| async move {
+        let content_type = match req.uri().path() {
+            "/quoted" => "application/json; charset=\"ISO-8859-1\"",
+            "/upper" => "application/json; CHARSET=iso-8859-1",
+            _ => "application/json; charset=iso-8859-1",
+        };
+        http::Response::builder()
+            .header("content-type", content_type)
+            .body(b"{\"name\":\"Jos\xe9\"}"[..].into())
+            .unwrap()
+    });
+
+    for path in ["/", "/quoted", "/upper"] {
+        let url = format!("http://{}{}", server.addr(), path);
+        let text = reqwest::get(&url).await.unwrap().text().await.unwrap();
+        assert_eq!(text, "{\"name\":\"José\"}", "{path}");
+    }
+}
+
+#[tokio::test]
+async fn response_text_strict_and_lossy() {
+    let server = server::http(move |req| async move {
+        let body = match req.uri().path() {
+            "/valid" => &b"\xEF\xBB\xBFvalid \xE2\x9C\x93"[..],
+            _ => &b"caf\xC3 au lait"[..],
+        };
+        http::Response::new(body.into())
+    });
+
+    let url = format!("http://{}/valid", server.addr());
+    let text = reqwest::get(&url)
+        .await
+        .unwrap()
+        .text_strict()
+        .await
+        .unwrap();
+    assert_eq!(text, "valid ✓");
+
+    let url = format!("http://{}/invalid", server.addr());
+    let err = reqwest::get(&url)
+        .await
+        .unwrap()
+        .text_strict()
+        .await
+        .unwrap_err();
+    assert!(err.is_decode());
+
+    let text = reqwest::get(&url)
+        .await
+        .unwrap()
+        .text_lossy()
+        .await
+        .unwrap();
+    assert_eq!(text, "caf\u{FFFD} au lait");
+}
+
+#[cfg(feature = "default-tls")]
+#[test]
+fn native_tls_rejects_tls_1_3_minimum() {
+    let err = reqwest::Client::builder()
+        .min_tls_version(reqwest::tls::Version::TLS_1_3)
+        .build()
+        .unwrap_err();
+    assert!(err.is_builder());
+    let source = std::error::Error::source(&err).unwrap();
+    assert_eq!(
+        source.to_string(),
+        "TLS 1.3 can't be the minimum version with the native-tls backend"
+    );
+}
+
+#[cfg(feature = "__rustls")]
+#[test]
+fn rustls_rejects_unsupported_versions() {
+    let err = reqwest::Client::builder()
+        .use_rustls_tls()
+        .max_tls_version(reqwest::tls::Version::TLS_1_1)
+        .build()
+        .unwrap_err();
+    assert!(err.is_builder());
+    let source = std::error::Error::source(&err).unwrap();
+    assert_eq!(
+        source.to_string(),
+        "rustls supports no TLS version between the minimum and maximum"
+    );
+}
+
+#[tokio::test]
+async fn host_header_overrides_host() {
+    let server = server::http(move |req| async move {
+        assert_eq!(req.headers()["host"], "internal.example");
+        http::Response::default()
+    });
+
+    let res = reqwest::Client::new()
+        .get(format!("http://{}/", server.addr()))
+        .host_header("internal.example")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(res.status(), reqwest::StatusCode::OK);
+
+    // connecting elsewhere while keeping the URL's host
+    let server = server::http(move |req| async move {
+        let host = req.headers()["host"].to_str().unwrap();
+        assert!(host.starts_with("split.test:"), "{host}");
+        http::Response::default()
+    });
+    let res = reqwest::Client::builder()
+        .resolve("split.test", server.addr())
+        .build()
+        .unwrap()
+        .get(format!("http://split.test:{}/", server.addr().port()))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(res.status(), reqwest::StatusCode::OK);
+}
+
+#[tokio::test]
+async fn response_extensions_survive_http_conversion() {
+    #[derive(Clone, Debug, PartialEq)]
+    struct TraceId(u64);
+
+    let server = server::http(move |_req| async { http::Response::new("ok".into()) });
+    let url = format!("http://{}/traced", server.addr());
+
+    let mut res = reqwest::get(&url).await.unwrap();
+    res.extensions_mut().insert(TraceId(7));
+    assert_eq!(res.extensions().get::<TraceId>(), Some(&TraceId(7)));
+    let elapsed = res.elapsed();
+
+    let res: http::Response<reqwest::Body> = res.into();
+    assert_eq!(res.extensions().get::<TraceId>(), Some(&TraceId(7)));
+
+    let res = reqwest::Response::from(res);
+    assert_eq!(res.extensions().get::<TraceId>(), Some(&TraceId(7)));
+    assert_eq!(res.url().as_str(), url);
+    assert_eq!(res.elapsed(), elapsed);
+    assert_eq!(res.text().await.unwrap(), "ok");
+}
+
+#[tokio::test]
+async fn response_url_mut() {
+    let server = server::http(move |_req| async {
+        http::Response::builder()
+            .status(404)
+            .body(Default::default())
+            .unwrap()
+    });
+
+    let mut res = reqwest::get(format!("http://{}/Items/?sid=1", server.addr()))
+        .await
+        .unwrap();
+    let url = res.url_mut();
+    url.set_path("/items");
+    url.set_query(None);
+    let expected = format!("http://{}/items", server.addr());
+    assert_eq!(res.url().as_str(), expected);
+
+    let err = res.error_for_status().unwrap_err();
+    assert_eq!(err.url().map(|u| u.as_str()), Some(expected.as_str()));
+}
+
+#[tokio::test]
+async fn execute_all_preserves_order() {
+    let server = server::http(move |req| async move {
+        tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+        http::Response::new(req.uri().path().to_owned().into())
+    });
+    let closed = std::net::TcpListener::bind("127.0.0.1:0")
+        .unwrap()
+        .local_addr()
+        .unwrap();
+
+    // a batch larger than the limit doesn't wait on its own responses
+    let client = Client::builder().max_concurrent(1).build().unwrap();
+    let requests = vec![
+        client.get(format!("http://{}/one", server.addr())),
+        client.get(format!("http://{closed}/refused")),
+        client.get(format!("http://{}/three", server.addr())),
+    ]
+    .into_iter()
+    .map(|req| req.build().unwrap())
+    .collect();
+
+    let mut results = client.execute_all(requests).await.into_iter();
+    let first = results.next().unwrap().unwrap();
+    assert_eq!(first.text().await.unwrap(), "/one");
+    assert!(results.next().unwrap().unwrap_err().is_connect());
+    let third = results.next().unwrap().unwrap();
+    assert_eq!(third.text().await.unwrap(), "/three");
+    assert!(results.next().is_none());
+}
+
+#[tokio::test]
+async fn send_and_drain_returns_status() {
+    let server = server::http(move |_| async move {
+        http::Response::builder()
+            .status(http::StatusCode::ACCEPTED)
+            .body(vec![b'x'; 64 * 1024].into())
+            .unwrap()
+    });
+
+    let client = Client::new();
+    let url = format!("http://{}", server.addr());
+
+    let status = client.post(&url).send_and_drain().await.unwrap();
+    assert_eq!(status, reqwest::StatusCode::ACCEPTED);
+
+    // the body was read to the end, freeing the connection
+    let res = client.get(&url).send().await.unwrap();
+    assert_eq!(res.connection_reused(), Some(true));
+}
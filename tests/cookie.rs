@@ -201,3 +201,202 @@ async fn cookie_store_path() {
     let url = format!("http://{}/subpath", server.addr());
     client.get(&url).send().await.unwrap();
 }
+
+#[tokio::test]
+async fn cookie_store_other_host() {
+    let server = server::http(move |req| async move {
+        let host = req.headers()["host"].to_str().unwrap().to_owned();
+        if req.uri() == "/" {
+            http::Response::builder()
+                .header("Set-Cookie", "key=val")
+                .body(Default::default())
+                .unwrap()
+        } else if host.starts_with("127.0.0.1") {
+            assert_eq!(req.headers()["cookie"], "key=val");
+            http::Response::default()
+        } else {
+            assert!(host.starts_with("localhost"), "{host}");
+            assert_eq!(req.headers().get("cookie"), None);
+            http::Response::default()
+        }
+    });
+
+    let client = reqwest::Client::builder()
+        .cookie_store(true)
+        .build()
+        .unwrap();
+
+    let url = format!("http://{}/", server.addr());
+    client.get(&url).send().await.unwrap();
+
+    let url = format!("http://{}/2", server.addr());
+    client.get(&url).send().await.unwrap();
+
+    let url = format!("http://localhost:{}/2", server.addr().port());
+    client.get(&url).send().await.unwrap();
+}
+
+#[tokio::test]
+async fn cookie_provider_calls() {
+    use reqwest::header::HeaderValue;
+    use std::sync::{Arc, Mutex};
+
+    #[derive(Default)]
+    struct Recorder {
+        calls: Mutex<Vec<String>>,
+    }
+
+    impl reqwest::cookie::CookieStore for Recorder {
+        fn set_cookies(
+            &self,
+            cookie_headers: &mut dyn Iterator<Item = &HeaderValue>,
+            url: &reqwest::Url,
+        ) {
+            let values = cookie_headers
+                .map(|v| v.to_str().unwrap().to_owned())
+                .collect::<Vec<_>>();
+            self.calls
+                .lock()
+                .unwrap()
+                .push(format!("set {} {}", url.path(), values.join(",")));
+        }
+
+        fn cookies(&self, url: &reqwest::Url) -> Option<HeaderValue> {
+            self.calls
+                .lock()
+                .unwrap()
+                .push(format!("get {}", url.path()));
+            Some(HeaderValue::from_static("from=store"))
+        }
+    }
+
+    let server = server::http(move |req| async move {
+        assert_eq!(req.headers()["cookie"], "from=store");
+        http::Response::builder()
+            .header("Set-Cookie", "key=val")
+            .body(Default::default())
+            .unwrap()
+    });
+
+    let store = Arc::new(Recorder::default());
+    let client = reqwest::Client::builder()
+        .cookie_provider(store.clone())
+        .build()
+        .unwrap();
+
+    let url = format!("http://{}/1", server.addr());
+    client.get(&url).send().await.unwrap();
+
+    assert_eq!(*store.calls.lock().unwrap(), ["get /1", "set /1 key=val"]);
+}
+
+#[tokio::test]
+async fn cookie_store_skipped_with_no_cookies() {
+    let server = server::http(move |req| async move {
+        let cookie = req
+            .headers()
+            .get("cookie")
+            .map(|v| v.to_str().unwrap().to_owned())
+            .unwrap_or_default();
+        match req.uri().path() {
+            "/redirect" => http::Response::builder()
+                .status(302)
+                .header("location", "/echo")
+                .header("Set-Cookie", "redirected=1")
+                .body(Default::default())
+                .unwrap(),
+            "/set" => http::Response::builder()
+                .header("Set-Cookie", "third=party")
+                .body(cookie.into())
+                .unwrap(),
+            _ => http::Response::new(cookie.into()),
+        }
+    });
+
+    let jar = std::sync::Arc::new(reqwest::cookie::Jar::default());
+    let base = format!("http://{}", server.addr());
+    jar.add_cookie_str("key=val", &base.parse().unwrap());
+    let client = reqwest::Client::builder()
+        .cookie_provider(jar)
+        .build()
+        .unwrap();
+
+    let sent = |path: &'static str, no_cookies: bool| {
+        let mut req = client.get(format!("{base}{path}"));
+        if no_cookies {
+            req = req.no_cookies();
+        }
+        async move { req.send().await.unwrap().text().await.unwrap() }
+    };
+
+    assert_eq!(sent("/echo", false).await, "key=val");
+    assert_eq!(sent("/set", true).await, "");
+    assert_eq!(sent("/redirect", true).await, "");
+    // neither Set-Cookie was stored
+    assert_eq!(sent("/echo", false).await, "key=val");
+}
+
+#[tokio::test]
+async fn cookie_store_fetch_credentials_mode() {
+    use reqwest::cookie::Credentials;
+
+    let server = server::http(move |req| async move {
+        let cookie = req
+            .headers()
+            .get("cookie")
+            .map(|v| v.to_str().unwrap().to_owned())
+            .unwrap_or_default();
+        match req.uri().path() {
+            "/cross" => {
+                let port = req.headers()["host"]
+                    .to_str()
+                    .unwrap()
+                    .rsplit(':')
+                    .next()
+                    .unwrap()
+                    .to_owned();
+                http::Response::builder()
+                    .status(302)
+                    .header("location", format!("http://localhost:{port}/echo"))
+                    .body(Default::default())
+                    .unwrap()
+            }
+            "/set" => http::Response::builder()
+                .header("Set-Cookie", "omitted=1")
+                .body(cookie.into())
+                .unwrap(),
+            _ => http::Response::new(cookie.into()),
+        }
+    });
+
+    let jar = std::sync::Arc::new(reqwest::cookie::Jar::default());
+    jar.add_cookie_str(
+        "key=val",
+        &format!("http://127.0.0.1:{}", server.addr().port())
+            .parse()
+            .unwrap(),
+    );
+    jar.add_cookie_str(
+        "other=val",
+        &format!("http://localhost:{}", server.addr().port())
+            .parse()
+            .unwrap(),
+    );
+    let client = reqwest::Client::builder()
+        .cookie_provider(jar)
+        .build()
+        .unwrap();
+
+    let sent = |path: &'static str, credentials: Credentials| {
+        let req = client
+            .get(format!("http://{}{path}", server.addr()))
+            .fetch_credentials_mode(credentials);
+        async move { req.send().await.unwrap().text().await.unwrap() }
+    };
+
+    assert_eq!(sent("/set", Credentials::Omit).await, "");
+    assert_eq!(sent("/echo", Credentials::Include).await, "key=val");
+    assert_eq!(sent("/echo", Credentials::SameOrigin).await, "key=val");
+    assert_eq!(sent("/cross", Credentials::Include).await, "other=val");
+    assert_eq!(sent("/cross", Credentials::SameOrigin).await, "");
+}
@@ -355,3 +355,78 @@ async fn test_chunked_fragmented_response_with_extra_bytes() {
     assert!(err.is_decode());
     assert!(start.elapsed() >= DELAY_BETWEEN_RESPONSE_PARTS - DELAY_MARGIN);
 }
+
+#[tokio::test]
+async fn gzip_request_body() {
+    use http_body_util::BodyExt;
+    use std::io::Read;
+
+    let server = server::http(move |req| async move {
+        assert_eq!(req.headers()["content-encoding"], "gzip");
+        let content_length = req.headers()["content-length"].clone();
+        let body = req.into_body().collect().await.unwrap().to_bytes();
+        assert_eq!(content_length, body.len().to_string());
+        let mut decoded = String::new();
+        flate2::read::GzDecoder::new(&body[..])
+            .read_to_string(&mut decoded)
+            .unwrap();
+        http::Response::new(decoded.into())
+    });
+
+    let content = "compressible ".repeat(1_000);
+    let res = reqwest::Client::new()
+        .post(format!("http://{}/upload", server.addr()))
+        .compress(reqwest::Encoding::Gzip)
+        .body(content.clone())
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(res.text().await.unwrap(), content);
+}
+
+#[tokio::test]
+async fn gzip_large_request_body() {
+    use http_body_util::BodyExt;
+    use std::io::Read;
+
+    let server = server::http(move |req| async move {
+        assert_eq!(req.headers()["content-encoding"], "gzip");
+        let body = req.into_body().collect().await.unwrap().to_bytes();
+        let mut decoded = String::new();
+        flate2::read::GzDecoder::new(&body[..])
+            .read_to_string(&mut decoded)
+            .unwrap();
+        http::Response::new(decoded.into())
+    });
+
+    // large enough to be compressed off the executor
+    let content = "compressible ".repeat(100_000);
+    let res = reqwest::Client::new()
+        .post(format!("http://{}/upload", server.addr()))
+        .compress(reqwest::Encoding::Gzip)
+        .body(content.clone())
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(res.text().await.unwrap(), content);
+}
+
+#[tokio::test]
+async fn gzip_request_streaming_body_is_an_error() {
+    let server = server::http(move |_req| async { http::Response::default() });
+
+    let chunks: Vec<Result<_, std::io::Error>> = vec![Ok("hello")];
+    let err = reqwest::Client::new()
+        .post(format!("http://{}/upload", server.addr()))
+        .body(reqwest::Body::wrap_stream(futures_util::stream::iter(
+            chunks,
+        )))
+        .compress(reqwest::Encoding::Gzip)
+        .send()
+        .await
+        .unwrap_err();
+
+    assert!(err.is_builder());
+}
@@ -56,17 +56,84 @@ async fn text_part() {
     assert_eq!(res.status(), reqwest::StatusCode::OK);
 }
 
+/// A minimal multipart/form-data parser, returning the headers and content
+/// of each part.
+fn parse_multipart(content_type: &str, body: &[u8]) -> Vec<(String, Vec<u8>)> {
+    let boundary = content_type
+        .strip_prefix("multipart/form-data; boundary=")
+        .expect("multipart content type");
+    let body = std::str::from_utf8(body).unwrap();
+    let body = body
+        .strip_suffix(&format!("--{boundary}--\r\n"))
+        .expect("closing delimiter");
+
+    body.split(&format!("--{boundary}\r\n"))
+        .skip(1)
+        .map(|part| {
+            let part = part.strip_suffix("\r\n").expect("part ends with CRLF");
+            let (headers, content) = part.split_once("\r\n\r\n").expect("part headers");
+            (headers.to_owned(), content.as_bytes().to_vec())
+        })
+        .collect()
+}
+
+#[tokio::test]
+async fn parsed_form_parts() {
+    let _ = env_logger::try_init();
+
+    let server = server::http(move |mut req| async move {
+        let ct = req.headers()["content-type"].to_str().unwrap().to_owned();
+
+        let mut full: Vec<u8> = Vec::new();
+        while let Some(item) = req.body_mut().frame().await {
+            full.extend(&*item.unwrap().into_data().unwrap());
+        }
+
+        let parts = parse_multipart(&ct, &full);
+        assert_eq!(parts.len(), 2);
+        assert_eq!(parts[0].0, "Content-Disposition: form-data; name=\"field\"");
+        assert_eq!(parts[0].1, b"value");
+        assert_eq!(
+            parts[1].0,
+            "Content-Disposition: form-data; name=\"file\"; filename=\"a.txt\"\r\n\
+             Content-Type: text/plain"
+        );
+        assert_eq!(parts[1].1, b"file contents");
+
+        http::Response::default()
+    });
+
+    let form = reqwest::multipart::Form::new().text("field", "value").part(
+        "file",
+        reqwest::multipart::Part::bytes(&b"file contents"[..])
+            .file_name("a.txt")
+            .mime_str("text/plain")
+            .unwrap(),
+    );
+    // Each form gets its own random boundary.
+    assert_ne!(form.boundary(), reqwest::multipart::Form::new().boundary());
+
+    let url = format!("http://{}/multipart/parsed", server.addr());
+    let res = reqwest::Client::new()
+        .post(&url)
+        .multipart(form)
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(res.status(), reqwest::StatusCode::OK);
+}
+
 #[cfg(feature = "stream")]
 #[tokio::test]
 async fn stream_part() {
     use futures_util::stream;
-    use std::future;
 
     let _ = env_logger::try_init();
 
-    let stream = reqwest::Body::wrap_stream(stream::once(future::ready(Ok::<_, reqwest::Error>(
-        "part1 part2".to_owned(),
-    ))));
+    let stream = reqwest::Body::wrap_stream(stream::iter(
+        ["part1", " ", "part2"].map(|chunk| Ok::<_, reqwest::Error>(chunk.to_owned())),
+    ));
     let part = reqwest::multipart::Part::stream(stream);
 
     let form = reqwest::multipart::Form::new()
@@ -96,6 +163,8 @@ async fn stream_part() {
         async move {
             assert_eq!(req.method(), "POST");
             assert_eq!(req.headers()["content-type"], ct);
+            // The length of a streamed part is unknown.
+            assert_eq!(req.headers().get("content-length"), None);
             assert_eq!(req.headers()["transfer-encoding"], "chunked");
 
             let full = req.collect().await.unwrap().to_bytes();
@@ -234,3 +303,38 @@ async fn async_impl_file_part() {
     assert_eq!(res.url().as_str(), &url);
     assert_eq!(res.status(), reqwest::StatusCode::OK);
 }
+
+#[cfg(feature = "stream")]
+#[tokio::test]
+async fn async_impl_file_part_guesses_mime() {
+    let part = reqwest::multipart::Part::file("README.md").await.unwrap();
+    let form = reqwest::multipart::Form::new().part("readme", part);
+
+    let ct = format!("multipart/form-data; boundary={}", form.boundary());
+    let server = server::http(move |req| {
+        let ct = ct.clone();
+        async move {
+            assert_eq!(req.headers()["content-type"], ct);
+            let full = req.collect().await.unwrap().to_bytes();
+            let full = String::from_utf8(full.to_vec()).unwrap();
+            assert!(full.contains(
+                "Content-Disposition: form-data; name=\"readme\"; filename=\"README.md\"\r\n\
+                 Content-Type: text/markdown\r\n\r\n"
+            ));
+            http::Response::default()
+        }
+    });
+
+    let res = reqwest::Client::new()
+        .post(format!("http://{}/multipart/4", server.addr()))
+        .multipart(form)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(res.status(), reqwest::StatusCode::OK);
+
+    let err = reqwest::multipart::Part::file("does/not/exist.txt")
+        .await
+        .unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::NotFound);
+}
@@ -443,3 +443,54 @@ async fn tunnel_includes_proxy_auth_with_multiple_proxies() {
     assert_eq!(res.url().as_str(), url);
     assert_eq!(res.status(), reqwest::StatusCode::OK);
 }
+
+#[tokio::test]
+async fn proxy_from_env_honors_no_proxy() {
+    let server = server::http(move |req| {
+        let uri = req.uri().to_string();
+        async move {
+            // the proxy gets the absolute URL, a direct request only the path
+            http::Response::new(uri.into())
+        }
+    });
+
+    // avoid races with other tests that change "http_proxy"
+    let _env_lock = HTTP_PROXY_ENV_MUTEX.lock().await;
+
+    let system_proxy = env::var("http_proxy");
+    let system_no_proxy = env::var("no_proxy");
+    env::set_var("http_proxy", format!("http://{}", server.addr()));
+    env::set_var("no_proxy", "localhost, *.direct.local");
+
+    let client = reqwest::Client::builder()
+        .proxy_from_env()
+        .resolve("api.direct.local", server.addr())
+        .build()
+        .unwrap();
+
+    match system_proxy {
+        Err(_) => env::remove_var("http_proxy"),
+        Ok(proxy) => env::set_var("http_proxy", proxy),
+    }
+    match system_no_proxy {
+        Err(_) => env::remove_var("no_proxy"),
+        Ok(no_proxy) => env::set_var("no_proxy", no_proxy),
+    }
+
+    let proxied = client
+        .get("http://hyper.rs.local/prox")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(proxied.text().await.unwrap(), "http://hyper.rs.local/prox");
+
+    let direct = client
+        .get(format!(
+            "http://api.direct.local:{}/direct",
+            server.addr().port()
+        ))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(direct.text().await.unwrap(), "/direct");
+}
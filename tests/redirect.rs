@@ -541,3 +541,88 @@ async fn test_redirect_301_302_303_empty_payload_headers() {
         );
     }
 }
+
+#[tokio::test]
+async fn test_fetch_redirect_mode_overrides_policy() {
+    use reqwest::redirect::RedirectMode;
+
+    let server = server::http(move |req| async move {
+        if req.uri() == "/start" {
+            http::Response::builder()
+                .status(302)
+                .header("location", "/dst")
+                .body(Body::default())
+                .unwrap()
+        } else {
+            assert_eq!(req.uri(), "/dst");
+            http::Response::default()
+        }
+    });
+
+    let url = format!("http://{}/start", server.addr());
+    let client = reqwest::Client::new();
+
+    let res = client
+        .get(&url)
+        .fetch_redirect_mode(RedirectMode::Manual)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(res.status(), reqwest::StatusCode::FOUND);
+
+    let err = client
+        .get(&url)
+        .fetch_redirect_mode(RedirectMode::Error)
+        .send()
+        .await
+        .unwrap_err();
+    assert!(err.is_redirect());
+
+    let res = reqwest::Client::builder()
+        .redirect(reqwest::redirect::Policy::none())
+        .build()
+        .unwrap()
+        .get(&url)
+        .fetch_redirect_mode(RedirectMode::Follow)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(res.url().path(), "/dst");
+}
+
+#[tokio::test]
+async fn test_redirect_chain() {
+    let server = server::http(move |req| async move {
+        let next = match req.uri().path() {
+            "/first" => "/second",
+            "/second" => "/final",
+            _ => return http::Response::default(),
+        };
+        http::Response::builder()
+            .status(302)
+            .header("location", next)
+            .body(Body::default())
+            .unwrap()
+    });
+
+    let client = reqwest::Client::new();
+    let res = client
+        .get(format!("http://{}/first", server.addr()))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(res.url().path(), "/final");
+    let chain = res
+        .redirect_chain()
+        .iter()
+        .map(|url| url.path())
+        .collect::<Vec<_>>();
+    assert_eq!(chain, ["/first", "/second"]);
+
+    let res = client
+        .get(format!("http://{}/final", server.addr()))
+        .send()
+        .await
+        .unwrap();
+    assert!(res.redirect_chain().is_empty());
+}
@@ -142,3 +142,230 @@ async fn highly_concurrent_requests_to_slow_http2_server_with_low_max_concurrent
 
     server.shutdown().await;
 }
+
+#[tokio::test]
+async fn retry_if_predicate_on_response_header() {
+    let _ = env_logger::try_init();
+    let cnt = Arc::new(AtomicUsize::new(0));
+    let server = server::http(move |_req| {
+        let cnt = cnt.clone();
+        async move {
+            let n = cnt.fetch_add(1, Ordering::Relaxed);
+            // the first two responses ask to be retried
+            http::Response::builder()
+                .header("x-attempt", n.to_string())
+                .header("x-retry", if n < 2 { "yes" } else { "no" })
+                .body(Default::default())
+                .unwrap()
+        }
+    });
+
+    let attempts = Arc::new(std::sync::Mutex::new(Vec::new()));
+    let seen = attempts.clone();
+    let scope = server.addr().ip().to_string();
+    let retries = reqwest::retry::for_host(scope)
+        .no_budget()
+        .retry_if(move |req_rep| {
+            seen.lock().unwrap().push(req_rep.attempt());
+            assert!(req_rep.error().is_none());
+            req_rep.headers().unwrap()["x-retry"] == "yes"
+        });
+
+    let url = format!("http://{}", server.addr());
+    let resp = reqwest::Client::builder()
+        .retry(retries)
+        .build()
+        .unwrap()
+        .get(url)
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(resp.headers()["x-attempt"], "2");
+    // the last attempt used up the default 2 retries, so isn't classified
+    assert_eq!(*attempts.lock().unwrap(), [1, 2]);
+}
+
+fn flaky_server(failures: usize) -> (server::Server, Arc<AtomicUsize>) {
+    let cnt = Arc::new(AtomicUsize::new(0));
+    let counter = cnt.clone();
+    let server = server::http(move |_req| {
+        let cnt = counter.clone();
+        async move {
+            if cnt.fetch_add(1, Ordering::Relaxed) < failures {
+                http::Response::builder()
+                    .status(http::StatusCode::SERVICE_UNAVAILABLE)
+                    .body(Default::default())
+                    .unwrap()
+            } else {
+                http::Response::default()
+            }
+        }
+    });
+    (server, cnt)
+}
+
+#[tokio::test]
+async fn transient_status_retried_with_backoff() {
+    let _ = env_logger::try_init();
+    let (server, cnt) = flaky_server(2);
+
+    let retries = reqwest::retry::for_host(server.addr().ip().to_string())
+        .no_budget()
+        .retry_on_transient_status()
+        .backoff(
+            std::time::Duration::from_millis(100),
+            std::time::Duration::from_secs(1),
+        );
+    let client = reqwest::Client::builder().retry(retries).build().unwrap();
+
+    let start = std::time::Instant::now();
+    let resp = client
+        .get(format!("http://{}", server.addr()))
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(resp.status(), 200);
+    assert_eq!(cnt.load(Ordering::Relaxed), 3);
+    // waits at least half of 100ms, then half of 200ms
+    assert!(start.elapsed() >= std::time::Duration::from_millis(150));
+}
+
+#[tokio::test]
+async fn transient_status_not_retried_for_post() {
+    let _ = env_logger::try_init();
+    let (server, cnt) = flaky_server(1);
+
+    let retries = reqwest::retry::for_host(server.addr().ip().to_string())
+        .no_budget()
+        .retry_on_transient_status();
+    let client = reqwest::Client::builder().retry(retries).build().unwrap();
+    let url = format!("http://{}", server.addr());
+
+    let resp = client.post(&url).body("hello").send().await.unwrap();
+    assert_eq!(resp.status(), 503);
+    assert_eq!(cnt.load(Ordering::Relaxed), 1);
+
+    // unless explicitly allowed
+    let retries = reqwest::retry::for_host(server.addr().ip().to_string())
+        .no_budget()
+        .retry_on_status_any_method([http::StatusCode::SERVICE_UNAVAILABLE]);
+    let client = reqwest::Client::builder().retry(retries).build().unwrap();
+    cnt.store(0, Ordering::Relaxed);
+
+    let resp = client.post(&url).body("hello").send().await.unwrap();
+    assert_eq!(resp.status(), 200);
+    assert_eq!(cnt.load(Ordering::Relaxed), 2);
+}
+
+#[cfg(feature = "stream")]
+#[tokio::test]
+async fn streaming_body_not_retried() {
+    let _ = env_logger::try_init();
+    let (server, cnt) = flaky_server(1);
+
+    let retries = reqwest::retry::for_host(server.addr().ip().to_string())
+        .no_budget()
+        .retry_on_transient_status();
+    let client = reqwest::Client::builder().retry(retries).build().unwrap();
+
+    let body = reqwest::Body::wrap_stream(futures_util::stream::once(async {
+        Ok::<_, std::io::Error>("hello")
+    }));
+    let resp = client
+        .put(format!("http://{}", server.addr()))
+        .body(body)
+        .send()
+        .await
+        .unwrap();
+
+    // the body can't be sent twice, so there was no retry
+    assert_eq!(resp.status(), 503);
+    assert_eq!(cnt.load(Ordering::Relaxed), 1);
+}
+
+fn retry_after_server(headers: Vec<(&'static str, String)>) -> (server::Server, Arc<AtomicUsize>) {
+    let cnt = Arc::new(AtomicUsize::new(0));
+    let counter = cnt.clone();
+    let server = server::http(move |_req| {
+        let cnt = counter.clone();
+        let headers = headers.clone();
+        async move {
+            if cnt.fetch_add(1, Ordering::Relaxed) == 0 {
+                let mut res =
+                    http::Response::builder().status(http::StatusCode::SERVICE_UNAVAILABLE);
+                for (name, value) in headers {
+                    res = res.header(name, value);
+                }
+                res.body(Default::default()).unwrap()
+            } else {
+                http::Response::default()
+            }
+        }
+    });
+    (server, cnt)
+}
+
+async fn elapsed_until_success(
+    server: &server::Server,
+    retries: reqwest::retry::Builder,
+) -> std::time::Duration {
+    let client = reqwest::Client::builder().retry(retries).build().unwrap();
+    let start = std::time::Instant::now();
+    let resp = client
+        .get(format!("http://{}", server.addr()))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 200);
+    start.elapsed()
+}
+
+#[tokio::test]
+async fn retry_after_seconds_honored() {
+    let _ = env_logger::try_init();
+    let (server, cnt) = retry_after_server(vec![("retry-after", "1".to_owned())]);
+
+    let retries = reqwest::retry::for_host(server.addr().ip().to_string())
+        .no_budget()
+        .retry_on_transient_status();
+    let elapsed = elapsed_until_success(&server, retries).await;
+
+    assert_eq!(cnt.load(Ordering::Relaxed), 2);
+    assert!(elapsed >= std::time::Duration::from_secs(1));
+}
+
+#[tokio::test]
+async fn retry_after_http_date_honored() {
+    let _ = env_logger::try_init();
+    // measured against the response's own Date header
+    let (server, cnt) = retry_after_server(vec![
+        ("date", "Wed, 21 Oct 2015 07:28:00 GMT".to_owned()),
+        ("retry-after", "Wed, 21 Oct 2015 07:28:01 GMT".to_owned()),
+    ]);
+
+    let retries = reqwest::retry::for_host(server.addr().ip().to_string())
+        .no_budget()
+        .retry_on_transient_status();
+    let elapsed = elapsed_until_success(&server, retries).await;
+
+    assert_eq!(cnt.load(Ordering::Relaxed), 2);
+    assert!(elapsed >= std::time::Duration::from_secs(1));
+}
+
+#[tokio::test]
+async fn retry_after_capped() {
+    let _ = env_logger::try_init();
+    let (server, cnt) = retry_after_server(vec![("retry-after", "3600".to_owned())]);
+
+    let retries = reqwest::retry::for_host(server.addr().ip().to_string())
+        .no_budget()
+        .retry_on_transient_status()
+        .max_retry_after(std::time::Duration::from_millis(100));
+    let elapsed = elapsed_until_success(&server, retries).await;
+
+    assert_eq!(cnt.load(Ordering::Relaxed), 2);
+    assert!(elapsed >= std::time::Duration::from_millis(100));
+    assert!(elapsed < std::time::Duration::from_secs(10));
+}
@@ -33,6 +33,30 @@ async fn client_timeout() {
     assert_eq!(err.url().map(|u| u.as_str()), Some(url.as_str()));
 }
 
+#[tokio::test]
+async fn huge_timeout_saturates() {
+    let _ = env_logger::try_init();
+
+    let server = server::http(move |_req| async { http::Response::new("Hello".into()) });
+
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_secs(u64::MAX))
+        .read_timeout(Duration::MAX)
+        .no_proxy()
+        .build()
+        .unwrap();
+
+    let url = format!("http://{}/huge", server.addr());
+    let res = client
+        .get(&url)
+        .timeout(Duration::MAX)
+        .send()
+        .await
+        .expect("huge timeout saturates");
+
+    assert_eq!(res.text().await.unwrap(), "Hello");
+}
+
 #[tokio::test]
 async fn request_timeout() {
     let _ = env_logger::try_init();
@@ -65,6 +89,38 @@ async fn request_timeout() {
     assert_eq!(err.url().map(|u| u.as_str()), Some(url.as_str()));
 }
 
+#[tokio::test]
+async fn request_no_timeout_disables_client_timeout() {
+    let _ = env_logger::try_init();
+
+    let server = server::http(move |_req| {
+        async {
+            // delay returning the response
+            tokio::time::sleep(Duration::from_millis(300)).await;
+            http::Response::default()
+        }
+    });
+
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_millis(100))
+        .no_proxy()
+        .build()
+        .unwrap();
+
+    let url = format!("http://{}/slow", server.addr());
+
+    let err = client.get(&url).send().await.unwrap_err();
+    assert!(err.is_timeout());
+
+    let res = client
+        .get(&url)
+        .no_timeout()
+        .send()
+        .await
+        .expect("timeout disabled");
+    assert_eq!(res.status(), reqwest::StatusCode::OK);
+}
+
 #[tokio::test]
 async fn connect_timeout() {
     let _ = env_logger::try_init();
@@ -177,6 +233,7 @@ async fn response_timeout() {
     let err = body.unwrap_err();
 
     assert!(err.is_timeout());
+    assert_eq!(err.url().map(|u| u.as_str()), Some(url.as_str()));
 }
 
 #[tokio::test]
@@ -336,6 +393,56 @@ fn timeout_blocking_request() {
     assert_eq!(err.url().map(|u| u.as_str()), Some(url.as_str()));
 }
 
+#[cfg(feature = "blocking")]
+#[test]
+fn no_timeout_blocking_request() {
+    let _ = env_logger::try_init();
+
+    let client = reqwest::blocking::Client::builder()
+        .timeout(Duration::from_millis(100))
+        .build()
+        .unwrap();
+
+    let server = server::http(move |_req| {
+        async {
+            // delay returning the response
+            tokio::time::sleep(Duration::from_millis(300)).await;
+            http::Response::default()
+        }
+    });
+
+    let url = format!("http://{}/slow", server.addr());
+    let res = client
+        .get(&url)
+        .no_timeout()
+        .send()
+        .expect("timeout disabled");
+
+    assert_eq!(res.status(), reqwest::StatusCode::OK);
+}
+
+#[cfg(feature = "blocking")]
+#[test]
+fn huge_timeout_blocking_request() {
+    let _ = env_logger::try_init();
+
+    let client = reqwest::blocking::Client::builder()
+        .timeout(Duration::from_secs(u64::MAX))
+        .build()
+        .unwrap();
+
+    let server = server::http(move |_req| async { http::Response::new("Hello".into()) });
+
+    let url = format!("http://{}/huge", server.addr());
+    let res = client
+        .get(&url)
+        .timeout(Duration::MAX)
+        .send()
+        .expect("huge timeout saturates");
+
+    assert_eq!(res.text().unwrap(), "Hello");
+}
+
 #[cfg(feature = "blocking")]
 #[test]
 fn connect_timeout_blocking_request() {
@@ -445,3 +552,99 @@ async fn response_body_timeout_forwards_size_hint() {
 
     assert_eq!(res.content_length(), Some(5));
 }
+
+#[tokio::test]
+async fn request_timeout_adjusted_after_build() {
+    let _ = env_logger::try_init();
+
+    let server = server::http(move |_req| {
+        async {
+            // delay returning the response
+            tokio::time::sleep(Duration::from_millis(300)).await;
+            http::Response::default()
+        }
+    });
+
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_millis(100))
+        .no_proxy()
+        .build()
+        .unwrap();
+
+    let url = format!("http://{}/slow", server.addr());
+
+    let (client, req) = client.get(&url).build_split();
+    let mut req = req.unwrap();
+    assert_eq!(req.timeout(), None);
+    *req.timeout_mut() = Some(Duration::from_secs(5));
+    assert_eq!(req.timeout(), Some(&Duration::from_secs(5)));
+    let res = client.execute(req).await.expect("timeout extended");
+    assert_eq!(res.status(), reqwest::StatusCode::OK);
+
+    let mut req = client
+        .get(&url)
+        .timeout(Duration::from_secs(5))
+        .build()
+        .unwrap();
+    *req.timeout_mut() = Some(Duration::from_millis(50));
+    let err = client.execute(req).await.unwrap_err();
+    assert!(err.is_timeout());
+}
+
+#[tokio::test]
+async fn timeout_is_a_deadline_across_redirects() {
+    let _ = env_logger::try_init();
+
+    // three hops, each well within the timeout, but not all together
+    let server = server::http(move |req| async move {
+        tokio::time::sleep(Duration::from_millis(150)).await;
+        let hop: u32 = req.uri().path()[1..].parse().unwrap();
+        if hop < 3 {
+            http::Response::builder()
+                .status(302)
+                .header("location", format!("/{}", hop + 1))
+                .body(Default::default())
+                .unwrap()
+        } else {
+            http::Response::default()
+        }
+    });
+
+    let url = format!("http://{}/1", server.addr());
+    let client = reqwest::Client::builder().no_proxy().build().unwrap();
+
+    let err = client
+        .get(&url)
+        .timeout(Duration::from_millis(350))
+        .send()
+        .await
+        .unwrap_err();
+    assert!(err.is_timeout());
+
+    let deadline = std::time::Instant::now() + Duration::from_millis(350);
+    let err = client
+        .get(&url)
+        .deadline(deadline)
+        .send()
+        .await
+        .unwrap_err();
+    assert!(err.is_timeout());
+
+    // the earlier of a deadline and a timeout wins
+    let err = client
+        .get(&url)
+        .timeout(Duration::from_secs(10))
+        .deadline(std::time::Instant::now() + Duration::from_millis(350))
+        .send()
+        .await
+        .unwrap_err();
+    assert!(err.is_timeout());
+
+    let res = client
+        .get(&url)
+        .deadline(std::time::Instant::now() + Duration::from_secs(10))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(res.url().path(), "/3");
+}
@@ -0,0 +1,136 @@
+#![cfg(not(target_arch = "wasm32"))]
+mod support;
+use support::server;
+
+use std::fmt::{self, Write};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing::{Event, Metadata, Subscriber};
+
+/// Records every span, field and event as a line of text.
+#[derive(Clone, Default)]
+struct Recorder {
+    lines: Arc<Mutex<Vec<String>>>,
+    next_id: Arc<AtomicU64>,
+}
+
+struct Fields<'a>(&'a mut String);
+
+impl Visit for Fields<'_> {
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        let _ = write!(self.0, " {}={:?}", field.name(), value);
+    }
+}
+
+impl Subscriber for Recorder {
+    fn enabled(&self, metadata: &Metadata<'_>) -> bool {
+        metadata.target().starts_with("reqwest")
+    }
+
+    fn new_span(&self, span: &Attributes<'_>) -> Id {
+        let mut line = format!("span {}", span.metadata().name());
+        span.record(&mut Fields(&mut line));
+        self.lines.lock().unwrap().push(line);
+        Id::from_u64(self.next_id.fetch_add(1, Ordering::Relaxed) + 1)
+    }
+
+    fn record(&self, _span: &Id, values: &Record<'_>) {
+        let mut line = "record".to_owned();
+        values.record(&mut Fields(&mut line));
+        self.lines.lock().unwrap().push(line);
+    }
+
+    fn record_follows_from(&self, _span: &Id, _follows: &Id) {}
+
+    fn event(&self, event: &Event<'_>) {
+        let mut line = "event".to_owned();
+        event.record(&mut Fields(&mut line));
+        self.lines.lock().unwrap().push(line);
+    }
+
+    fn enter(&self, _span: &Id) {}
+
+    fn exit(&self, _span: &Id) {}
+}
+
+#[tokio::test]
+async fn request_span_and_redirect_event() {
+    let server = server::http(move |req| async move {
+        if req.uri().path() == "/start" {
+            http::Response::builder()
+                .status(302)
+                .header("location", "/end?token=secret")
+                .body(Default::default())
+                .unwrap()
+        } else {
+            http::Response::default()
+        }
+    });
+
+    let recorder = Recorder::default();
+    let _guard = tracing::subscriber::set_default(recorder.clone());
+
+    let client = reqwest::Client::new();
+    let res = client
+        .get(format!("http://{}/start?token=secret", server.addr()))
+        .bearer_auth("secret")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(res.status(), reqwest::StatusCode::OK);
+
+    let lines = recorder.lines.lock().unwrap().clone();
+    let has = |expected: &str| lines.iter().any(|line| line.contains(expected));
+
+    assert!(has("span request method=GET"), "{lines:#?}");
+    assert!(has(r#"host="127.0.0.1" path="/start""#), "{lines:#?}");
+    assert!(
+        has(r#"status=302 host="127.0.0.1" path="/end""#),
+        "{lines:#?}"
+    );
+    assert!(has("message=following redirect"), "{lines:#?}");
+    assert!(has("record status=200"), "{lines:#?}");
+    assert!(has("elapsed_ms="), "{lines:#?}");
+    assert!(!has("secret"), "{lines:#?}");
+}
+
+#[tokio::test]
+async fn warn_on_unread_body() {
+    let server = server::http(move |_req| async { http::Response::new("body".into()) });
+    let url = format!("http://{}/unread?token=secret", server.addr());
+
+    let recorder = Recorder::default();
+    let _guard = tracing::subscriber::set_default(recorder.clone());
+    let warned = || {
+        recorder
+            .lines
+            .lock()
+            .unwrap()
+            .iter()
+            .any(|line| line.contains("response body dropped unread"))
+    };
+
+    let client = reqwest::Client::builder()
+        .warn_on_unread_body(true)
+        .build()
+        .unwrap();
+
+    let res = client.get(&url).send().await.unwrap();
+    assert_eq!(res.text().await.unwrap(), "body");
+    client.head(&url).send().await.unwrap();
+    assert!(!warned());
+
+    drop(client.get(&url).send().await.unwrap());
+    assert!(warned());
+
+    let lines = recorder.lines.lock().unwrap();
+    let warning = lines
+        .iter()
+        .find(|line| line.contains("dropped unread"))
+        .unwrap();
+    assert!(warning.contains(r#"path="/unread""#), "{warning}");
+    assert!(!warning.contains("secret"), "{warning}");
+}
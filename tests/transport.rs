@@ -0,0 +1,76 @@
+#![cfg(not(target_arch = "wasm32"))]
+
+use std::sync::Arc;
+
+use http::Method;
+use reqwest::test_util::MockTransport;
+
+#[tokio::test]
+async fn mock_transport_follows_redirects() {
+    let mock = Arc::new(
+        MockTransport::builder()
+            .redirect("http://example.com/1", "/2")
+            .redirect("http://example.com/2", "http://example.org/3")
+            .respond("http://example.org/3", 200, "hello")
+            .build(),
+    );
+    let client = reqwest::Client::builder()
+        .transport(mock.clone())
+        .build()
+        .unwrap();
+
+    let res = client.get("http://example.com/1").send().await.unwrap();
+    assert_eq!(res.status(), reqwest::StatusCode::OK);
+    assert_eq!(res.url().as_str(), "http://example.org/3");
+    assert_eq!(res.redirect_chain().len(), 2);
+    assert_eq!(res.text().await.unwrap(), "hello");
+
+    let urls = mock
+        .requests()
+        .into_iter()
+        .map(|(method, url)| {
+            assert_eq!(method, Method::GET);
+            url.to_string()
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        urls,
+        [
+            "http://example.com/1",
+            "http://example.com/2",
+            "http://example.org/3"
+        ]
+    );
+}
+
+#[tokio::test]
+async fn mock_transport_redirect_loop() {
+    let mock = Arc::new(
+        MockTransport::builder()
+            .redirect("http://example.com/a", "/b")
+            .redirect("http://example.com/b", "/a")
+            .build(),
+    );
+    let client = reqwest::Client::builder()
+        .transport(mock.clone())
+        .redirect(reqwest::redirect::Policy::limited(5))
+        .build()
+        .unwrap();
+
+    let err = client.get("http://example.com/a").send().await.unwrap_err();
+    assert!(err.is_redirect());
+    // the first request, and the 5 redirects followed
+    assert_eq!(mock.requests().len(), 6);
+}
+
+#[tokio::test]
+async fn mock_transport_unknown_url() {
+    let mock = MockTransport::builder().build();
+    let client = reqwest::Client::builder()
+        .transport(Arc::new(mock))
+        .build()
+        .unwrap();
+
+    let res = client.get("http://example.com/").send().await.unwrap();
+    assert_eq!(res.status(), reqwest::StatusCode::NOT_FOUND);
+}
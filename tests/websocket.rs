@@ -0,0 +1,141 @@
+#![cfg(not(target_arch = "wasm32"))]
+mod support;
+use support::server;
+
+use http::header::{HeaderMap, SEC_WEBSOCKET_ACCEPT, SEC_WEBSOCKET_KEY};
+use reqwest::websocket::Message;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+
+// The example handshake from RFC 6455.
+const KEY: &str = "dGhlIHNhbXBsZSBub25jZQ==";
+const ACCEPT: &str = "s3pPLMBiTxaQ9kYGzzhZRbK+xOo=";
+
+async fn read_client_frame<R: AsyncRead + Unpin>(io: &mut R) -> (u8, Vec<u8>) {
+    let mut head = [0; 2];
+    io.read_exact(&mut head).await.unwrap();
+    assert_eq!(head[1] & 0x80, 0x80, "client frames must be masked");
+    let len = match head[1] & 0x7F {
+        126 => io.read_u16().await.unwrap() as usize,
+        127 => io.read_u64().await.unwrap() as usize,
+        len => len as usize,
+    };
+    let mut mask = [0; 4];
+    io.read_exact(&mut mask).await.unwrap();
+    let mut payload = vec![0; len];
+    io.read_exact(&mut payload).await.unwrap();
+    for (i, b) in payload.iter_mut().enumerate() {
+        *b ^= mask[i % 4];
+    }
+    (head[0], payload)
+}
+
+async fn write_server_frame<W: AsyncWrite + Unpin>(io: &mut W, head: u8, payload: &[u8]) {
+    assert!(payload.len() < 126);
+    io.write_all(&[head, payload.len() as u8]).await.unwrap();
+    io.write_all(payload).await.unwrap();
+}
+
+/// Returns the server, and a channel receiving whatever the client sent
+/// after its Close frame.
+fn echo_server(
+    accept: &'static str,
+) -> (
+    server::Server,
+    tokio::sync::mpsc::UnboundedReceiver<Vec<u8>>,
+) {
+    let (after_close_tx, after_close_rx) = tokio::sync::mpsc::unbounded_channel();
+    let server = server::http(move |req| {
+        assert_eq!(req.headers()["connection"], "upgrade");
+        assert_eq!(req.headers()["upgrade"], "websocket");
+        assert_eq!(req.headers()["sec-websocket-version"], "13");
+        assert_eq!(req.headers()["sec-websocket-key"], KEY);
+
+        let after_close_tx = after_close_tx.clone();
+        tokio::spawn(async move {
+            let mut io = hyper_util::rt::TokioIo::new(hyper::upgrade::on(req).await.unwrap());
+
+            loop {
+                match read_client_frame(&mut io).await {
+                    // ping, then echo text back in two fragments
+                    (0x81, text) => {
+                        write_server_frame(&mut io, 0x89, b"ping?").await;
+                        assert_eq!(read_client_frame(&mut io).await, (0x8A, b"ping?".to_vec()));
+                        let (a, b) = text.split_at(text.len() / 2);
+                        write_server_frame(&mut io, 0x01, a).await;
+                        write_server_frame(&mut io, 0x80, b).await;
+                    }
+                    (0x88, code) => {
+                        write_server_frame(&mut io, 0x88, &code).await;
+                        let mut rest = Vec::new();
+                        io.read_to_end(&mut rest).await.unwrap();
+                        let _ = after_close_tx.send(rest);
+                        break;
+                    }
+                    frame => panic!("unexpected frame {frame:?}"),
+                }
+            }
+        });
+
+        async move {
+            http::Response::builder()
+                .status(http::StatusCode::SWITCHING_PROTOCOLS)
+                .header(http::header::CONNECTION, "Upgrade")
+                .header(http::header::UPGRADE, "websocket")
+                .header(SEC_WEBSOCKET_ACCEPT, accept)
+                .body(reqwest::Body::default())
+                .unwrap()
+        }
+    });
+    (server, after_close_rx)
+}
+
+fn fixed_key() -> HeaderMap {
+    let mut headers = HeaderMap::new();
+    headers.insert(SEC_WEBSOCKET_KEY, KEY.parse().unwrap());
+    headers
+}
+
+#[tokio::test]
+async fn websocket_echo_text() {
+    let (server, mut after_close) = echo_server(ACCEPT);
+
+    let mut ws = reqwest::Client::new()
+        .get(format!("http://{}/ws", server.addr()))
+        .upgrade()
+        .headers(fixed_key())
+        .send()
+        .await
+        .unwrap()
+        .into_websocket()
+        .await
+        .unwrap();
+
+    for text in ["hello", "wörld"] {
+        ws.send(Message::Text(text.into())).await.unwrap();
+        let echoed = ws.recv().await.unwrap().unwrap();
+        assert_eq!(echoed, Message::Text(text.into()));
+    }
+
+    ws.close().await.unwrap();
+    // the server's reply to the Close frame isn't echoed back
+    assert_eq!(after_close.recv().await.unwrap(), b"");
+}
+
+#[tokio::test]
+async fn websocket_invalid_accept() {
+    let (server, _) = echo_server("not the right key");
+
+    let err = reqwest::Client::new()
+        .get(format!("http://{}/ws", server.addr()))
+        .upgrade()
+        .headers(fixed_key())
+        .send()
+        .await
+        .unwrap()
+        .into_websocket()
+        .await
+        .unwrap_err();
+
+    let source = std::error::Error::source(&err).unwrap();
+    assert_eq!(source.to_string(), "invalid Sec-WebSocket-Accept header");
+}